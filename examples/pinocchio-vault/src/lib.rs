@@ -0,0 +1,119 @@
+//! # Pinocchio Vault Parser - RPCX Package Example
+//!
+//! Parses a fixed-layout Pinocchio account. The program has no Borsh or Anchor
+//! dependency: state is a `#[repr(C)]` struct read straight from account data,
+//! tagged with a one-byte discriminator.
+//!
+//! ```text
+//! offset  size  field
+//! 0       1     discriminator (1 = Vault)
+//! 1       32    owner
+//! 33      8     amount (u64, little-endian)
+//! 41      1     bump
+//! 42      7     padding
+//! ```
+
+use solana_rpcx_sdk::prelude::*;
+use solana_rpcx_sdk::Pubkey;
+use once_cell::sync::Lazy;
+
+/// Replace with your deployed program id
+const VAULT_PROGRAM_ID: &str = "3hqtsL4w6bx3ZESHi7cfQwmU969FcNmm58Hpd2waRwLf";
+
+/// Discriminator byte the program writes at offset 0
+const VAULT_DISCRIMINATOR: u8 = 1;
+
+/// Mirrors the program's state struct byte for byte
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable, Serialize)]
+struct Vault {
+    owner: Pubkey,
+    amount: u64,
+    bump: u8,
+    #[serde(skip)]
+    _padding: [u8; 7],
+}
+
+static PARSER: Lazy<Parser> = Lazy::new(|| {
+    ParserBuilder::new(VAULT_PROGRAM_ID)
+        .register_pinocchio_account::<Vault>("Vault", vec![VAULT_DISCRIMINATOR])
+        .with_metadata(ProgramMetadata {
+            name: Some("Pinocchio Vault".to_string()),
+            program_id: Some(VAULT_PROGRAM_ID.to_string()),
+            project_url: None,
+            version: Some("0.1.0".to_string()),
+        })
+        .build()
+});
+
+struct Component;
+
+impl ProgramParserGuest for Component {
+    fn parse_account(account: SolanaAccount) -> Result<ParsedAccount, ParseError> {
+        PARSER.parse_account(&account)
+    }
+    
+    fn parse_accounts(accounts: Vec<SolanaAccount>) 
+        -> Result<Vec<Result<ParsedAccount, ParseError>>, String> 
+    {
+        Ok(accounts.into_iter().map(|a| PARSER.parse_account(&a)).collect())
+    }
+    
+    fn parse_with_options(account: SolanaAccount, options: ParseOptions) 
+        -> Result<ParsedAccount, ParseError> 
+    {
+        PARSER.parse_account_with_options(&account, &options)
+    }
+    
+    fn parse_instruction(instruction: InstructionData) 
+        -> Result<ParsedInstruction, ParseError> 
+    {
+        PARSER.parse_instruction(&instruction)
+    }
+    
+    fn parse_instructions(instructions: Vec<InstructionData>) 
+        -> Result<Vec<Result<ParsedInstruction, ParseError>>, String> 
+    {
+        Ok(PARSER.parse_instructions(&instructions))
+    }
+    
+    fn can_parse(owner: String, data_preview: Vec<u8>) -> bool {
+        PARSER.can_parse(&owner, &data_preview)
+    }
+    
+    fn get_supported_types() -> Vec<String> {
+        PARSER.get_supported_types()
+    }
+    
+    fn get_program_metadata() -> Option<ProgramMetadata> {
+        PARSER.get_metadata()
+    }
+}
+
+impl AccountsTransformerGuest for Component {
+    fn transform_accounts(_accounts: Vec<SolanaAccount>, _params: String) -> Result<String, String> {
+        Err("Not implemented".to_string())
+    }
+}
+
+impl AccountsTransformerSetupGuest for Component {
+    fn setup() -> TransformerRequest {
+        TransformerRequest::builder()
+            .owner(VAULT_PROGRAM_ID)
+            .build()
+    }
+}
+
+impl TransactionTransformerGuest for Component {
+    fn transform_transaction(_tx: SolanaTransaction, _params: String) -> Result<String, String> {
+        Err("Not implemented".to_string())
+    }
+}
+
+impl ViewFunctionGuest for Component {
+    fn view(_method: String, _params: String) -> Result<String, String> {
+        Err("Not implemented".to_string())
+    }
+}
+
+solana_rpcx_sdk::bindings::export!(Component with_types_in solana_rpcx_sdk::bindings);
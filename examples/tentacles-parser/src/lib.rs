@@ -97,7 +97,8 @@ fn split_wallet_to_json(wallet: &SplitWallet) -> Result<String, String> {
     serde_json::to_string(&json).map_err(|e| e.to_string())
 }
 
-static PARSER: Lazy<Parser> = Lazy::new(|| {
+/// Parser for Tentacles accounts, shared with the integration tests
+pub static PARSER: Lazy<Parser> = Lazy::new(|| {
     ParserBuilder::new(TENTACLES_PROGRAM_ID.to_string())
         .register_anchor_account::<SplitWallet, _>(split_wallet_to_json)
         .with_metadata(ProgramMetadata {
@@ -109,6 +110,23 @@ static PARSER: Lazy<Parser> = Lazy::new(|| {
         .build()
 });
 
+/// Sums balances across the input accounts
+static TRANSFORMER: Lazy<Transformer> = Lazy::new(|| {
+    TransformerBuilder::new()
+        .with_owner_filter(TENTACLES_PROGRAM_ID.to_string())
+        .register_transform(|accounts, _params| {
+            let total_lamports: u64 = accounts.iter().map(|a| a.lamports).sum();
+            
+            let json = serde_json::json!({
+                "accounts": accounts.len(),
+                "total_lamports": total_lamports,
+            });
+            
+            serde_json::to_string(&json).map_err(|e| e.to_string())
+        })
+        .build()
+});
+
 struct Component;
 
 impl ProgramParserGuest for Component {
@@ -125,16 +143,7 @@ impl ProgramParserGuest for Component {
     fn parse_with_options(account: SolanaAccount, options: ParseOptions) 
         -> Result<ParsedAccount, ParseError> 
     {
-        let mut result = PARSER.parse_account(&account)?;
-        
-        if options.pretty_json {
-            let value: serde_json::Value = serde_json::from_str(&result.data)
-                .map_err(|e| ParseError::InvalidData(e.to_string()))?;
-            result.data = serde_json::to_string_pretty(&value)
-                .map_err(|e| ParseError::InvalidData(e.to_string()))?;
-        }
-        
-        Ok(result)
+        PARSER.parse_account_with_options(&account, &options)
     }
     
     fn parse_instruction(instruction: InstructionData) 
@@ -146,7 +155,7 @@ impl ProgramParserGuest for Component {
     fn parse_instructions(instructions: Vec<InstructionData>) 
         -> Result<Vec<Result<ParsedInstruction, ParseError>>, String> 
     {
-        Ok(instructions.into_iter().map(|i| PARSER.parse_instruction(&i)).collect())
+        Ok(PARSER.parse_instructions(&instructions))
     }
     
     fn can_parse(owner: String, data_preview: Vec<u8>) -> bool {
@@ -163,18 +172,16 @@ impl ProgramParserGuest for Component {
 }
 
 impl AccountsTransformerGuest for Component {
-    fn transform_accounts(_accounts: Vec<SolanaAccount>, _params: String) -> Result<String, String> {
-        Err("Not implemented".to_string())
+    fn transform_accounts(accounts: Vec<SolanaAccount>, params: String) -> Result<String, String> {
+        TRANSFORMER.transform(accounts, &params)
     }
 }
 
 impl AccountsTransformerSetupGuest for Component {
     fn setup() -> TransformerRequest {
-        TransformerRequest { 
-            seeds: vec![], 
-            addresses: None, 
-            owner_filter: None 
-        }
+        TransformerRequest::builder()
+            .owner(TENTACLES_PROGRAM_ID)
+            .build()
     }
 }
 
@@ -185,8 +192,11 @@ impl TransactionTransformerGuest for Component {
 }
 
 impl ViewFunctionGuest for Component {
-    fn view(_method: String, _params: String) -> Result<String, String> {
-        Err("Not implemented".to_string())
+    fn view(method: String, _params: String) -> Result<String, String> {
+        match method.as_str() {
+            "type_info" => Ok(PARSER.type_info_json()),
+            _ => Err(format!("Unknown view method: {}", method)),
+        }
     }
 }
 
@@ -0,0 +1,48 @@
+//! Parses a real SplitWallet account (the blob from
+//! `rpcX/examples/debug_discriminator.rs`) through the example parser.
+
+use solana_rpcx_sdk::prelude::*;
+use tentacles::ID as TENTACLES_PROGRAM_ID;
+use tentacles_parser::PARSER;
+
+const SPLIT_WALLET: &[u8] = include_bytes!("fixtures/split_wallet.bin");
+
+fn split_wallet_account() -> SolanaAccount {
+    SolanaAccount {
+        pubkey: "FgH8NKRZ16MRQgogZrdKSMqMhr4gnZSVgHBSU92hMnzh".to_string(),
+        data: SPLIT_WALLET.to_vec(),
+        owner: TENTACLES_PROGRAM_ID.to_string(),
+        lamports: 0,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+#[test]
+fn parses_split_wallet_fixture() {
+    let parsed = PARSER.parse_account(&split_wallet_account()).unwrap();
+    
+    assert_eq!(parsed.account_type, "SplitWallet");
+    assert_eq!(parsed.discriminator.as_deref(), Some(&SPLIT_WALLET[..8]));
+    
+    let json: serde_json::Value = serde_json::from_str(&parsed.data).unwrap();
+    assert_eq!(json["name"], "Scalex-Wallet-Issuer");
+    assert!(json["total_members"].is_u64());
+    
+    let members = json["members"].as_array().unwrap();
+    assert!(!members.is_empty());
+    for member in members {
+        assert!(member["member"].is_string());
+        assert!(member["shares"].is_u64());
+    }
+}
+
+#[test]
+fn rejects_fixture_under_wrong_owner() {
+    let account = SolanaAccount {
+        owner: "11111111111111111111111111111111".to_string(),
+        ..split_wallet_account()
+    };
+    
+    assert!(PARSER.parse_account(&account).is_err());
+}
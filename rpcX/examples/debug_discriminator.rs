@@ -1,8 +1,8 @@
 use anyhow::Result;
+use solana_rpcx_sdk::find_discriminator_source;
 
 fn main() -> Result<()> {
     use base64::{Engine as _, engine::general_purpose};
-    use sha2::{Digest, Sha256};
     
     let base64_data = "1V3mvCjdz0TLk1URUR0AW6lT34eRmA4qTlC2nyoBwDQ0cpc6RzyG06WcuYagBgC7ccpYRLu0gOfDE24pspc0pwtbyZ0NiE2oFAAAAFNjYWxleC1XYWxsZXQtSXNzdWVyxvp6877brTo9ZfNqq8l0MbG75MLS9uDkfKYCA0UvXWHIAAAAAAAAAAMAAAAAAAAAAHAVpwAAAAAAr6LVFgKSaLB5e6BlHo+/1NjGdiyILqvFx97m1kb0reAAAAAAAAAAAPwAAAAAAAAAABcAAAAAAAAAAwAAAAr3soMTKY36twUyWuZDRbuav7jxegx2s2J/KR0Ave4sAQAAAAAAAAAUAAAAAAAAAL3ZkGtHDX/qNkLAD2g/vSqWO0u5gxQZNTNXUciQOZX0WCVqSYyFXpPxs9IqFyCQwfPyXVMICbqvp0D98SF4iYgBAAAAAAAAABQAAAAAAAAAaiYuGtwQav7t6+nKMLRgLUXMwZQyU9va3yJMWkiyCVbSLRPCkObw05aBCQU3vrbTGuTEACp7/mWtnpxEeCQVpsYAAAAAAAAAEwAAAAAAAAA9769YrqLSGssxsDCDK3Bgz563MDY4eioK+rcRRHccKwAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=";
     
@@ -18,41 +18,17 @@ fn main() -> Result<()> {
     }
     println!();
     
-    // Try different possible names
-    let candidates = vec![
-        ("account", "SplitWallet"),
-        ("account", "split_wallet"),
-        ("account", "splitwallet"),
-        ("global", "SplitWallet"),
-        ("state", "SplitWallet"),
-    ];
+    // Guessed type names; case variants and the usual Anchor namespaces are tried too
+    let candidates = ["SplitWallet", "Wallet", "Split"];
     
-    for (namespace, name) in candidates {
-        let computed = compute_discriminator(namespace, name);
-        let preimage = format!("{}:{}", namespace, name);
-        
-        println!("\nPreimage: \"{}\"", preimage);
-        println!("  Bytes: {:?}", computed);
-        println!("  Hex:   {}", hex_string(&computed));
-        
-        // Check if it matches
-        if data.len() >= 8 && &data[0..8] == &computed[..] {
-            println!("match found");
-        }
+    match find_discriminator_source(&data, &candidates, &[]) {
+        Some((namespace, name)) => println!("match found: \"{}:{}\"", namespace, name),
+        None => println!("no candidate matches"),
     }
   
     Ok(())
 }
 
-fn compute_discriminator(namespace: &str, name: &str) -> [u8; 8] {
-    use sha2::{Digest, Sha256};
-    let preimage = format!("{}:{}", namespace, name);
-    let hash = Sha256::digest(preimage.as_bytes());
-    let mut discriminator = [0u8; 8];
-    discriminator.copy_from_slice(&hash[..8]);
-    discriminator
-}
-
 fn hex_string(bytes: &[u8]) -> String {
     bytes.iter()
         .map(|b| format!("{:02x}", b))
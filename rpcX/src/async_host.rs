@@ -0,0 +1,120 @@
+//! Async wasmtime host, for embedding rpcX in an async service
+//!
+//! The `solana-rpcX` binary links the component synchronously, so its
+//! `accounts-db` imports block a runtime thread on every RPC fetch. With the
+//! `async-host` feature this module links the same world with async imports
+//! instead: `get-account` awaits the request, and the guest runs on a fiber
+//! that yields while it waits.
+//!
+//! ```ignore
+//! let engine = rpcx::async_host::engine()?;
+//! let mut linker = Linker::new(&engine);
+//! rpcx::async_host::add_to_linker(&mut linker)?;
+//!
+//! let component = Component::from_file(&engine, "parser.wasm")?;
+//! let mut store = Store::new(&engine, AsyncHostState::new(RpcClient::new(rpc_url)?));
+//! let instance = FullParser::instantiate_async(&mut store, &component, &linker).await?;
+//! let parsed = instance
+//!     .component_solana_rpcx_bindings_program_parser()
+//!     .call_parse_account(&mut store, &account)
+//!     .await?;
+//! ```
+
+use anyhow::Result;
+use wasmtime::component::Linker;
+use wasmtime::{Config, Engine};
+use wasmtime_wasi::{ResourceTable, WasiCtx, WasiCtxBuilder, WasiView};
+use crate::rpc::{RpcAccount, RpcClient};
+
+wasmtime::component::bindgen!({
+    path: "../solana-rpcx-bindings/wit",
+    world: "full-parser",
+    async: true,
+});
+
+use component::solana_rpcx_bindings::types::SolanaAccount;
+
+/// Store data for the async host
+pub struct AsyncHostState {
+    wasi: WasiCtx,
+    table: ResourceTable,
+    rpc: RpcClient,
+}
+
+impl AsyncHostState {
+    /// State fetching accounts through `rpc`, with guest output on stderr
+    pub fn new(rpc: RpcClient) -> Self {
+        Self::with_wasi(rpc, WasiCtxBuilder::new().inherit_stderr().build())
+    }
+
+    /// State with a caller-configured WASI context
+    pub fn with_wasi(rpc: RpcClient, wasi: WasiCtx) -> Self {
+        Self {
+            wasi,
+            table: ResourceTable::new(),
+            rpc,
+        }
+    }
+}
+
+impl WasiView for AsyncHostState {
+    fn ctx(&mut self) -> &mut WasiCtx {
+        &mut self.wasi
+    }
+
+    fn table(&mut self) -> &mut ResourceTable {
+        &mut self.table
+    }
+}
+
+// wasmtime 26's async bindings are `async_trait` traits
+#[async_trait::async_trait]
+impl component::solana_rpcx_bindings::accounts_db::Host for AsyncHostState {
+    async fn get_account(&mut self, pubkey: String) -> Option<SolanaAccount> {
+        self.rpc.fetch_account(&pubkey).await
+            .unwrap_or_else(|e| {
+                eprintln!("Host: get_account failed: {}", e);
+                None
+            })
+            .map(to_wasm_account)
+    }
+
+    async fn get_multiple_accounts(&mut self, pubkeys: Vec<String>) -> Vec<Option<SolanaAccount>> {
+        match self.rpc.fetch_multiple_accounts(&pubkeys).await {
+            Ok(accounts) => accounts.into_iter().map(|a| a.map(to_wasm_account)).collect(),
+            Err(e) => {
+                eprintln!("Host: get_multiple_accounts failed: {}", e);
+                vec![None; pubkeys.len()]
+            }
+        }
+    }
+}
+
+/// An engine with async support and the component model enabled
+pub fn engine() -> Result<Engine> {
+    let mut config = Config::new();
+    config.wasm_component_model(true);
+    config.async_support(true);
+    Engine::new(&config)
+}
+
+/// Add WASI and the async `accounts-db` imports to `linker`
+///
+/// The linker's engine must come from [`engine`] (or otherwise have async
+/// support), and components must be instantiated with `instantiate_async`.
+pub fn add_to_linker(linker: &mut Linker<AsyncHostState>) -> Result<()> {
+    wasmtime_wasi::add_to_linker_async(linker)?;
+    component::solana_rpcx_bindings::accounts_db::add_to_linker(linker, |state: &mut AsyncHostState| state)
+}
+
+/// Convert a fetched account to the guest's type
+pub fn to_wasm_account(account: RpcAccount) -> SolanaAccount {
+    SolanaAccount {
+        pubkey: account.pubkey,
+        data: account.data,
+        owner: account.owner,
+        lamports: account.lamports,
+        executable: account.executable,
+        rent_epoch: account.rent_epoch,
+    }
+}
@@ -0,0 +1,82 @@
+//! Account data read from disk, for parsing without RPC
+
+use anyhow::{Result, anyhow};
+use base64::{Engine as _, engine::general_purpose};
+use solana_rpcx_sdk::hex_to_bytes;
+
+/// Decode an account data file's contents
+///
+/// Files that are entirely base64 (surrounding whitespace and line breaks
+/// allowed, as in a copied `getAccountInfo` response) are decoded; anything
+/// else is taken as the raw account bytes. Raw data that happens to be valid
+/// base64 text would be misread, but real account data is rarely all
+/// printable ASCII of a length divisible by four.
+pub fn decode_account_data(contents: Vec<u8>) -> Vec<u8> {
+    let text: Vec<u8> = contents.iter()
+        .copied()
+        .filter(|b| !b.is_ascii_whitespace())
+        .collect();
+    
+    let looks_base64 = !text.is_empty()
+        && text.len().is_multiple_of(4)
+        && text.iter().all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'/' | b'='));
+    
+    if !looks_base64 {
+        return contents;
+    }
+    
+    general_purpose::STANDARD.decode(&text).unwrap_or(contents)
+}
+
+/// Decode account data given as hex, e.g. from Solana Explorer or program logs
+///
+/// An optional `0x` prefix and any whitespace are ignored.
+pub fn decode_hex_data(hex: &str) -> Result<Vec<u8>> {
+    let digits: String = hex.split_whitespace().collect();
+    hex_to_bytes(&digits).map_err(|e| anyhow!(e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn base64_with_line_breaks_is_decoded() {
+        let contents = b"  AQID\nBAUG\r\nBw==\n".to_vec();
+        
+        assert_eq!(decode_account_data(contents), vec![1, 2, 3, 4, 5, 6, 7]);
+    }
+    
+    #[test]
+    fn raw_bytes_are_kept() {
+        let contents = vec![0, 159, 146, 150, 255, 10];
+        
+        assert_eq!(decode_account_data(contents.clone()), contents);
+    }
+    
+    #[test]
+    fn text_that_is_not_base64_is_kept() {
+        // Wrong length for base64, and a character outside its alphabet
+        for contents in [b"AQI".to_vec(), b"AQ-D".to_vec(), b"AQ=D".to_vec()] {
+            assert_eq!(decode_account_data(contents.clone()), contents);
+        }
+    }
+    
+    #[test]
+    fn empty_and_blank_files_are_kept() {
+        assert_eq!(decode_account_data(Vec::new()), Vec::<u8>::new());
+        assert_eq!(decode_account_data(b" \n".to_vec()), b" \n".to_vec());
+    }
+    
+    #[test]
+    fn hex_prefix_and_whitespace_are_ignored() {
+        assert_eq!(decode_hex_data("0x0102 ff\n10").unwrap(), vec![1, 2, 255, 16]);
+        assert_eq!(decode_hex_data("  AbCd  ").unwrap(), vec![0xab, 0xcd]);
+    }
+    
+    #[test]
+    fn malformed_hex_is_an_error() {
+        assert!(decode_hex_data("0x123").is_err());
+        assert!(decode_hex_data("zz").is_err());
+    }
+}
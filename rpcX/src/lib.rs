@@ -0,0 +1,10 @@
+//! rpcX host library
+//!
+//! Pieces of the host that are useful outside the `solana-rpcX` binary.
+
+#[cfg(feature = "async-host")]
+pub mod async_host;
+pub mod input;
+pub mod output;
+pub mod rpc;
+pub mod stats;
@@ -1,20 +1,41 @@
 // Complete example: Fetch from RPC, decode base64, parse with WASM component
 
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
 use anyhow::{Result, anyhow};
+use clap::{ArgGroup, Parser};
 use wasmtime::component::*;
 use wasmtime::{Config, Engine, Store};
 use wasmtime_wasi::{ResourceTable, WasiCtx, WasiCtxBuilder, WasiView};
-use serde_json::json;
-use base64::{Engine as _, engine::general_purpose};
+use rpcx::input;
+use rpcx::output::{Emitter, Format, Outcome};
+use rpcx::rpc::{RpcAccount, RpcClient, RpcConfig, resolve_lookup_tables};
+use rpcx::stats::{BatchStats, ParseCost};
+use solana_rpcx_bindings::{CLOSED_ACCOUNT_PREFIX, error_code};
+use solana_rpcx_sdk::{
+    MAX_DISCRIMINATOR_LEN, OutputOptions,
+    inner_instructions_from_meta, loaded_addresses_from_meta, transaction_records,
+};
 
 wasmtime::component::bindgen!({
     path: "../solana-rpcx-bindings/wit",
     world: "full-parser",
 });
 
+/// Interfaces the `full-parser` world requires a component to export
+const REQUIRED_EXPORTS: [&str; 5] = [
+    "component:solana-rpcx-bindings/program-parser",
+    "component:solana-rpcx-bindings/accounts-transformer",
+    "component:solana-rpcx-bindings/accounts-transformer-setup",
+    "component:solana-rpcx-bindings/transaction-transformer",
+    "component:solana-rpcx-bindings/view-function",
+];
+
 struct HostState {
     wasi: WasiCtx,
     table: ResourceTable,
+    rpc: RpcClient,
 }
 
 impl WasiView for HostState {
@@ -32,24 +53,121 @@ impl component::solana_rpcx_bindings::accounts_db::Host for HostState {
         &mut self,
         pubkey: String,
     ) -> Option<component::solana_rpcx_bindings::types::SolanaAccount> {
-        println!("Host: get_account called for {}", pubkey);
-        None
+        eprintln!("Host: get_account called for {}", pubkey);
+        
+        block_on(self.rpc.fetch_account(&pubkey))
+            .unwrap_or_else(|e| {
+                eprintln!("Host: get_account failed: {}", e);
+                None
+            })
+            .map(to_wasm_account)
     }
     
     fn get_multiple_accounts(
         &mut self,
         pubkeys: Vec<String>,
     ) -> Vec<Option<component::solana_rpcx_bindings::types::SolanaAccount>> {
-        vec![None; pubkeys.len()]
+        match block_on(self.rpc.fetch_multiple_accounts(&pubkeys)) {
+            Ok(accounts) => accounts.into_iter().map(|a| a.map(to_wasm_account)).collect(),
+            Err(e) => {
+                eprintln!("Host: get_multiple_accounts failed: {}", e);
+                vec![None; pubkeys.len()]
+            }
+        }
     }
 }
 
+/// Fetch Solana accounts over RPC and parse them with a WASM component
+#[derive(Parser)]
+#[command(group(ArgGroup::new("local_data").args(["data_file", "data_hex"])))]
+struct Args {
+    /// Account to fetch and parse
+    #[arg(long, env = "ACCOUNT_ADDRESS", default_value = "FgH8NKRZ16MRQgogZrdKSMqMhr4gnZSVgHBSU92hMnzh")]
+    account: String,
+    
+    /// Parse every account owned by this program (getProgramAccounts) instead of `--account`
+    #[arg(long, conflicts_with = "local_data")]
+    program: Option<String>,
+    
+    /// With `--program`, fetch only each account's discriminator first, classify
+    /// them, then fetch full data for the accounts the parser recognizes
+    #[arg(long, requires = "program")]
+    classify_first: bool,
+    
+    /// With `--classify-first`, only fetch and parse these account types
+    #[arg(long, value_delimiter = ',', requires = "classify_first")]
+    types: Vec<String>,
+    
+    /// Parse account data from this file (raw bytes or base64) instead of fetching it;
+    /// the account is labelled with `--account`
+    #[arg(long, requires = "owner")]
+    data_file: Option<PathBuf>,
+    
+    /// Parse this hex account data (`0x` prefix and whitespace allowed) instead of
+    /// fetching it; the account is labelled with `--account`
+    #[arg(long, requires = "owner")]
+    data_hex: Option<String>,
+    
+    /// Owner program of the `--data-file` or `--data-hex` account
+    #[arg(long, requires = "local_data")]
+    owner: Option<String>,
+    
+    /// RPC endpoint; repeat or comma-separate to fail over to the next on errors
+    #[arg(long, env = "RPC_URL", value_delimiter = ',', default_value = "https://mainnet.helius-rpc.com")]
+    rpc_url: Vec<String>,
+    
+    /// Timeout for each RPC request, in seconds
+    #[arg(long, default_value_t = 30)]
+    rpc_timeout: u64,
+    
+    /// User-Agent header for RPC requests
+    #[arg(long)]
+    user_agent: Option<String>,
+    
+    /// Parser component to load
+    #[arg(long, default_value = "../target/wasm32-wasip1/release/tentacles_parser.wasm")]
+    component: PathBuf,
+    
+    /// Output format; `json` and `ndjson` keep stdout free of progress messages
+    #[arg(long, value_enum, default_value_t = Format::Pretty)]
+    format: Format,
+    
+    /// Report wall-clock time and fuel for each parse, and totals at the end
+    #[arg(long)]
+    stats: bool,
+    
+    /// Print the component's registered types and discriminators as JSON, then exit
+    #[arg(long)]
+    list_types: bool,
+    
+    /// Parse the instructions of this base64 transaction (legacy or v0) and print
+    /// one record per instruction in --format; lookup tables are fetched over RPC
+    #[arg(long, conflicts_with_all = ["program", "local_data"])]
+    transaction: Option<String>,
+    
+    /// Fetch this transaction (getTransaction) and parse its instructions, including
+    /// inner instructions (CPIs) from its status meta
+    #[arg(long, conflicts_with_all = ["program", "local_data", "transaction"])]
+    signature: Option<String>,
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
+    let args = Args::parse();
+    let mut emitter = Emitter::new(args.format, std::io::stdout().lock());
+    
+    // One client for every fetch, so connections and TLS sessions are reused
+    let mut rpc_config = RpcConfig { timeout: Duration::from_secs(args.rpc_timeout), ..Default::default() };
+    if let Some(user_agent) = &args.user_agent {
+        rpc_config.user_agent = user_agent.clone();
+    }
+    let rpc = RpcClient::with_endpoints(&args.rpc_url, &rpc_config)?;
   
-    println!("Setting up WASM runtime...");
+    emitter.status("Setting up WASM runtime...");
     let mut config = Config::new();
     config.wasm_component_model(true);
+    // Fuel metering slows the guest down a little, so only pay for it when asked
+    config.consume_fuel(args.stats);
     let engine = Engine::new(&config)?;
     
     let mut linker = Linker::new(&engine);
@@ -59,180 +177,348 @@ async fn main() -> Result<()> {
         |state: &mut HostState| state
     )?;
     
-    let component = Component::from_file(
-        &engine, 
-        "../target/wasm32-wasip1/release/tentacles_parser.wasm"
-    )?;
+    let component = Component::from_file(&engine, &args.component)?;
+    preflight(&engine, &component, &args.component)?;
     
-    let wasi = WasiCtxBuilder::new().inherit_stdio().build();
+    // Guest output would interleave with results, so only inherit it for people
+    let wasi = if args.format.is_machine() {
+        WasiCtxBuilder::new().inherit_stderr().build()
+    } else {
+        WasiCtxBuilder::new().inherit_stdio().build()
+    };
     let state = HostState { 
         wasi,
         table: ResourceTable::new(),
+        rpc: rpc.clone(),
     };
     let mut store = Store::new(&engine, state);
+    if args.stats {
+        store.set_fuel(u64::MAX)?;
+    }
     
     let instance = FullParser::instantiate(&mut store, &component, &linker)?;
     let parser = instance.component_solana_rpcx_bindings_program_parser();
-    println!("WASM component ready\n");
-    
-    println!("Fetching account from Solana RPC...");
-    
-    // You can replace this with any Tentacles SplitWallet address
-    let account_pubkey = std::env::var("ACCOUNT_ADDRESS")
-        .unwrap_or_else(|_| "FgH8NKRZ16MRQgogZrdKSMqMhr4gnZSVgHBSU92hMnzh".to_string());
-    
-    let rpc_url = std::env::var("RPC_URL")
-        .unwrap_or_else(|_| "https://mainnet.helius-rpc.com".to_string());
+    emitter.status("WASM component ready\n");
+    
+    if args.list_types {
+        let type_info = instance.component_solana_rpcx_bindings_view_function()
+            .call_view(&mut store, "type_info", "")?
+            .map_err(|e| anyhow!("Component does not report type info: {}", e))?;
+        println!("{}", type_info);
+        return Ok(());
+    }
     
-    println!("  RPC: https://mainnet.helius-rpc.com");
-    println!("  Account: {}\n", account_pubkey);
+    if let Some(encoded) = &args.transaction {
+        let records = parse_transaction(encoded, None, &rpc, parser, &mut store, &emitter).await?;
+        return emit_records(emitter, records);
+    }
     
-    // Fetch account info
-    let rpc_account = fetch_account_from_rpc(&rpc_url, &account_pubkey).await?;
-    println!("Account fetched: {} bytes, {} lamports", 
-        rpc_account.data.len(), rpc_account.lamports);
+    if let Some(signature) = &args.signature {
+        emitter.status(format!("Fetching transaction {}...", signature));
+        let fetched = rpc.fetch_transaction(signature).await?
+            .ok_or_else(|| anyhow!("Transaction not found"))?;
+        let records = parse_transaction(&fetched.transaction, Some(&fetched.meta), &rpc, parser, &mut store, &emitter).await?;
+        return emit_records(emitter, records);
+    }
     
-    let wasm_account = component::solana_rpcx_bindings::types::SolanaAccount {
-        pubkey: account_pubkey.clone(),
-        data: rpc_account.data,
-        owner: rpc_account.owner,
-        lamports: rpc_account.lamports,
-        executable: rpc_account.executable,
-        rent_epoch: rpc_account.rent_epoch,
+    let local_data = match (&args.data_file, &args.data_hex) {
+        (Some(path), _) => {
+            emitter.status(format!("Reading account data from {}...", path.display()));
+            Some(input::decode_account_data(std::fs::read(path)?))
+        }
+        (_, Some(hex)) => Some(input::decode_hex_data(hex)?),
+        _ => None,
     };
-
-    println!("Parsing account with WASM component...\n");
-    
-    match parser.call_parse_account(&mut store, &wasm_account)? {
-        Ok(parsed) => {
-            println!("Successfully parsed!\n");
-            println!("Account Type: {}\n", parsed.account_type);
-            
-            // Pretty print the parsed data
-            if let Ok(json) = serde_json::from_str::<serde_json::Value>(&parsed.data) {
-                println!("Parsed Account Data:");
-                println!("{}\n", serde_json::to_string_pretty(&json)?);
+    
+    let accounts = match (local_data, &args.owner, &args.program) {
+        (Some(data), Some(owner), _) => {
+            vec![RpcAccount {
+                pubkey: args.account.clone(),
+                lamports: 0,
+                owner: owner.clone(),
+                data,
+                executable: false,
+                rent_epoch: 0,
+            }]
+        }
+        (_, _, Some(program_id)) => {
+            emitter.status("Fetching from Solana RPC...");
+            emitter.status(format!("  RPC: {}", args.rpc_url.join(", ")));
+            emitter.status(format!("  Program: {}\n", program_id));
+            if args.classify_first {
+                classify_then_fetch(&args, &rpc, program_id, parser, &mut store, &emitter).await?
+            } else {
+                rpc.fetch_program_accounts(program_id).await?
             }
         }
-        Err(e) => {
-            println!("Parse error: {:?}\n", e);
+        _ => {
+            emitter.status("Fetching from Solana RPC...");
+            emitter.status(format!("  RPC: {}", args.rpc_url.join(", ")));
+            emitter.status(format!("  Account: {}\n", args.account));
+            let account = rpc.fetch_account(&args.account).await?
+                .ok_or_else(|| anyhow!("Account does not exist"))?;
+            vec![account]
+        }
+    };
+    emitter.status(format!("Loaded {} account(s)", accounts.len()));
+    
+    emitter.status("Parsing with WASM component...\n");
+    
+    let mut batch_stats = BatchStats::default();
+    for account in accounts {
+        let pubkey = account.pubkey.clone();
+        let owner = account.owner.clone();
+        let account = to_wasm_account(account);
+        
+        let fuel_before = if args.stats { store.get_fuel()? } else { 0 };
+        let started = Instant::now();
+        let result = parser.call_parse_account(&mut store, &account)?;
+        
+        if args.stats {
+            let cost = ParseCost {
+                elapsed: started.elapsed(),
+                fuel: fuel_before - store.get_fuel()?,
+            };
+            emitter.status(format!("  {}: {}", pubkey, cost));
+            batch_stats.record(&pubkey, cost);
         }
+        
+        let outcome = match result {
+            Ok(parsed) => Outcome::Parsed {
+                account_type: parsed.account_type,
+                data: parsed.data,
+            },
+            Err(e) => failed(e),
+        };
+        emitter.emit(&pubkey, &owner, &outcome)?;
     }
     
-    println!("✅ Complete!");
+    if args.stats {
+        emitter.status(format!("Stats: {}", batch_stats));
+    }
+    for (url, failures) in rpc.failure_counts() {
+        if failures > 0 {
+            emitter.status(format!("RPC endpoint {} is failing ({} in a row)", url, failures));
+        }
+    }
+    emitter.status("✅ Complete!");
+    emitter.finish()?;
     
     Ok(())
 }
 
 
 // helpers
-#[derive(Debug)]
-struct RpcAccount {
-    pub lamports: u64,
-    pub owner: String,
-    pub data: Vec<u8>,
-    pub executable: bool,
-    pub rent_epoch: u64,
+fn to_wasm_account(account: RpcAccount) -> component::solana_rpcx_bindings::types::SolanaAccount {
+    component::solana_rpcx_bindings::types::SolanaAccount {
+        pubkey: account.pubkey,
+        data: account.data,
+        owner: account.owner,
+        lamports: account.lamports,
+        executable: account.executable,
+        rent_epoch: account.rent_epoch,
+    }
 }
 
-/// Fetch account from Solana RPC and decode base64 data
-async fn fetch_account_from_rpc(rpc_url: &str, pubkey: &str) -> Result<RpcAccount> {
-    let client = reqwest::Client::new();
-    
-    // Build RPC request
-    let request = json!({
-        "jsonrpc": "2.0",
-        "id": 1,
-        "method": "getAccountInfo",
-        "params": [
-            pubkey,
-            {
-                "encoding": "base64",
-                "commitment": "confirmed"
-            }
-        ]
-    });
-    
-    // Send request
-    let response = client
-        .post(rpc_url)
-        .json(&request)
-        .send()
-        .await?;
-    
-    let json: serde_json::Value = response.json().await?;
-    
-    // Parse response
-    let value = json
-        .get("result")
-        .and_then(|r| r.get("value"))
-        .ok_or_else(|| anyhow!("Account not found or RPC error"))?;
-    
-    // Check if account exists
-    if value.is_null() {
-        return Err(anyhow!("Account does not exist"));
+/// Split a guest parse error into a stable kind and its message
+fn failed(error: component::solana_rpcx_bindings::types::ParseError) -> Outcome {
+    use component::solana_rpcx_bindings::types::ParseError;
+    
+    // The host's bindgen types are distinct from the guest's, so map to the
+    // same codes the guest-side `ParseError::code` uses
+    let (kind, message) = match error {
+        ParseError::UnknownAccountType(message) if message.starts_with(CLOSED_ACCOUNT_PREFIX) => {
+            (error_code::CLOSED_ACCOUNT, message)
+        }
+        ParseError::UnknownAccountType(message) => (error_code::UNKNOWN_ACCOUNT_TYPE, message),
+        ParseError::DeserializationFailed(message) => (error_code::DESERIALIZATION_FAILED, message),
+        ParseError::InvalidData(message) => (error_code::INVALID_DATA, message),
+        ParseError::InsufficientData(message) => (error_code::INSUFFICIENT_DATA, message),
+    };
+    Outcome::Failed { kind, message }
+}
+
+/// The same error as the SDK's type, for its transaction helpers
+fn to_sdk_error(
+    error: component::solana_rpcx_bindings::types::ParseError,
+) -> solana_rpcx_sdk::bindings::component::solana_rpcx_bindings::types::ParseError {
+    use component::solana_rpcx_bindings::types::ParseError;
+    use solana_rpcx_sdk::bindings::component::solana_rpcx_bindings::types::ParseError as SdkParseError;
+    
+    match error {
+        ParseError::UnknownAccountType(message) => SdkParseError::UnknownAccountType(message),
+        ParseError::DeserializationFailed(message) => SdkParseError::DeserializationFailed(message),
+        ParseError::InvalidData(message) => SdkParseError::InvalidData(message),
+        ParseError::InsufficientData(message) => SdkParseError::InsufficientData(message),
     }
-    
-    // Extract fields
-    let lamports = value["lamports"].as_u64()
-        .ok_or_else(|| anyhow!("Missing lamports"))?;
-    
-    let owner = value["owner"].as_str()
-        .ok_or_else(|| anyhow!("Missing owner"))?
-        .to_string();
-    
-    let executable = value["executable"].as_bool().unwrap_or(false);
-    let rent_epoch = value["rentEpoch"].as_u64().unwrap_or(0);
-    
-    // Decode base64 data
-    let data_array = value["data"].as_array()
-        .ok_or_else(|| anyhow!("Missing data array"))?;
-    
-    let base64_data = data_array[0].as_str()
-        .ok_or_else(|| anyhow!("Missing base64 data"))?;
-    
-    let data = general_purpose::STANDARD.decode(base64_data)?;
-    
-    Ok(RpcAccount {
-        lamports,
-        owner,
-        data,
-        executable,
-        rent_epoch,
-    })
 }
 
-#[allow(dead_code)]
-fn parse_from_json(json_str: &str) -> Result<RpcAccount> {
-    let json: serde_json::Value = serde_json::from_str(json_str)?;
-    
-    let value = json["result"]["value"]
-        .as_object()
-        .ok_or_else(|| anyhow!("Invalid JSON structure"))?;
+/// `--classify-first`: classify a program's accounts from their discriminators,
+/// then fetch the full data of the ones wanted
+/// 
+/// Only the first [`MAX_DISCRIMINATOR_LEN`] bytes of each account are
+/// downloaded for the first pass, and the component matches them with the
+/// SDK's `classify_only` option instead of deserializing.
+async fn classify_then_fetch<W: std::io::Write>(
+    args: &Args,
+    rpc: &RpcClient,
+    program_id: &str,
+    parser: &exports::component::solana_rpcx_bindings::program_parser::Guest,
+    store: &mut Store<HostState>,
+    emitter: &Emitter<W>,
+) -> Result<Vec<RpcAccount>> {
+    let prefixes = rpc.fetch_program_account_prefixes(program_id, MAX_DISCRIMINATOR_LEN).await?;
+    emitter.status(format!("Classifying {} account(s) by discriminator...", prefixes.len()));
+    
+    let options = component::solana_rpcx_bindings::types::ParseOptions {
+        include_raw: false,
+        pretty_json: false,
+        custom: Some(OutputOptions { classify_only: true, ..Default::default() }.to_custom()),
+    };
     
-    let lamports = value["lamports"].as_u64()
-        .ok_or_else(|| anyhow!("Missing lamports"))?;
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    let mut wanted = Vec::new();
+    for account in prefixes {
+        let pubkey = account.pubkey.clone();
+        let account_type = parser.call_parse_with_options(&mut *store, &to_wasm_account(account), &options)?
+            .map(|parsed| parsed.account_type)
+            .ok();
+        
+        if let Some(account_type) = &account_type
+            && (args.types.is_empty() || args.types.contains(account_type))
+        {
+            wanted.push(pubkey);
+        }
+        *counts.entry(account_type.unwrap_or_else(|| "(unrecognized)".to_string())).or_default() += 1;
+    }
+    for (account_type, count) in &counts {
+        emitter.status(format!("  {}: {}", account_type, count));
+    }
     
-    let owner = value["owner"].as_str()
-        .ok_or_else(|| anyhow!("Missing owner"))?
-        .to_string();
+    emitter.status(format!("Fetching full data for {} account(s)...", wanted.len()));
+    let accounts = rpc.fetch_multiple_accounts(&wanted).await?;
+    // Accounts closed between the two passes are skipped
+    Ok(accounts.into_iter().flatten().collect())
+}
+
+/// `--transaction` and `--signature`: parse the instructions of a base64
+/// transaction
+/// 
+/// Only instructions the component's `can-parse` accepts are sent to it; the
+/// rest are recorded as raw hex. With the transaction's status `meta`, its
+/// inner instructions are parsed too and lookup table addresses are taken
+/// from it; otherwise a v0 message's lookup tables are fetched and decoded
+/// so every program id and account can be resolved.
+async fn parse_transaction<W: std::io::Write>(
+    encoded: &str,
+    meta: Option<&serde_json::Value>,
+    rpc: &RpcClient,
+    parser: &exports::component::solana_rpcx_bindings::program_parser::Guest,
+    store: &mut Store<HostState>,
+    emitter: &Emitter<W>,
+) -> Result<serde_json::Value> {
+    use solana_rpcx_sdk::bindings::component::solana_rpcx_bindings::types as sdk;
+    
+    let transaction = solana_rpcx_sdk::decode_transaction_base64(encoded).map_err(|e| anyhow!("{}", e.message()))?;
+    emitter.status(format!(
+        "Transaction {} ({}, {} instruction(s))",
+        transaction.signature().unwrap_or("(unsigned)"),
+        transaction.version.map_or("legacy".to_string(), |v| format!("v{}", v)),
+        transaction.instructions.len(),
+    ));
+    
+    let (mut loaded, inner) = match meta {
+        Some(meta) => (
+            loaded_addresses_from_meta(meta).map_err(|e| anyhow!("{}", e.message()))?,
+            inner_instructions_from_meta(meta).map_err(|e| anyhow!("{}", e.message()))?,
+        ),
+        None => Default::default(),
+    };
+    let table_keys = transaction.lookup_table_keys();
+    if !table_keys.is_empty() && loaded.is_empty() {
+        emitter.status(format!("Fetching {} lookup table(s)...", table_keys.len()));
+        let tables: HashMap<String, Vec<String>> = table_keys.iter()
+            .cloned()
+            .zip(resolve_lookup_tables(rpc, &table_keys).await?)
+            .collect();
+        loaded = transaction.load_addresses(&tables).map_err(|e| anyhow!("{}", e.message()))?;
+    }
     
-    let executable = value["executable"].as_bool().unwrap_or(false);
-    let rent_epoch = value["rentEpoch"].as_u64().unwrap_or(0);
+    let instructions = transaction.resolve_instructions(&loaded).map_err(|e| anyhow!("{}", e.message()))?;
+    let inner = transaction.resolve_inner_instructions(&inner, &loaded).map_err(|e| anyhow!("{}", e.message()))?;
     
-    let data_array = value["data"].as_array()
-        .ok_or_else(|| anyhow!("Missing data array"))?;
+    // Parse in record order (each instruction, then its CPIs), so the
+    // results can be handed back to `transaction_records` one by one
+    let ordered = instructions.iter().enumerate().flat_map(|(index, instruction)| {
+        let invoked = inner.iter()
+            .filter(move |(parent, _)| *parent == index)
+            .flat_map(|(_, instructions)| instructions);
+        std::iter::once(instruction).chain(invoked)
+    });
     
-    let base64_data = data_array[0].as_str()
-        .ok_or_else(|| anyhow!("Missing base64 data"))?;
+    let mut results = Vec::new();
+    for instruction in ordered {
+        if !parser.call_can_parse(&mut *store, &instruction.program_id, &instruction.data)? {
+            results.push(None);
+            continue;
+        }
+        
+        let host_instruction = component::solana_rpcx_bindings::types::InstructionData {
+            program_id: instruction.program_id.clone(),
+            data: instruction.data.clone(),
+            accounts: instruction.accounts.clone(),
+        };
+        // Back to the SDK's types, which share the WIT definitions
+        let result = parser.call_parse_instruction(&mut *store, &host_instruction)?
+            .map(|parsed| sdk::ParsedInstruction {
+                instruction_name: parsed.instruction_name,
+                data: parsed.data,
+            })
+            .map_err(to_sdk_error);
+        results.push(Some(result));
+    }
     
-    let data = general_purpose::STANDARD.decode(base64_data)?;
+    let mut results = results.into_iter();
+    Ok(transaction_records(&instructions, &inner, |_| results.next().flatten()))
+}
+
+/// Write each instruction record in `--format`, then finish the output
+fn emit_records<W: std::io::Write>(mut emitter: Emitter<W>, records: serde_json::Value) -> Result<()> {
+    if let serde_json::Value::Array(records) = records {
+        for record in records {
+            emitter.emit_record(record)?;
+        }
+    }
+    emitter.finish()?;
+    Ok(())
+}
+
+/// Check the component exports everything `full-parser` needs
+/// 
+/// `FullParser::instantiate` fails on the first missing export with a
+/// type-checking error; this names all of them up front.
+fn preflight(engine: &Engine, component: &Component, path: &std::path::Path) -> Result<()> {
+    let component_type = component.component_type();
+    let missing: Vec<&str> = REQUIRED_EXPORTS
+        .into_iter()
+        .filter(|name| component_type.get_export(engine, name).is_none())
+        .collect();
+    
+    if missing.is_empty() {
+        return Ok(());
+    }
     
-    Ok(RpcAccount {
-        lamports,
-        owner,
-        data,
-        executable,
-        rent_epoch,
-    })
-}
\ No newline at end of file
+    let exported: Vec<&str> = component_type.exports(engine).map(|(name, _)| name).collect();
+    Err(anyhow!(
+        "{} is not an rpcX parser component: missing export(s) {}\n  it exports: {}\n  \
+         build it against the `full-parser` world of solana-rpcx-bindings",
+        path.display(),
+        missing.join(", "),
+        if exported.is_empty() { "nothing".to_string() } else { exported.join(", ") },
+    ))
+}
+
+/// Run an RPC future from a sync host call (we're already inside the runtime)
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    tokio::task::block_in_place(|| tokio::runtime::Handle::current().block_on(future))
+}
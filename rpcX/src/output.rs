@@ -0,0 +1,267 @@
+//! Output formats for parse results
+//!
+//! `pretty` is for people at a terminal. `json` and `ndjson` are for
+//! pipelines: stdout then carries only results, and progress messages go to
+//! stderr.
+
+use std::fmt::Display;
+use std::io::{self, Write};
+use serde_json::{Value, json};
+use solana_rpcx_sdk::known_program_name;
+
+/// How parse results are written to stdout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum Format {
+    /// One JSON object per line, written as each account is parsed
+    Ndjson,
+    /// A single JSON array, written once every account is parsed
+    Json,
+    /// Human-readable text
+    #[default]
+    Pretty,
+}
+
+impl Format {
+    /// Whether stdout is reserved for machine-readable results
+    pub fn is_machine(self) -> bool {
+        self != Format::Pretty
+    }
+}
+
+/// Result of parsing one account, as the host saw it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Parsed { account_type: String, data: String },
+    Failed {
+        /// Stable snake_case name of the `parse-error` variant, e.g.
+        /// `"insufficient_data"`, for pipelines to switch on
+        kind: &'static str,
+        message: String,
+    },
+}
+
+/// Writes parse results in the chosen [`Format`]
+pub struct Emitter<W: Write> {
+    format: Format,
+    out: W,
+    /// Records held back until [`finish`](Self::finish) for `json`
+    buffered: Vec<Value>,
+}
+
+impl<W: Write> Emitter<W> {
+    pub fn new(format: Format, out: W) -> Self {
+        Self { format, out, buffered: Vec::new() }
+    }
+
+    /// Print a progress message where it won't corrupt machine output
+    pub fn status(&self, message: impl Display) {
+        if self.format.is_machine() {
+            eprintln!("{}", message);
+        } else {
+            println!("{}", message);
+        }
+    }
+
+    /// Write (or, for `json`, buffer) the result for one account
+    ///
+    /// `ndjson` lines are flushed immediately so consumers can stream them.
+    /// `owner` is only shown in `pretty` output, named if it's a well-known
+    /// program.
+    pub fn emit(&mut self, pubkey: &str, owner: &str, outcome: &Outcome) -> io::Result<()> {
+        match self.format {
+            Format::Pretty => self.emit_pretty(pubkey, owner, outcome),
+            _ => self.emit_record(record(pubkey, outcome)),
+        }
+    }
+
+    /// Write (or, for `json`, buffer) a record that is already JSON, such as
+    /// one instruction of a parsed transaction
+    ///
+    /// `pretty` prints it indented.
+    pub fn emit_record(&mut self, record: Value) -> io::Result<()> {
+        match self.format {
+            Format::Ndjson => {
+                writeln!(self.out, "{}", record)?;
+                self.out.flush()
+            }
+            Format::Json => {
+                self.buffered.push(record);
+                Ok(())
+            }
+            Format::Pretty => writeln!(self.out, "{}\n", serde_json::to_string_pretty(&record)?),
+        }
+    }
+
+    /// Write anything still buffered
+    pub fn finish(mut self) -> io::Result<()> {
+        if self.format == Format::Json {
+            let records = Value::Array(std::mem::take(&mut self.buffered));
+            writeln!(self.out, "{}", serde_json::to_string_pretty(&records)?)?;
+        }
+        self.out.flush()
+    }
+
+    fn emit_pretty(&mut self, pubkey: &str, owner: &str, outcome: &Outcome) -> io::Result<()> {
+        match known_program_name(owner) {
+            Some(name) => writeln!(self.out, "Owner: {} ({})", owner, name)?,
+            None => writeln!(self.out, "Owner: {}", owner)?,
+        }
+
+        match outcome {
+            Outcome::Parsed { account_type, data } => {
+                writeln!(self.out, "Successfully parsed {}!\n", pubkey)?;
+                writeln!(self.out, "Account Type: {}\n", account_type)?;
+                writeln!(self.out, "Parsed Account Data:")?;
+                match serde_json::from_str::<Value>(data) {
+                    Ok(json) => writeln!(self.out, "{}\n", serde_json::to_string_pretty(&json)?),
+                    Err(_) => writeln!(self.out, "{}\n", data),
+                }
+            }
+            Outcome::Failed { kind, message } => writeln!(self.out, "Parse error for {} ({}): {}\n", pubkey, kind, message),
+        }
+    }
+}
+
+/// One self-contained JSON object per account
+///
+/// The parser's data is embedded as JSON when it parses, so consumers don't
+/// have to decode a string inside the record.
+fn record(pubkey: &str, outcome: &Outcome) -> Value {
+    match outcome {
+        Outcome::Parsed { account_type, data } => json!({
+            "pubkey": pubkey,
+            "account_type": account_type,
+            "data": serde_json::from_str::<Value>(data).unwrap_or_else(|_| json!(data)),
+        }),
+        Outcome::Failed { kind, message } => json!({
+            "pubkey": pubkey,
+            "error": { "kind": kind, "message": message },
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OWNER: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+
+    fn parsed(data: &str) -> Outcome {
+        Outcome::Parsed { account_type: "Pool".to_string(), data: data.to_string() }
+    }
+
+    fn failed() -> Outcome {
+        Outcome::Failed { kind: "insufficient_data", message: "Insufficient data".to_string() }
+    }
+
+    fn lines(out: &[u8]) -> Vec<Value> {
+        std::str::from_utf8(out).unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn record_embeds_parsed_data_as_json() {
+        assert_eq!(record("A", &parsed(r#"{"fee":5}"#)), json!({
+            "pubkey": "A",
+            "account_type": "Pool",
+            "data": { "fee": 5 },
+        }));
+    }
+
+    #[test]
+    fn record_keeps_data_that_is_not_json_as_a_string() {
+        assert_eq!(record("A", &parsed("not json"))["data"], json!("not json"));
+    }
+
+    #[test]
+    fn ndjson_writes_one_record_per_line() {
+        let mut out = Vec::new();
+        let mut emitter = Emitter::new(Format::Ndjson, &mut out);
+        emitter.emit("A", OWNER, &parsed(r#"{"fee":5}"#)).unwrap();
+        emitter.emit("B", OWNER, &failed()).unwrap();
+        emitter.finish().unwrap();
+
+        let records = lines(&out);
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0]["pubkey"], "A");
+        assert_eq!(records[1], record("B", &failed()));
+    }
+
+    #[test]
+    fn json_writes_one_array_on_finish() {
+        let mut out = Vec::new();
+        let mut emitter = Emitter::new(Format::Json, &mut out);
+        emitter.emit("A", OWNER, &parsed("{}")).unwrap();
+        emitter.emit("B", OWNER, &failed()).unwrap();
+        emitter.finish().unwrap();
+
+        let records: Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(records, json!([record("A", &parsed("{}")), record("B", &failed())]));
+    }
+
+    #[test]
+    fn json_with_no_records_is_an_empty_array() {
+        let mut out = Vec::new();
+        Emitter::new(Format::Json, &mut out).finish().unwrap();
+
+        assert_eq!(serde_json::from_slice::<Value>(&out).unwrap(), json!([]));
+    }
+
+    #[test]
+    fn pretty_output_is_not_json() {
+        let mut out = Vec::new();
+        let mut emitter = Emitter::new(Format::Pretty, &mut out);
+        emitter.emit("A", OWNER, &parsed(r#"{"fee":5}"#)).unwrap();
+        emitter.finish().unwrap();
+
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("Successfully parsed A!"));
+        assert!(text.contains("Account Type: Pool"));
+        assert!(serde_json::from_str::<Value>(&text).is_err());
+    }
+
+    #[test]
+    fn failures_carry_a_kind_and_a_message() {
+        assert_eq!(record("B", &failed()), json!({
+            "pubkey": "B",
+            "error": { "kind": "insufficient_data", "message": "Insufficient data" },
+        }));
+    }
+
+    #[test]
+    fn pretty_output_names_well_known_owners() {
+        let mut out = Vec::new();
+        let mut emitter = Emitter::new(Format::Pretty, &mut out);
+        emitter.emit("A", OWNER, &parsed("{}")).unwrap();
+        emitter.emit("B", "Prog1111111111111111111111111111111111111111", &failed()).unwrap();
+        emitter.finish().unwrap();
+
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains(&format!("Owner: {} (Token Program)", OWNER)));
+        assert!(text.contains("Owner: Prog1111111111111111111111111111111111111111\n"));
+    }
+
+    #[test]
+    fn ndjson_records_are_written_one_per_line() {
+        let mut out = Vec::new();
+        let mut emitter = Emitter::new(Format::Ndjson, &mut out);
+        emitter.emit_record(json!({ "index": 0, "raw": "ab" })).unwrap();
+        emitter.emit_record(json!({ "index": 1, "raw": "cd" })).unwrap();
+        emitter.finish().unwrap();
+
+        assert_eq!(String::from_utf8(out).unwrap(), "{\"index\":0,\"raw\":\"ab\"}\n{\"index\":1,\"raw\":\"cd\"}\n");
+    }
+
+    #[test]
+    fn json_records_are_written_as_one_array() {
+        let mut out = Vec::new();
+        let mut emitter = Emitter::new(Format::Json, &mut out);
+        emitter.emit_record(json!({ "index": 0 })).unwrap();
+        emitter.emit_record(json!({ "index": 1 })).unwrap();
+        emitter.finish().unwrap();
+
+        assert_eq!(serde_json::from_slice::<Value>(&out).unwrap(), json!([{ "index": 0 }, { "index": 1 }]));
+    }
+}
@@ -0,0 +1,408 @@
+//! Solana JSON-RPC account fetching
+//!
+//! Shared by the host's `accounts-db` implementation and anything else that
+//! needs accounts with decoded data (e.g. integration tests).
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use anyhow::{Result, anyhow};
+use serde_json::{Value, json};
+use solana_rpcx_sdk::bindings::component::solana_rpcx_bindings::types::SolanaAccount;
+use solana_rpcx_sdk::{ADDRESS_LOOKUP_TABLE_PROGRAM_ID, decode_lookup_table};
+
+/// Most keys `getMultipleAccounts` accepts per request
+pub const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+/// An account as returned by RPC, with its data decoded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RpcAccount {
+    pub pubkey: String,
+    pub lamports: u64,
+    pub owner: String,
+    pub data: Vec<u8>,
+    pub executable: bool,
+    pub rent_epoch: u64,
+}
+
+impl RpcAccount {
+    /// Decode an RPC account object (`base64` or `base58` encoding)
+    /// 
+    /// Uses the SDK's [`account_from_rpc_value`](solana_rpcx_sdk::account_from_rpc_value);
+    /// a `null` account is an error.
+    pub fn from_json(pubkey: impl Into<String>, value: &Value) -> Result<Self> {
+        let pubkey = pubkey.into();
+        decode_optional(&pubkey, value)?.ok_or_else(|| anyhow!("Account {} doesn't exist", pubkey))
+    }
+}
+
+impl From<SolanaAccount> for RpcAccount {
+    fn from(account: SolanaAccount) -> Self {
+        Self {
+            pubkey: account.pubkey,
+            lamports: account.lamports,
+            owner: account.owner,
+            data: account.data,
+            executable: account.executable,
+            rent_epoch: account.rent_epoch,
+        }
+    }
+}
+
+/// A transaction as returned by `getTransaction`
+#[derive(Debug, Clone, PartialEq)]
+pub struct RpcTransaction {
+    pub slot: u64,
+    /// The serialized transaction, base64
+    pub transaction: String,
+    /// Status meta: `err`, `innerInstructions`, `loadedAddresses`, `logMessages`, ...
+    pub meta: Value,
+}
+
+/// Default per-request timeout
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Settings for the HTTP client behind an [`RpcClient`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RpcConfig {
+    /// Timeout for a whole request, including reading the response
+    pub timeout: Duration,
+    /// Timeout for establishing a connection
+    pub connect_timeout: Duration,
+    pub user_agent: String,
+}
+
+impl Default for RpcConfig {
+    fn default() -> Self {
+        Self {
+            timeout: DEFAULT_TIMEOUT,
+            connect_timeout: Duration::from_secs(10),
+            user_agent: concat!("rpcX/", env!("CARGO_PKG_VERSION")).to_string(),
+        }
+    }
+}
+
+/// Solana JSON-RPC endpoints behind a shared HTTP client
+///
+/// The client keeps a connection pool and TLS sessions, so create one and
+/// reuse it (clones share the pool and failure counts) rather than one per
+/// request.
+///
+/// With several endpoints, each request goes to the endpoint with the
+/// fewest failures so far (ties in the order given, so the first is the
+/// primary) and fails over to the next when a request can't be sent, times
+/// out, gets an HTTP error status or a malformed response. JSON-RPC errors
+/// are answers about the request itself and are returned without retrying.
+/// An endpoint's failure count resets when it next succeeds.
+#[derive(Debug, Clone)]
+pub struct RpcClient {
+    http: reqwest::Client,
+    endpoints: Arc<[Endpoint]>,
+    /// Address lists of lookup tables resolved so far, by table key
+    lookup_tables: Arc<Mutex<HashMap<String, Vec<String>>>>,
+}
+
+#[derive(Debug)]
+struct Endpoint {
+    url: String,
+    failures: AtomicU64,
+}
+
+impl RpcClient {
+    /// Client for `url` with the default [`RpcConfig`]
+    pub fn new(url: impl Into<String>) -> Result<Self> {
+        Self::with_config(url, &RpcConfig::default())
+    }
+    
+    /// Client for `url` with custom timeouts and user agent
+    pub fn with_config(url: impl Into<String>, config: &RpcConfig) -> Result<Self> {
+        Self::with_endpoints([url], config)
+    }
+    
+    /// Client failing over between `urls`, the first being the primary
+    pub fn with_endpoints<I, S>(urls: I, config: &RpcConfig) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let endpoints: Arc<[Endpoint]> = urls.into_iter()
+            .map(|url| Endpoint { url: url.into(), failures: AtomicU64::new(0) })
+            .collect();
+        if endpoints.is_empty() {
+            return Err(anyhow!("No RPC endpoints given"));
+        }
+        
+        let http = reqwest::Client::builder()
+            .timeout(config.timeout)
+            .connect_timeout(config.connect_timeout)
+            .user_agent(config.user_agent.as_str())
+            .build()?;
+        
+        Ok(Self { http, endpoints, lookup_tables: Arc::default() })
+    }
+    
+    /// Endpoint URLs, in the order given
+    pub fn urls(&self) -> impl Iterator<Item = &str> {
+        self.endpoints.iter().map(|endpoint| endpoint.url.as_str())
+    }
+    
+    /// Each endpoint's URL and its failures since it last succeeded
+    pub fn failure_counts(&self) -> Vec<(&str, u64)> {
+        self.endpoints.iter()
+            .map(|endpoint| (endpoint.url.as_str(), endpoint.failures.load(Ordering::Relaxed)))
+            .collect()
+    }
+    
+    /// Fetch one account; `None` if it doesn't exist
+    pub async fn fetch_account(&self, pubkey: &str) -> Result<Option<RpcAccount>> {
+        let result = self.call("getAccountInfo", json!([
+            pubkey,
+            { "encoding": "base64", "commitment": "confirmed" }
+        ])).await?;
+        
+        decode_optional(pubkey, &result["value"])
+    }
+    
+    /// Fetch several accounts, in input order
+    ///
+    /// Requests are split into batches of [`MAX_MULTIPLE_ACCOUNTS`].
+    pub async fn fetch_multiple_accounts(&self, pubkeys: &[String]) -> Result<Vec<Option<RpcAccount>>> {
+        let mut accounts = Vec::with_capacity(pubkeys.len());
+        
+        for batch in pubkeys.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            let result = self.call("getMultipleAccounts", json!([
+                batch,
+                { "encoding": "base64", "commitment": "confirmed" }
+            ])).await?;
+            
+            let values = result["value"].as_array()
+                .ok_or_else(|| anyhow!("Missing value array"))?;
+            if values.len() != batch.len() {
+                return Err(anyhow!("Expected {} accounts, got {}", batch.len(), values.len()));
+            }
+            
+            for (pubkey, value) in batch.iter().zip(values) {
+                accounts.push(decode_optional(pubkey, value)?);
+            }
+        }
+        
+        Ok(accounts)
+    }
+    
+    /// Fetch a confirmed transaction and its status meta; `None` if the
+    /// node doesn't have it
+    /// 
+    /// Requested as base64 with v0 support, so any transaction can be decoded
+    /// with the SDK.
+    pub async fn fetch_transaction(&self, signature: &str) -> Result<Option<RpcTransaction>> {
+        let result = self.call("getTransaction", json!([
+            signature,
+            { "encoding": "base64", "commitment": "confirmed", "maxSupportedTransactionVersion": 0 }
+        ])).await?;
+        
+        if result.is_null() {
+            return Ok(None);
+        }
+        let transaction = result["transaction"].get(0)
+            .and_then(Value::as_str)
+            .ok_or_else(|| anyhow!("Missing base64 transaction"))?
+            .to_string();
+        
+        Ok(Some(RpcTransaction {
+            slot: result["slot"].as_u64().unwrap_or(0),
+            transaction,
+            meta: result["meta"].clone(),
+        }))
+    }
+    
+    /// Fetch every account owned by a program
+    pub async fn fetch_program_accounts(&self, program_id: &str) -> Result<Vec<RpcAccount>> {
+        self.program_accounts(program_id, json!({ "encoding": "base64", "commitment": "confirmed" })).await
+    }
+    
+    /// Fetch the first `len` bytes of every account owned by a program
+    /// 
+    /// Uses `dataSlice`, so each account's `data` is only its prefix (shorter
+    /// if the account is). Enough to classify accounts by discriminator before
+    /// fetching the full data of the interesting ones.
+    pub async fn fetch_program_account_prefixes(&self, program_id: &str, len: usize) -> Result<Vec<RpcAccount>> {
+        self.program_accounts(program_id, json!({
+            "encoding": "base64",
+            "commitment": "confirmed",
+            "dataSlice": { "offset": 0, "length": len },
+        })).await
+    }
+    
+    async fn program_accounts(&self, program_id: &str, config: Value) -> Result<Vec<RpcAccount>> {
+        let result = self.call("getProgramAccounts", json!([program_id, config])).await?;
+        
+        result.as_array()
+            .ok_or_else(|| anyhow!("Missing account array"))?
+            .iter()
+            .map(|keyed| {
+                let pubkey = keyed["pubkey"].as_str()
+                    .ok_or_else(|| anyhow!("Missing pubkey"))?;
+                RpcAccount::from_json(pubkey, &keyed["account"])
+            })
+            .collect()
+    }
+    
+    /// Send one JSON-RPC request and return its `result`, failing over
+    /// between endpoints
+    async fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        });
+        
+        // Least-failing first; the sort is stable, so ties keep their order
+        let mut order: Vec<&Endpoint> = self.endpoints.iter().collect();
+        order.sort_by_key(|endpoint| endpoint.failures.load(Ordering::Relaxed));
+        
+        let mut errors = Vec::new();
+        for endpoint in order {
+            match self.send(&endpoint.url, &request).await {
+                Ok(json) => {
+                    endpoint.failures.store(0, Ordering::Relaxed);
+                    return result_of(json);
+                }
+                Err(e) => {
+                    endpoint.failures.fetch_add(1, Ordering::Relaxed);
+                    errors.push(format!("{}: {}", endpoint.url, e));
+                }
+            }
+        }
+        
+        Err(anyhow!("{} failed on every RPC endpoint:\n  {}", method, errors.join("\n  ")))
+    }
+    
+    async fn send(&self, url: &str, request: &Value) -> Result<Value> {
+        let json = self.http
+            .post(url)
+            .json(request)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(json)
+    }
+}
+
+/// Fetch address lookup tables and decode their address lists
+/// 
+/// Returns one list per key, in input order, for resolving the accounts a
+/// v0 message loads. Tables are cached on the client (and its clones), since
+/// the same few are referenced by transaction after transaction; only the
+/// ones not seen before are fetched, in one batch. Tables are append-only,
+/// so cached addresses stay valid, but entries added to a table after it
+/// was cached are not picked up.
+pub async fn resolve_lookup_tables(rpc: &RpcClient, table_keys: &[String]) -> Result<Vec<Vec<String>>> {
+    let mut missing: Vec<String> = {
+        let cache = rpc.lookup_tables.lock().expect("lookup table cache poisoned");
+        table_keys.iter().filter(|key| !cache.contains_key(*key)).cloned().collect()
+    };
+    missing.sort();
+    missing.dedup();
+    
+    if !missing.is_empty() {
+        let mut resolved = Vec::with_capacity(missing.len());
+        for (key, account) in missing.iter().zip(rpc.fetch_multiple_accounts(&missing).await?) {
+            let account = account.ok_or_else(|| anyhow!("Lookup table {} does not exist", key))?;
+            if account.owner != ADDRESS_LOOKUP_TABLE_PROGRAM_ID {
+                return Err(anyhow!("{} is not a lookup table (owner {})", key, account.owner));
+            }
+            let addresses = decode_lookup_table(&account.data)
+                .map_err(|e| anyhow!("Lookup table {}: {}", key, e.message()))?;
+            resolved.push((key.clone(), addresses));
+        }
+        rpc.lookup_tables.lock().expect("lookup table cache poisoned").extend(resolved);
+    }
+    
+    let cache = rpc.lookup_tables.lock().expect("lookup table cache poisoned");
+    Ok(table_keys.iter().map(|key| cache[key].clone()).collect())
+}
+
+fn result_of(mut json: Value) -> Result<Value> {
+    if let Some(error) = json.get("error") {
+        return Err(anyhow!("RPC error: {}", error));
+    }
+    
+    match json.get_mut("result") {
+        Some(result) => Ok(result.take()),
+        None => Err(anyhow!("RPC response has no result")),
+    }
+}
+
+fn decode_optional(pubkey: &str, value: &Value) -> Result<Option<RpcAccount>> {
+    let account = solana_rpcx_sdk::account_from_rpc_value(value, pubkey)?;
+    Ok(account.map(RpcAccount::from))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn account_json(data: Value) -> Value {
+        json!({
+            "data": data,
+            "executable": false,
+            "lamports": 2039280,
+            "owner": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+            "rentEpoch": 18446744073709551615u64,
+            "space": 3
+        })
+    }
+    
+    #[test]
+    fn from_json_decodes_base64_and_base58() {
+        let base64 = RpcAccount::from_json("A", &account_json(json!(["AQID", "base64"]))).unwrap();
+        assert_eq!(base64, RpcAccount {
+            pubkey: "A".to_string(),
+            lamports: 2_039_280,
+            owner: "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA".to_string(),
+            data: vec![1, 2, 3],
+            executable: false,
+            rent_epoch: u64::MAX,
+        });
+        
+        let base58 = RpcAccount::from_json("A", &account_json(json!(["Ldp", "base58"]))).unwrap();
+        assert_eq!(base58.data, vec![1, 2, 3]);
+    }
+    
+    #[test]
+    fn from_json_reports_missing_fields() {
+        let mut value = account_json(json!(["AQID", "base64"]));
+        value.as_object_mut().unwrap().remove("lamports");
+        
+        let error = RpcAccount::from_json("A", &value).unwrap_err();
+        assert_eq!(error.to_string(), "Invalid data: Account A: missing lamports");
+    }
+    
+    #[test]
+    fn null_accounts_are_none_or_an_error() {
+        assert_eq!(decode_optional("A", &Value::Null).unwrap(), None);
+        
+        let error = RpcAccount::from_json("A", &Value::Null).unwrap_err();
+        assert_eq!(error.to_string(), "Account A doesn't exist");
+    }
+    
+    #[test]
+    fn result_of_surfaces_rpc_errors() {
+        let error = result_of(json!({
+            "jsonrpc": "2.0",
+            "error": { "code": -32602, "message": "Invalid param: WrongSize" },
+            "id": 1
+        })).unwrap_err();
+        assert!(error.to_string().starts_with("RPC error: "), "{}", error);
+        assert!(error.to_string().contains("WrongSize"), "{}", error);
+        
+        let error = result_of(json!({ "jsonrpc": "2.0", "id": 1 })).unwrap_err();
+        assert_eq!(error.to_string(), "RPC response has no result");
+        
+        assert_eq!(result_of(json!({ "result": { "value": null } })).unwrap(), json!({ "value": null }));
+    }
+}
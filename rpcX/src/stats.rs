@@ -0,0 +1,116 @@
+//! Per-parse timing and fuel accounting for `--stats`
+
+use std::fmt;
+use std::time::Duration;
+
+/// Cost of one `parse-account` call
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseCost {
+    pub elapsed: Duration,
+    /// Fuel consumed by the guest
+    pub fuel: u64,
+}
+
+impl fmt::Display for ParseCost {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.3} ms, {} fuel", self.elapsed.as_secs_f64() * 1000.0, self.fuel)
+    }
+}
+
+/// Totals across a batch, plus the most expensive account
+#[derive(Debug, Clone, Default)]
+pub struct BatchStats {
+    pub parses: usize,
+    pub elapsed: Duration,
+    pub fuel: u64,
+    /// Account with the highest fuel use, and its cost
+    pub heaviest: Option<(String, ParseCost)>,
+}
+
+impl BatchStats {
+    pub fn record(&mut self, pubkey: &str, cost: ParseCost) {
+        self.parses += 1;
+        self.elapsed += cost.elapsed;
+        self.fuel = self.fuel.saturating_add(cost.fuel);
+
+        if self.heaviest.as_ref().is_none_or(|(_, heaviest)| cost.fuel > heaviest.fuel) {
+            self.heaviest = Some((pubkey.to_string(), cost));
+        }
+    }
+}
+
+impl fmt::Display for BatchStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let total = ParseCost { elapsed: self.elapsed, fuel: self.fuel };
+        write!(f, "{} parse(s): {}", self.parses, total)?;
+
+        if self.parses > 0 {
+            let average = ParseCost {
+                elapsed: self.elapsed / self.parses as u32,
+                fuel: self.fuel / self.parses as u64,
+            };
+            write!(f, " (average {})", average)?;
+        }
+        if let Some((pubkey, cost)) = &self.heaviest {
+            write!(f, "; heaviest {} at {}", pubkey, cost)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cost(millis: u64, fuel: u64) -> ParseCost {
+        ParseCost { elapsed: Duration::from_millis(millis), fuel }
+    }
+
+    #[test]
+    fn record_sums_and_keeps_the_heaviest() {
+        let mut stats = BatchStats::default();
+        stats.record("A", cost(2, 100));
+        stats.record("B", cost(1, 300));
+        stats.record("C", cost(3, 200));
+
+        assert_eq!(stats.parses, 3);
+        assert_eq!(stats.elapsed, Duration::from_millis(6));
+        assert_eq!(stats.fuel, 600);
+        assert_eq!(stats.heaviest, Some(("B".to_string(), cost(1, 300))));
+    }
+
+    #[test]
+    fn first_of_equal_costs_stays_heaviest() {
+        let mut stats = BatchStats::default();
+        stats.record("A", cost(1, 100));
+        stats.record("B", cost(1, 100));
+
+        assert_eq!(stats.heaviest.unwrap().0, "A");
+    }
+
+    #[test]
+    fn fuel_total_saturates() {
+        let mut stats = BatchStats::default();
+        stats.record("A", cost(0, u64::MAX));
+        stats.record("B", cost(0, 1));
+
+        assert_eq!(stats.fuel, u64::MAX);
+    }
+
+    #[test]
+    fn display_shows_totals_average_and_heaviest() {
+        let mut stats = BatchStats::default();
+        stats.record("A", cost(1, 100));
+        stats.record("B", cost(3, 300));
+
+        assert_eq!(
+            stats.to_string(),
+            "2 parse(s): 4.000 ms, 400 fuel (average 2.000 ms, 200 fuel); heaviest B at 3.000 ms, 300 fuel"
+        );
+    }
+
+    #[test]
+    fn empty_batch_has_no_average() {
+        assert_eq!(BatchStats::default().to_string(), "0 parse(s): 0.000 ms, 0 fuel");
+    }
+}
@@ -0,0 +1,68 @@
+//! `parse_account` dispatch cost as the number of registered types grows
+//!
+//! Run with `cargo bench -p solana-rpcx-sdk --bench dispatch`
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use solana_rpcx_sdk::prelude::*;
+
+const PROGRAM_ID: &str = "11111111111111111111111111111111";
+
+#[derive(BorshDeserialize, Serialize)]
+struct Counter {
+    authority: [u8; 32],
+    count: u64,
+}
+
+fn type_discriminator(i: usize) -> [u8; 8] {
+    compute_anchor_discriminator("account", &format!("Type{}", i))
+}
+
+fn build_parser(types: usize) -> Parser {
+    (0..types)
+        .fold(ParserBuilder::new(PROGRAM_ID), |builder, i| {
+            builder.register_account_with_discriminator::<Counter>(
+                format!("Type{}", i),
+                type_discriminator(i).to_vec(),
+            )
+        })
+        .build()
+}
+
+fn account(discriminator: [u8; 8]) -> SolanaAccount {
+    let mut data = discriminator.to_vec();
+    data.extend_from_slice(&[7u8; 32]);
+    data.extend_from_slice(&42u64.to_le_bytes());
+
+    SolanaAccount {
+        pubkey: "bench".to_string(),
+        data,
+        owner: PROGRAM_ID.to_string(),
+        lamports: 1_000_000,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+fn bench_dispatch(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse_account_dispatch");
+
+    for types in [1, 10, 50] {
+        let parser = build_parser(types);
+        // The last registered type is the worst case for in-order dispatch
+        let matching = account(type_discriminator(types - 1));
+        let non_matching = account([0xff; 8]);
+
+        group.bench_with_input(BenchmarkId::new("matching", types), &matching, |b, account| {
+            b.iter(|| parser.parse_account(black_box(account)))
+        });
+
+        group.bench_with_input(BenchmarkId::new("non_matching", types), &non_matching, |b, account| {
+            b.iter(|| parser.parse_account(black_box(account)))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_dispatch);
+criterion_main!(benches);
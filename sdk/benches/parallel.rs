@@ -0,0 +1,75 @@
+//! Sequential vs parallel parsing of 100k SPL token accounts
+//!
+//! Run with `cargo bench -p solana-rpcx-sdk --features rayon --bench parallel`
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use solana_rpcx_sdk::prelude::*;
+
+const PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+const ACCOUNT_COUNT: usize = 100_000;
+
+/// SPL token account layout (165 bytes)
+#[derive(BorshDeserialize, Serialize)]
+struct TokenAccount {
+    mint: [u8; 32],
+    owner: [u8; 32],
+    amount: u64,
+    delegate_tag: u32,
+    delegate: [u8; 32],
+    state: u8,
+    is_native_tag: u32,
+    is_native: u64,
+    delegated_amount: u64,
+    close_authority_tag: u32,
+    close_authority: [u8; 32],
+}
+
+fn token_accounts() -> Vec<SolanaAccount> {
+    (0..ACCOUNT_COUNT)
+        .map(|i| {
+            let mut data = vec![0u8; 165];
+            data[0..32].fill(1);
+            data[32..64].fill(2);
+            data[64..72].copy_from_slice(&(i as u64).to_le_bytes());
+            data[108] = 1;
+
+            SolanaAccount {
+                pubkey: format!("account-{}", i),
+                data,
+                owner: PROGRAM_ID.to_string(),
+                lamports: 2_039_280,
+                executable: false,
+                rent_epoch: 0,
+            }
+        })
+        .collect()
+}
+
+fn bench_parallel(c: &mut Criterion) {
+    let parser = ParserBuilder::new(PROGRAM_ID)
+        .register_borsh_account::<TokenAccount>("TokenAccount")
+        .build();
+    let accounts = token_accounts();
+
+    let mut group = c.benchmark_group("parse_100k_token_accounts");
+    group.throughput(Throughput::Elements(ACCOUNT_COUNT as u64));
+    group.sample_size(10);
+
+    group.bench_function("sequential", |b| {
+        b.iter(|| {
+            accounts
+                .iter()
+                .map(|account| parser.parse_account(account))
+                .collect::<Vec<_>>()
+        })
+    });
+
+    group.bench_function("parallel", |b| {
+        b.iter(|| parser.parse_accounts_parallel(&accounts))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_parallel);
+criterion_main!(benches);
@@ -0,0 +1,111 @@
+//! Throws arbitrary bytes at every dispatch path; any panic is a bug.
+//!
+//! `cargo +nightly fuzz run parse_account` from `sdk/`.
+
+#![no_main]
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use libfuzzer_sys::fuzz_target;
+use solana_rpcx_sdk::prelude::*;
+use solana_rpcx_sdk::Pubkey;
+
+const PROGRAM_ID: &str = "11111111111111111111111111111111";
+
+const IDL: &str = r#"{
+    "address": "11111111111111111111111111111111",
+    "metadata": { "name": "fuzz", "version": "0.1.0" },
+    "instructions": [{
+        "name": "deposit",
+        "discriminator": [242, 35, 198, 137, 82, 225, 242, 182],
+        "accounts": [],
+        "args": [{ "name": "amount", "type": "u64" }, { "name": "memo", "type": "string" }]
+    }],
+    "accounts": [{ "name": "Pool", "discriminator": [241, 154, 109, 4, 17, 177, 109, 188] }],
+    "types": [{
+        "name": "Pool",
+        "type": {
+            "kind": "struct",
+            "fields": [
+                { "name": "authority", "type": "pubkey" },
+                { "name": "tags", "type": { "vec": "string" } },
+                { "name": "limit", "type": { "option": "u128" } },
+                { "name": "weights", "type": { "array": ["u16", 4] } }
+            ]
+        }
+    }]
+}"#;
+
+#[derive(Clone, BorshDeserialize, Serialize)]
+enum Status {
+    Active,
+    Frozen { until: i64 },
+}
+
+#[derive(Clone, BorshDeserialize, Serialize)]
+struct Profile {
+    owner: Pubkey,
+    name: String,
+    scores: Vec<u64>,
+    labels: HashMap<String, Vec<u8>>,
+    status: Status,
+    parent: Option<Box<Profile>>,
+}
+
+#[derive(BorshDeserialize, Serialize)]
+struct Counter {
+    count: u64,
+}
+
+#[derive(BorshDeserialize, Serialize)]
+struct Transfer {
+    amount: u64,
+    recipients: Vec<Pubkey>,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable, Serialize)]
+struct Vault {
+    owner: Pubkey,
+    amount: u64,
+}
+
+static PARSER: LazyLock<Parser> = LazyLock::new(|| {
+    ParserBuilder::from_anchor_idl(IDL)
+        .and_then(|builder| builder.instructions_from_anchor_idl(IDL))
+        .expect("fuzz IDL is valid")
+        .register_account_with_discriminator::<Profile>("Profile", vec![1])
+        .register_account_with_disc_and_len::<Counter>("CounterV1", vec![2], 9)
+        .register_pinocchio_account::<Vault>("Vault", vec![3])
+        .register_anchor_instruction::<Transfer>("transfer")
+        .register_borsh_account::<Counter>("Counter")
+        .build()
+});
+
+fuzz_target!(|data: &[u8]| {
+    let account = SolanaAccount {
+        pubkey: PROGRAM_ID.to_string(),
+        data: data.to_vec(),
+        owner: PROGRAM_ID.to_string(),
+        lamports: 0,
+        executable: false,
+        rent_epoch: 0,
+    };
+    let options = OutputOptions {
+        bignum_as_string: true,
+        key_case: Some(Casing::Camel),
+        sort_keys: true,
+        ..Default::default()
+    }
+    .to_parse_options(true);
+    
+    let _ = PARSER.parse_account(&account);
+    let _ = PARSER.parse_account_with_options(&account, &options);
+    let _ = PARSER.parse_instruction(&InstructionData {
+        program_id: PROGRAM_ID.to_string(),
+        data: data.to_vec(),
+        accounts: Vec::new(),
+    });
+    let _ = PARSER.parse_any(data);
+});
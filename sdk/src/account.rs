@@ -14,8 +14,14 @@ pub trait AccountParser: Sized {
     fn try_parse(data: &[u8]) -> Result<Self, ParseError>;
     
     /// Check if this parser can handle the given data
+    /// 
+    /// When a discriminator is available only the leading bytes are
+    /// compared; otherwise this falls back to a full `try_parse`.
     fn can_parse(data: &[u8]) -> bool {
-        Self::try_parse(data).is_ok()
+        match Self::discriminator() {
+            Some(discriminator) => data.starts_with(&discriminator),
+            None => Self::try_parse(data).is_ok(),
+        }
     }
     
     /// Convert to JSON string for output
@@ -27,6 +33,18 @@ pub trait AccountParser: Sized {
             .map_err(|e| ParseError::InvalidData(e.to_string()))
     }
     
+    /// Convert to a JSON value for output
+    /// 
+    /// Used by `ParserBuilder::register`, so the data is only serialized
+    /// to a string once.
+    fn to_value(&self) -> Result<serde_json::Value, ParseError>
+    where
+        Self: serde::Serialize,
+    {
+        serde_json::to_value(self)
+            .map_err(|e| ParseError::InvalidData(e.to_string()))
+    }
+    
     /// Optional: Get discriminator bytes if applicable
     fn discriminator() -> Option<Vec<u8>> {
         None
@@ -0,0 +1,49 @@
+//! Compressed account (state compression) leaves
+//!
+//! Compressed accounts, e.g. Light Protocol state or cNFT metadata, live in a
+//! Merkle tree instead of on-chain accounts, so indexers hand us leaf data
+//! rather than a `SolanaAccount`.
+
+use crate::bindings::component::solana_rpcx_bindings::types::ParseError;
+
+/// Length of the data hash at the start of a leaf
+pub const LEAF_HASH_LEN: usize = 32;
+
+/// A compressed account leaf split into its hash and account bytes
+///
+/// Leaves are assumed to be laid out as:
+///
+/// ```text
+/// [ data hash: 32 bytes ][ discriminator ][ Borsh body ]
+/// ```
+///
+/// i.e. the hash the tree commits to, followed by the account's serialized
+/// bytes exactly as a normal account would hold them. The hash is passed
+/// through, not verified: trees hash with Poseidon or Keccak depending on the
+/// program, and proving inclusion is the indexer's job. There is no owner,
+/// lamports or pubkey, so the owner check, closed-account detection and
+/// fallback of `parse_account` don't apply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompressedLeaf<'a> {
+    /// Hash of `data` as committed to in the tree
+    pub data_hash: [u8; LEAF_HASH_LEN],
+    /// The serialized account: discriminator followed by the Borsh body
+    pub data: &'a [u8],
+}
+
+impl<'a> CompressedLeaf<'a> {
+    /// Split raw leaf bytes, failing if the hash is incomplete
+    pub fn split(leaf: &'a [u8]) -> Result<Self, ParseError> {
+        if leaf.len() < LEAF_HASH_LEN {
+            return Err(ParseError::InsufficientData(format!(
+                "Compressed leaf too short: need {} hash bytes at offset 0, have {}", LEAF_HASH_LEN, leaf.len()
+            )));
+        }
+
+        let (hash, data) = leaf.split_at(LEAF_HASH_LEN);
+        let mut data_hash = [0u8; LEAF_HASH_LEN];
+        data_hash.copy_from_slice(hash);
+
+        Ok(Self { data_hash, data })
+    }
+}
@@ -3,4 +3,180 @@
 //! Different programs use different discriminator approaches.
 //! This module provides abstractions for handling them.
 
-// Future: Add trait-based discriminator strategies
\ No newline at end of file
+use crate::{compute_anchor_discriminator, to_snake_case};
+
+/// How an account type is recognized from its data
+///
+/// Pass one to [`ParserBuilder::register_account_with_strategy`](crate::ParserBuilder::register_account_with_strategy);
+/// the specialized `register_*` methods are shorthands for the built-in
+/// strategies. The Borsh body is read after the bytes [`extract`](Self::extract)
+/// returns.
+pub trait DiscriminatorStrategy: Send + Sync {
+    /// The discriminator bytes at the start of `data`, or `None` if the data
+    /// is too short or the strategy has none
+    fn extract(&self, data: &[u8]) -> Option<Vec<u8>>;
+
+    /// Whether `data` carries the expected discriminator
+    fn matches(&self, data: &[u8]) -> bool;
+
+    /// Fixed leading bytes every match starts with, used to index dispatch
+    ///
+    /// Strategies returning `None` (the default) are tried on every account
+    /// and filtered with [`matches`](Self::matches), like a parser without a
+    /// discriminator.
+    fn prefix(&self) -> Option<Vec<u8>> {
+        None
+    }
+}
+
+/// Exact leading bytes of any length
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Prefix(pub Vec<u8>);
+
+impl DiscriminatorStrategy for Prefix {
+    fn extract(&self, data: &[u8]) -> Option<Vec<u8>> {
+        data.get(..self.0.len()).map(<[u8]>::to_vec)
+    }
+
+    fn matches(&self, data: &[u8]) -> bool {
+        data.starts_with(&self.0)
+    }
+
+    fn prefix(&self) -> Option<Vec<u8>> {
+        Some(self.0.clone())
+    }
+}
+
+/// Anchor's 8-byte `sha256("account:<Name>")` prefix
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Anchor8(pub [u8; 8]);
+
+impl Anchor8 {
+    /// The discriminator Anchor derives for account type `name`
+    pub fn account(name: &str) -> Self {
+        Self(compute_anchor_discriminator("account", name))
+    }
+}
+
+/// A single tag byte at offset 0, common in native and Pinocchio programs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct U8Tag(pub u8);
+
+/// A little-endian `u16` tag at offset 0
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct U16LE(pub u16);
+
+/// No discriminator: every account is a candidate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct NoDiscriminator;
+
+macro_rules! fixed_prefix_strategy {
+    ($ty:ty, |$this:ident| $bytes:expr) => {
+        impl DiscriminatorStrategy for $ty {
+            fn extract(&self, data: &[u8]) -> Option<Vec<u8>> {
+                let $this = self;
+                data.get(..$bytes.len()).map(<[u8]>::to_vec)
+            }
+
+            fn matches(&self, data: &[u8]) -> bool {
+                let $this = self;
+                data.starts_with(&$bytes)
+            }
+
+            fn prefix(&self) -> Option<Vec<u8>> {
+                let $this = self;
+                Some($bytes.to_vec())
+            }
+        }
+    };
+}
+
+fixed_prefix_strategy!(Anchor8, |this| this.0);
+fixed_prefix_strategy!(U8Tag, |this| [this.0]);
+fixed_prefix_strategy!(U16LE, |this| this.0.to_le_bytes());
+
+impl DiscriminatorStrategy for NoDiscriminator {
+    fn extract(&self, _data: &[u8]) -> Option<Vec<u8>> {
+        None
+    }
+
+    fn matches(&self, _data: &[u8]) -> bool {
+        true
+    }
+}
+
+/// Anchor namespaces tried when none are given
+pub const DEFAULT_NAMESPACES: [&str; 4] = ["account", "global", "event", "state"];
+
+/// Find the Anchor preimage `namespace:name` that produces a discriminator
+///
+/// For identifying unknown account types: pass the account's leading bytes
+/// (anything past 8 bytes is ignored, and shorter prefixes match on the
+/// bytes given) along with guessed type names. Each name is also tried in
+/// snake_case, PascalCase, camelCase and lowercase, so `"split_wallet"`
+/// finds `SplitWallet`. An empty `namespaces` means [`DEFAULT_NAMESPACES`].
+/// Returns the matching `(namespace, name)` as spelled in the preimage.
+///
+/// ```
+/// use solana_rpcx_sdk::{compute_anchor_discriminator, find_discriminator_source};
+///
+/// let data = compute_anchor_discriminator("account", "SplitWallet");
+///
+/// assert_eq!(
+///     find_discriminator_source(&data, &["vault", "split_wallet"], &[]),
+///     Some(("account".to_string(), "SplitWallet".to_string())),
+/// );
+/// assert_eq!(find_discriminator_source(&data, &["vault"], &["account"]), None);
+/// ```
+pub fn find_discriminator_source(
+    discriminator: &[u8],
+    names: &[&str],
+    namespaces: &[&str],
+) -> Option<(String, String)> {
+    let discriminator = &discriminator[..discriminator.len().min(8)];
+    if discriminator.is_empty() {
+        return None;
+    }
+
+    let namespaces = if namespaces.is_empty() { &DEFAULT_NAMESPACES[..] } else { namespaces };
+
+    for name in names {
+        for candidate in name_variants(name) {
+            for namespace in namespaces {
+                let computed = compute_anchor_discriminator(namespace, &candidate);
+                if computed.starts_with(discriminator) {
+                    return Some((namespace.to_string(), candidate));
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// A name as given, then in the casings programs commonly use
+fn name_variants(name: &str) -> Vec<String> {
+    let snake = to_snake_case(name);
+    let pascal: String = snake.split('_').map(capitalize).collect();
+    let camel = match pascal.chars().next() {
+        Some(first) => first.to_ascii_lowercase().to_string() + &pascal[first.len_utf8()..],
+        None => String::new(),
+    };
+    let flat = snake.replace('_', "");
+
+    let mut variants = Vec::new();
+    for variant in [name.to_string(), snake, pascal, camel, flat] {
+        if !variant.is_empty() && !variants.contains(&variant) {
+            variants.push(variant);
+        }
+    }
+    variants
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
@@ -0,0 +1,66 @@
+//! Discriminator-based dispatch
+//!
+//! Shared by account and instruction parsing.
+
+use std::collections::HashMap;
+
+/// Parser positions keyed by discriminator bytes
+type Bucket = HashMap<Vec<u8>, Vec<usize>>;
+
+/// Index from discriminator to parser position
+/// 
+/// Lookup only returns parsers whose discriminator prefixes the data, plus
+/// parsers without a discriminator, so dispatch doesn't have to try every
+/// registered parser.
+pub(crate) struct DiscriminatorIndex {
+    /// Buckets grouped by discriminator length
+    by_len: Vec<(usize, Bucket)>,
+    /// Positions of parsers without a discriminator
+    undiscriminated: Vec<usize>,
+}
+
+impl DiscriminatorIndex {
+    /// Build an index over parsers' discriminators, in dispatch order
+    pub(crate) fn new<'a>(discriminators: impl Iterator<Item = &'a Option<Vec<u8>>>) -> Self {
+        let mut by_len: Vec<(usize, Bucket)> = Vec::new();
+        let mut undiscriminated = Vec::new();
+        
+        for (position, discriminator) in discriminators.enumerate() {
+            let Some(discriminator) = discriminator else {
+                undiscriminated.push(position);
+                continue;
+            };
+            
+            let bucket = match by_len.iter().position(|(len, _)| *len == discriminator.len()) {
+                Some(i) => &mut by_len[i].1,
+                None => {
+                    by_len.push((discriminator.len(), HashMap::new()));
+                    &mut by_len.last_mut().unwrap().1
+                }
+            };
+            bucket.entry(discriminator.clone()).or_default().push(position);
+        }
+        
+        Self { by_len, undiscriminated }
+    }
+    
+    /// Longest registered discriminator, if any parser has one
+    pub(crate) fn max_len(&self) -> Option<usize> {
+        self.by_len.iter().map(|(len, _)| *len).max()
+    }
+    
+    /// Positions of the parsers that could match `data`, in dispatch order
+    pub(crate) fn candidates(&self, data: &[u8]) -> Vec<usize> {
+        let mut positions: Vec<usize> = self.by_len
+            .iter()
+            .filter(|(len, _)| data.len() >= *len)
+            .filter_map(|(len, bucket)| bucket.get(&data[..*len]))
+            .flatten()
+            .chain(&self.undiscriminated)
+            .copied()
+            .collect();
+        
+        positions.sort_unstable();
+        positions
+    }
+}
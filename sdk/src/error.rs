@@ -1,5 +1,13 @@
 use crate::bindings::component::solana_rpcx_bindings::types::ParseError;
 
+/// [`SdkError::ClosedAccount`] becomes an `UnknownAccountType` with this prefix
+pub use crate::bindings::CLOSED_ACCOUNT_PREFIX;
+
+/// Whether a parse error reports a closed (all-zero) account
+pub fn is_closed_account(error: &ParseError) -> bool {
+    matches!(error, ParseError::UnknownAccountType(msg) if msg.starts_with(CLOSED_ACCOUNT_PREFIX))
+}
+
 /// SDK-specific errors that convert to ParseError
 #[derive(Debug)]
 pub enum SdkError {
@@ -7,8 +15,54 @@ pub enum SdkError {
     InvalidData(String),
     InsufficientData(String),
     UnknownType(String),
+    /// The account looks closed: no parser matched and its discriminator
+    /// bytes are all zero
+    ClosedAccount(String),
+    /// Decoding failed at a known byte offset within the data
+    FieldError {
+        field: String,
+        offset: usize,
+        reason: String,
+    },
+}
+
+impl SdkError {
+    /// Prefix a field error with the name of the enclosing field, building
+    /// paths like `members[2].shares`
+    pub(crate) fn in_field(self, name: &str) -> Self {
+        match self {
+            SdkError::FieldError { field, offset, reason } => {
+                let field = if field.is_empty() {
+                    name.to_string()
+                } else if field.starts_with('[') {
+                    format!("{}{}", name, field)
+                } else {
+                    format!("{}.{}", name, field)
+                };
+                SdkError::FieldError { field, offset, reason }
+            }
+            other => other,
+        }
+    }
 }
 
+impl std::fmt::Display for SdkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SdkError::DeserializationFailed(msg) => write!(f, "Deserialization failed: {}", msg),
+            SdkError::InvalidData(msg) => write!(f, "Invalid data: {}", msg),
+            SdkError::InsufficientData(msg) => write!(f, "Insufficient data: {}", msg),
+            SdkError::UnknownType(msg) => write!(f, "Unknown type: {}", msg),
+            SdkError::ClosedAccount(msg) => write!(f, "{}{}", CLOSED_ACCOUNT_PREFIX, msg),
+            SdkError::FieldError { field, offset, reason } => {
+                write!(f, "Field `{}` at offset {}: {}", field, offset, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SdkError {}
+
 impl From<SdkError> for ParseError {
     fn from(err: SdkError) -> ParseError {
         match err {
@@ -16,6 +70,127 @@ impl From<SdkError> for ParseError {
             SdkError::InvalidData(msg) => ParseError::InvalidData(msg),
             SdkError::InsufficientData(msg) => ParseError::InsufficientData(msg),
             SdkError::UnknownType(msg) => ParseError::UnknownAccountType(msg),
+            SdkError::ClosedAccount(msg) => {
+                ParseError::UnknownAccountType(format!("{}{}", CLOSED_ACCOUNT_PREFIX, msg))
+            }
+            SdkError::FieldError { field, offset, reason } => ParseError::DeserializationFailed(
+                format!("Field `{}` at offset {}: {}", field, offset, reason)
+            ),
+        }
+    }
+}
+
+/// Configuration problems detected when building a `Parser`
+#[derive(Debug, Clone, PartialEq)]
+pub enum BuildError {
+    /// The program id is not a 32-byte base58 pubkey
+    InvalidProgramId {
+        program_id: String,
+        reason: String,
+    },
+    /// Two parsers of the same kind share a discriminator
+    DuplicateDiscriminator {
+        discriminator: Vec<u8>,
+        first: String,
+        second: String,
+    },
+    /// Two account parsers share both a discriminator and a data length
+    DuplicateLayout {
+        discriminator: Vec<u8>,
+        data_len: usize,
+        first: String,
+        second: String,
+    },
+    /// Two parsers of the same kind share a name
+    DuplicateName(String),
+    /// A parser without a discriminator is tried before one with a
+    /// discriminator, so it can match that parser's data first
+    ShadowedParser {
+        undiscriminated: String,
+        shadowed: String,
+    },
+    /// Account names were given for an instruction that isn't registered
+    UnknownInstruction(String),
+    /// A discriminator is empty or longer than `MAX_DISCRIMINATOR_LEN`
+    InvalidDiscriminator {
+        name: String,
+        len: usize,
+    },
+    /// Parsers for different programs were merged
+    ProgramIdMismatch {
+        first: String,
+        second: String,
+    },
+}
+
+impl std::fmt::Display for BuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BuildError::InvalidProgramId { program_id, reason } => write!(
+                f,
+                "program id {:?} is not a valid pubkey ({}); call allow_any_program_id() to skip this check",
+                program_id, reason
+            ),
+            BuildError::DuplicateDiscriminator { discriminator, first, second } => write!(
+                f,
+                "{} and {} share discriminator {:?}",
+                first, second, discriminator
+            ),
+            BuildError::DuplicateLayout { discriminator, data_len, first, second } => write!(
+                f,
+                "{} and {} share discriminator {:?} and data length {}",
+                first, second, discriminator, data_len
+            ),
+            BuildError::DuplicateName(name) => write!(f, "{} is registered more than once", name),
+            BuildError::ShadowedParser { undiscriminated, shadowed } => write!(
+                f,
+                "{} has no discriminator and is registered before {}, which it can shadow; \
+                 register it last or call allow_mixed_discriminators()",
+                undiscriminated, shadowed
+            ),
+            BuildError::UnknownInstruction(name) => write!(
+                f,
+                "account names given for {}, which is not a registered instruction",
+                name
+            ),
+            BuildError::InvalidDiscriminator { name, len: 0 } => write!(
+                f,
+                "{} has an empty discriminator, which matches everything; register it without one instead",
+                name
+            ),
+            BuildError::InvalidDiscriminator { name, len } => write!(
+                f,
+                "{} has a {}-byte discriminator; at most {} bytes are allowed",
+                name, len, crate::MAX_DISCRIMINATOR_LEN
+            ),
+            BuildError::ProgramIdMismatch { first, second } => write!(
+                f,
+                "cannot merge parsers for different programs ({} and {})",
+                first, second
+            ),
         }
     }
-}
\ No newline at end of file
+}
+
+impl std::error::Error for BuildError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn closed_accounts_keep_their_code_across_the_boundary() {
+        let error = ParseError::from(SdkError::ClosedAccount("9xQe has all-zero discriminator bytes".to_string()));
+
+        assert!(is_closed_account(&error));
+        assert_eq!(error.code(), crate::bindings::error_code::CLOSED_ACCOUNT);
+    }
+
+    #[test]
+    fn other_unknown_types_are_not_closed() {
+        let error = ParseError::from(SdkError::UnknownType("No parser matched".to_string()));
+
+        assert!(!is_closed_account(&error));
+        assert_eq!(error.code(), crate::bindings::error_code::UNKNOWN_ACCOUNT_TYPE);
+    }
+}
@@ -0,0 +1,158 @@
+//! Anchor events from transaction logs
+//!
+//! `emit!` writes an event with `sol_log_data`, which shows up in a
+//! transaction's `meta.logMessages` as a `Program data: <base64>` line
+//! holding the 8-byte event discriminator and the Borsh payload. The line
+//! doesn't name the program, so it's attributed to whichever program the
+//! surrounding `Program <id> invoke [n]` / `success` lines say was running.
+//! `emit_cpi!` events travel as self-CPI instruction data instead, behind
+//! [`ANCHOR_EVENT_IX_TAG`].
+
+use base64::{engine::general_purpose, Engine as _};
+use crate::bindings::component::solana_rpcx_bindings::types::*;
+
+/// Prefix of the log lines `sol_log_data` writes
+pub const PROGRAM_DATA_PREFIX: &str = "Program data: ";
+
+/// Leading bytes of `emit_cpi!` instruction data, before the event itself
+pub const ANCHOR_EVENT_IX_TAG: [u8; 8] = [0xe4, 0x45, 0xa5, 0x2e, 0x51, 0xcb, 0x9a, 0x1d];
+
+/// The runtime's marker that the remaining logs were dropped
+const LOG_TRUNCATED: &str = "Log truncated";
+
+/// A `Program data:` line, decoded
+#[derive(Debug, Clone)]
+pub struct ProgramDataLog {
+    /// Position of the line in `logMessages`
+    pub log_index: usize,
+    /// The program executing when the line was written, if the invoke lines
+    /// before it say
+    pub program_id: Option<String>,
+    /// The logged bytes, or why they couldn't be decoded
+    pub data: Result<Vec<u8>, ParseError>,
+}
+
+/// An event parsed from a `Program data:` line
+#[derive(Debug, Clone)]
+pub struct LogEvent {
+    pub log_index: usize,
+    pub program_id: Option<String>,
+    /// The event name is the parsed `account_type`
+    pub result: Result<ParsedAccount, ParseError>,
+}
+
+/// Find and decode every `Program data:` line in a transaction's logs
+///
+/// `sol_log_data` separates the slices it was given with spaces; they are
+/// concatenated (Anchor logs a single slice). Scanning stops at the
+/// runtime's `Log truncated` marker, since nothing after it was recorded.
+///
+/// ```
+/// use solana_rpcx_sdk::program_data_logs;
+///
+/// let logs = [
+///     "Program 11111111111111111111111111111111 invoke [1]",
+///     "Program data: AQID",
+///     "Program data: AQ",
+///     "Program 11111111111111111111111111111111 success",
+///     "Program data: BA==",
+/// ];
+/// let found = program_data_logs(&logs);
+/// assert_eq!(found[0].data.as_deref().unwrap(), [1, 2, 3]);
+/// assert_eq!(found[0].program_id.as_deref(), Some("11111111111111111111111111111111"));
+/// assert!(found[1].data.is_err());
+/// assert_eq!(found[2].program_id, None);
+/// ```
+pub fn program_data_logs<S: AsRef<str>>(logs: &[S]) -> Vec<ProgramDataLog> {
+    let mut stack: Vec<&str> = Vec::new();
+    let mut found = Vec::new();
+
+    for (log_index, line) in logs.iter().enumerate() {
+        let line = line.as_ref();
+        if line.starts_with(LOG_TRUNCATED) {
+            break;
+        }
+
+        if let Some(encoded) = line.strip_prefix(PROGRAM_DATA_PREFIX) {
+            found.push(ProgramDataLog {
+                log_index,
+                program_id: stack.last().map(|id| id.to_string()),
+                data: decode_slices(encoded),
+            });
+        } else if let Some(rest) = line.strip_prefix("Program ") {
+            let mut words = rest.split_whitespace();
+            match (words.next(), words.next()) {
+                (Some(program_id), Some("invoke")) => stack.push(program_id),
+                (Some(_), Some("success" | "failed:")) => {
+                    stack.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    found
+}
+
+fn decode_slices(encoded: &str) -> Result<Vec<u8>, ParseError> {
+    let mut data = Vec::new();
+    for slice in encoded.split_whitespace() {
+        let decoded = general_purpose::STANDARD.decode(slice)
+            .map_err(|e| ParseError::InvalidData(format!("Malformed Program data line: {}", e)))?;
+        data.extend(decoded);
+    }
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROGRAM: &str = "11111111111111111111111111111111";
+    const OTHER: &str = "SysvarRent111111111111111111111111111111111";
+
+    #[test]
+    fn scanning_stops_at_log_truncated() {
+        let logs = [
+            format!("Program {} invoke [1]", PROGRAM),
+            "Program data: AQ==".to_string(),
+            "Log truncated".to_string(),
+            "Program data: Ag==".to_string(),
+        ];
+
+        let found = program_data_logs(&logs);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].data.as_deref().unwrap(), [1]);
+    }
+
+    #[test]
+    fn nested_invokes_are_attributed_to_the_running_program() {
+        let logs = [
+            format!("Program {} invoke [1]", PROGRAM),
+            format!("Program {} invoke [2]", OTHER),
+            "Program data: AQ==".to_string(),
+            format!("Program {} failed: custom program error: 0x1", OTHER),
+            "Program data: Ag==".to_string(),
+            format!("Program {} success", PROGRAM),
+        ];
+
+        let found = program_data_logs(&logs);
+        assert_eq!(found[0].program_id.as_deref(), Some(OTHER));
+        assert_eq!(found[1].program_id.as_deref(), Some(PROGRAM));
+        assert_eq!(found[1].log_index, 4);
+    }
+
+    #[test]
+    fn slices_are_concatenated() {
+        let found = program_data_logs(&["Program data: AQ== AgM="]);
+        assert_eq!(found[0].data.as_deref().unwrap(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn malformed_base64_only_fails_its_own_line() {
+        let found = program_data_logs(&["Program data: %%%", "Program data: AQ=="]);
+
+        assert!(matches!(&found[0].data, Err(ParseError::InvalidData(msg)) if msg.starts_with("Malformed Program data line")));
+        assert_eq!(found[1].data.as_deref().unwrap(), [1]);
+    }
+}
@@ -0,0 +1,748 @@
+//! Anchor IDL support
+//!
+//! Loads an Anchor IDL JSON document and decodes Borsh-encoded account and
+//! instruction data into JSON driven by the IDL type layout, so no
+//! hand-written `to_json` function is needed.
+//!
+//! Both the legacy IDL format (`publicKey`, account layouts inline) and the
+//! Anchor 0.30+ format (`pubkey`, explicit discriminators, layouts in `types`)
+//! are accepted.
+
+use serde_json::{Map, Value};
+use crate::bindings::component::solana_rpcx_bindings::types::*;
+use crate::error::*;
+
+/// A field type from the IDL
+#[derive(Debug, Clone, PartialEq)]
+pub enum IdlType {
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    F32,
+    F64,
+    String,
+    Bytes,
+    Pubkey,
+    Vec(Box<IdlType>),
+    Option(Box<IdlType>),
+    Array(Box<IdlType>, usize),
+    /// Reference to an entry in the IDL's `types` section
+    Defined(String),
+}
+
+/// A named field of an IDL struct
+#[derive(Debug, Clone)]
+pub struct IdlField {
+    pub name: String,
+    pub ty: IdlType,
+}
+
+/// An account type declared in the IDL
+#[derive(Debug, Clone)]
+pub struct IdlAccount {
+    pub name: String,
+    pub discriminator: Vec<u8>,
+    pub fields: Vec<IdlField>,
+}
+
+/// An instruction declared in the IDL
+#[derive(Debug, Clone)]
+pub struct IdlInstruction {
+    pub name: String,
+    pub discriminator: Vec<u8>,
+    pub args: Vec<IdlField>,
+    /// Names of the instruction's accounts in order, with legacy nested
+    /// account groups flattened
+    pub accounts: Vec<String>,
+}
+
+/// A user-defined type from the IDL's `types` section
+#[derive(Debug, Clone)]
+pub struct IdlTypeDef {
+    pub name: String,
+    pub kind: IdlTypeDefKind,
+}
+
+/// Layout of a user-defined type
+#[derive(Debug, Clone)]
+pub enum IdlTypeDefKind {
+    Struct(Vec<IdlField>),
+    /// Borsh enum, encoded as a `u8` variant index followed by the variant's fields
+    Enum(Vec<IdlEnumVariant>),
+}
+
+/// A single variant of an IDL enum
+#[derive(Debug, Clone)]
+pub struct IdlEnumVariant {
+    pub name: String,
+    pub fields: IdlVariantFields,
+}
+
+/// Fields carried by an enum variant
+#[derive(Debug, Clone)]
+pub enum IdlVariantFields {
+    Unit,
+    Named(Vec<IdlField>),
+    Tuple(Vec<IdlType>),
+}
+
+/// Default maximum nesting of defined types, vecs, options and arrays while
+/// decoding
+///
+/// Deeper data fails with `InvalidData` instead of overflowing the stack on
+/// self-referential types; see
+/// [`ParserBuilder::max_decode_depth`](crate::ParserBuilder::max_decode_depth).
+pub const MAX_DECODE_DEPTH: usize = 32;
+
+/// A parsed Anchor IDL
+#[derive(Debug, Clone)]
+pub struct Idl {
+    /// Program address, if the IDL declares one
+    pub address: Option<String>,
+    pub name: Option<String>,
+    pub version: Option<String>,
+    pub accounts: Vec<IdlAccount>,
+    pub instructions: Vec<IdlInstruction>,
+    pub types: Vec<IdlTypeDef>,
+}
+
+impl Idl {
+    /// Parse an IDL from its JSON representation
+    pub fn from_json(idl_json: &str) -> Result<Self, SdkError> {
+        let root: Value = serde_json::from_str(idl_json)
+            .map_err(|e| SdkError::InvalidData(format!("Invalid IDL JSON: {}", e)))?;
+
+        let address = root.get("address")
+            .or_else(|| root.get("metadata").and_then(|m| m.get("address")))
+            .and_then(Value::as_str)
+            .map(str::to_string);
+
+        let name = root.get("name")
+            .or_else(|| root.get("metadata").and_then(|m| m.get("name")))
+            .and_then(Value::as_str)
+            .map(str::to_string);
+
+        let version = root.get("version")
+            .or_else(|| root.get("metadata").and_then(|m| m.get("version")))
+            .and_then(Value::as_str)
+            .map(str::to_string);
+
+        let raw_types = root.get("types").and_then(Value::as_array);
+
+        let mut accounts = Vec::new();
+        for account in root.get("accounts").and_then(Value::as_array).into_iter().flatten() {
+            accounts.push(parse_account(account, raw_types)?);
+        }
+
+        let mut instructions = Vec::new();
+        for instruction in root.get("instructions").and_then(Value::as_array).into_iter().flatten() {
+            instructions.push(parse_instruction(instruction)?);
+        }
+
+        let mut types = Vec::new();
+        for ty in raw_types.into_iter().flatten() {
+            types.push(parse_type_def(ty)?);
+        }
+
+        let idl = Self { address, name, version, accounts, instructions, types };
+        idl.check_references()?;
+        Ok(idl)
+    }
+
+    /// Look up a user-defined type by name
+    pub fn type_def(&self, name: &str) -> Option<&IdlTypeDef> {
+        self.types.iter().find(|t| t.name == name)
+    }
+
+    /// Decode account data, skipping over the discriminator
+    ///
+    /// Error offsets are relative to the start of `data`.
+    pub fn decode_account(&self, account: &IdlAccount, data: &[u8]) -> Result<Value, ParseError> {
+        self.decode_account_with_max_depth(account, data, MAX_DECODE_DEPTH)
+    }
+
+    /// [`decode_account`](Self::decode_account) with a nesting limit other
+    /// than [`MAX_DECODE_DEPTH`]
+    pub fn decode_account_with_max_depth(
+        &self,
+        account: &IdlAccount,
+        data: &[u8],
+        max_depth: usize,
+    ) -> Result<Value, ParseError> {
+        let mut cursor = Cursor::new(data);
+        cursor.take(account.discriminator.len()).map_err(|e| e.in_field("discriminator"))?;
+        Ok(self.decode_fields(&account.fields, &mut cursor, Depth::new(max_depth))?)
+    }
+
+    /// Decode instruction data, skipping over the discriminator, into an
+    /// object mapping argument name to value
+    pub fn decode_instruction(&self, instruction: &IdlInstruction, data: &[u8]) -> Result<Value, ParseError> {
+        self.decode_instruction_with_max_depth(instruction, data, MAX_DECODE_DEPTH)
+    }
+
+    /// [`decode_instruction`](Self::decode_instruction) with a nesting limit
+    /// other than [`MAX_DECODE_DEPTH`]
+    pub fn decode_instruction_with_max_depth(
+        &self,
+        instruction: &IdlInstruction,
+        data: &[u8],
+        max_depth: usize,
+    ) -> Result<Value, ParseError> {
+        let mut cursor = Cursor::new(data);
+        cursor.take(instruction.discriminator.len()).map_err(|e| e.in_field("discriminator"))?;
+        Ok(self.decode_fields(&instruction.args, &mut cursor, Depth::new(max_depth))?)
+    }
+
+    /// Make sure every `defined` reference points at a known type
+    fn check_references(&self) -> Result<(), SdkError> {
+        let types = self.accounts.iter().flat_map(|a| &a.fields)
+            .chain(self.instructions.iter().flat_map(|i| &i.args))
+            .map(|f| &f.ty)
+            .chain(self.types.iter().flat_map(|t| t.field_types()));
+
+        for mut ty in types {
+            loop {
+                match ty {
+                    IdlType::Vec(inner) | IdlType::Option(inner) | IdlType::Array(inner, _) => ty = inner,
+                    IdlType::Defined(name) => {
+                        if self.type_def(name).is_none() {
+                            return Err(SdkError::InvalidData(format!("Unknown IDL type: {}", name)));
+                        }
+                        break;
+                    }
+                    _ => break,
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn decode_fields(&self, fields: &[IdlField], cursor: &mut Cursor, depth: Depth) -> Result<Value, SdkError> {
+        let mut object = Map::new();
+        for field in fields {
+            let value = self.decode_value(&field.ty, cursor, depth)
+                .map_err(|e| e.in_field(&field.name))?;
+            object.insert(field.name.clone(), value);
+        }
+        Ok(Value::Object(object))
+    }
+
+    fn decode_value(&self, ty: &IdlType, cursor: &mut Cursor, depth: Depth) -> Result<Value, SdkError> {
+        // Recursive type references would otherwise recurse until the stack overflows
+        let depth = match ty {
+            IdlType::Vec(_) | IdlType::Option(_) | IdlType::Array(..) | IdlType::Defined(_) => depth.deeper()?,
+            _ => depth,
+        };
+
+        Ok(match ty {
+            IdlType::Vec(inner) => {
+                let len = u32::from_le_bytes(cursor.take_array()?) as usize;
+                // Every element takes at least one byte, so a longer length is bogus
+                if len > cursor.remaining() {
+                    return Err(cursor.error(format!(
+                        "Vec length {} exceeds remaining {} bytes", len, cursor.remaining(),
+                    )));
+                }
+                let mut items = Vec::with_capacity(len);
+                for i in 0..len {
+                    items.push(self.decode_value(inner, cursor, depth).map_err(|e| e.in_field(&format!("[{}]", i)))?);
+                }
+                Value::Array(items)
+            }
+            IdlType::Option(inner) => match cursor.take_array::<1>()?[0] {
+                0 => Value::Null,
+                1 => self.decode_value(inner, cursor, depth)?,
+                other => return Err(cursor.error_at_last(format!("Invalid option tag {}", other))),
+            },
+            IdlType::Array(inner, len) => {
+                let mut items = Vec::with_capacity((*len).min(cursor.remaining()));
+                for i in 0..*len {
+                    items.push(self.decode_value(inner, cursor, depth).map_err(|e| e.in_field(&format!("[{}]", i)))?);
+                }
+                Value::Array(items)
+            }
+            IdlType::Defined(name) => {
+                let def = self.type_def(name)
+                    .ok_or_else(|| SdkError::InvalidData(format!("Unknown IDL type: {}", name)))?;
+                match &def.kind {
+                    IdlTypeDefKind::Struct(fields) => self.decode_fields(fields, cursor, depth)?,
+                    IdlTypeDefKind::Enum(variants) => self.decode_enum(def, variants, cursor, depth)?,
+                }
+            }
+            scalar => decode_scalar(scalar, cursor)?,
+        })
+    }
+
+    /// Enums render like serde's externally tagged representation: unit
+    /// variants as `"Name"`, others as `{"Name": {...fields}}` or
+    /// `{"Name": [...values]}`
+    fn decode_enum(
+        &self,
+        def: &IdlTypeDef,
+        variants: &[IdlEnumVariant],
+        cursor: &mut Cursor,
+        depth: Depth,
+    ) -> Result<Value, SdkError> {
+        let index = cursor.take_array::<1>()?[0];
+        let variant = variants.get(index as usize)
+            .ok_or_else(|| cursor.error_at_last(format!(
+                "Invalid variant index {} for enum {}", index, def.name
+            )))?;
+
+        let value = match &variant.fields {
+            IdlVariantFields::Unit => return Ok(Value::String(variant.name.clone())),
+            IdlVariantFields::Named(fields) => self.decode_fields(fields, cursor, depth)
+                .map_err(|e| e.in_field(&variant.name))?,
+            IdlVariantFields::Tuple(types) => {
+                let mut items = Vec::with_capacity(types.len());
+                for (i, ty) in types.iter().enumerate() {
+                    items.push(self.decode_value(ty, cursor, depth)
+                        .map_err(|e| e.in_field(&format!("{}.{}", variant.name, i)))?);
+                }
+                Value::Array(items)
+            }
+        };
+
+        let mut object = Map::new();
+        object.insert(variant.name.clone(), value);
+        Ok(Value::Object(object))
+    }
+}
+
+impl IdlTypeDef {
+    /// All field types referenced directly by this type
+    fn field_types(&self) -> Vec<&IdlType> {
+        match &self.kind {
+            IdlTypeDefKind::Struct(fields) => fields.iter().map(|f| &f.ty).collect(),
+            IdlTypeDefKind::Enum(variants) => variants
+                .iter()
+                .flat_map(|v| match &v.fields {
+                    IdlVariantFields::Unit => Vec::new(),
+                    IdlVariantFields::Named(fields) => fields.iter().map(|f| &f.ty).collect(),
+                    IdlVariantFields::Tuple(types) => types.iter().collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+fn parse_account(account: &Value, types: Option<&Vec<Value>>) -> Result<IdlAccount, SdkError> {
+    let name = account.get("name")
+        .and_then(Value::as_str)
+        .ok_or_else(|| SdkError::InvalidData("IDL account is missing a name".to_string()))?
+        .to_string();
+
+    let discriminator = match account.get("discriminator") {
+        Some(disc) => parse_discriminator(disc, &name)?,
+        None => crate::compute_anchor_discriminator("account", &name).to_vec(),
+    };
+
+    // Legacy IDLs inline the layout; 0.30+ IDLs keep it in `types`
+    let layout = match account.get("type") {
+        Some(ty) => ty,
+        None => types
+            .into_iter()
+            .flatten()
+            .find(|t| t.get("name").and_then(Value::as_str) == Some(name.as_str()))
+            .and_then(|t| t.get("type"))
+            .ok_or_else(|| SdkError::InvalidData(format!("No type definition for account {}", name)))?,
+    };
+
+    let fields = parse_struct_fields(layout, &name)?;
+
+    Ok(IdlAccount { name, discriminator, fields })
+}
+
+fn parse_instruction(instruction: &Value) -> Result<IdlInstruction, SdkError> {
+    let name = instruction.get("name")
+        .and_then(Value::as_str)
+        .ok_or_else(|| SdkError::InvalidData("IDL instruction is missing a name".to_string()))?
+        .to_string();
+
+    // Legacy IDLs use camelCase names, but Anchor hashes the snake_case form
+    let discriminator = match instruction.get("discriminator") {
+        Some(disc) => parse_discriminator(disc, &name)?,
+        None => crate::anchor_instruction_discriminator(&name).to_vec(),
+    };
+
+    let args = parse_fields(instruction.get("args"), &name)?;
+
+    let mut accounts = Vec::new();
+    collect_account_names(instruction.get("accounts"), &mut accounts);
+
+    Ok(IdlInstruction { name, discriminator, args, accounts })
+}
+
+/// Flatten an instruction's `accounts`, descending into nested groups
+fn collect_account_names(accounts: Option<&Value>, names: &mut Vec<String>) {
+    for account in accounts.and_then(Value::as_array).into_iter().flatten() {
+        match account.get("accounts") {
+            Some(group) => collect_account_names(Some(group), names),
+            None => names.extend(account.get("name").and_then(Value::as_str).map(str::to_string)),
+        }
+    }
+}
+
+fn parse_type_def(ty: &Value) -> Result<IdlTypeDef, SdkError> {
+    let name = ty.get("name")
+        .and_then(Value::as_str)
+        .ok_or_else(|| SdkError::InvalidData("IDL type is missing a name".to_string()))?
+        .to_string();
+
+    let layout = ty.get("type")
+        .ok_or_else(|| SdkError::InvalidData(format!("IDL type {} has no layout", name)))?;
+
+    let kind = match layout.get("kind").and_then(Value::as_str) {
+        Some("enum") => IdlTypeDefKind::Enum(parse_variants(layout, &name)?),
+        _ => IdlTypeDefKind::Struct(parse_struct_fields(layout, &name)?),
+    };
+
+    Ok(IdlTypeDef { name, kind })
+}
+
+fn parse_variants(layout: &Value, name: &str) -> Result<Vec<IdlEnumVariant>, SdkError> {
+    layout.get("variants")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .map(|variant| {
+            let variant_name = variant.get("name")
+                .and_then(Value::as_str)
+                .ok_or_else(|| SdkError::InvalidData(format!("Unnamed variant in {}", name)))?;
+
+            let fields = match variant.get("fields").and_then(Value::as_array) {
+                None => IdlVariantFields::Unit,
+                Some(fields) if fields.is_empty() => IdlVariantFields::Unit,
+                // Named fields are objects with a `name`, tuple fields are bare types
+                Some(fields) if fields[0].get("name").is_some() => {
+                    IdlVariantFields::Named(parse_fields(variant.get("fields"), variant_name)?)
+                }
+                Some(fields) => IdlVariantFields::Tuple(
+                    fields.iter().map(parse_type).collect::<Result<_, _>>()?
+                ),
+            };
+
+            Ok(IdlEnumVariant {
+                name: variant_name.to_string(),
+                fields,
+            })
+        })
+        .collect()
+}
+
+fn parse_discriminator(disc: &Value, name: &str) -> Result<Vec<u8>, SdkError> {
+    disc.as_array()
+        .ok_or_else(|| SdkError::InvalidData(format!("Invalid discriminator for {}", name)))?
+        .iter()
+        .map(|b| {
+            b.as_u64()
+                .and_then(|b| u8::try_from(b).ok())
+                .ok_or_else(|| SdkError::InvalidData(format!("Invalid discriminator byte for {}", name)))
+        })
+        .collect()
+}
+
+fn parse_struct_fields(layout: &Value, name: &str) -> Result<Vec<IdlField>, SdkError> {
+    if layout.get("kind").and_then(Value::as_str) != Some("struct") {
+        return Err(SdkError::InvalidData(format!("Type {} is not a struct", name)));
+    }
+
+    parse_fields(layout.get("fields"), name)
+}
+
+fn parse_fields(fields: Option<&Value>, name: &str) -> Result<Vec<IdlField>, SdkError> {
+    fields
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .map(|field| {
+            let field_name = field.get("name")
+                .and_then(Value::as_str)
+                .ok_or_else(|| SdkError::InvalidData(format!("Unnamed field in {}", name)))?;
+            let ty = field.get("type")
+                .ok_or_else(|| SdkError::InvalidData(format!("Field {}.{} has no type", name, field_name)))?;
+
+            Ok(IdlField {
+                name: field_name.to_string(),
+                ty: parse_type(ty)?,
+            })
+        })
+        .collect()
+}
+
+fn parse_type(ty: &Value) -> Result<IdlType, SdkError> {
+    if let Some(object) = ty.as_object() {
+        return parse_compound_type(object, ty);
+    }
+
+    let ty_name = ty.as_str()
+        .ok_or_else(|| SdkError::InvalidData(format!("Unsupported IDL type: {}", ty)))?;
+
+    Ok(match ty_name {
+        "bool" => IdlType::Bool,
+        "u8" => IdlType::U8,
+        "u16" => IdlType::U16,
+        "u32" => IdlType::U32,
+        "u64" => IdlType::U64,
+        "u128" => IdlType::U128,
+        "i8" => IdlType::I8,
+        "i16" => IdlType::I16,
+        "i32" => IdlType::I32,
+        "i64" => IdlType::I64,
+        "i128" => IdlType::I128,
+        "f32" => IdlType::F32,
+        "f64" => IdlType::F64,
+        "string" => IdlType::String,
+        "bytes" => IdlType::Bytes,
+        "publicKey" | "pubkey" => IdlType::Pubkey,
+        other => return Err(SdkError::InvalidData(format!("Unsupported IDL type: {}", other))),
+    })
+}
+
+fn parse_compound_type(object: &Map<String, Value>, ty: &Value) -> Result<IdlType, SdkError> {
+    if let Some(inner) = object.get("vec") {
+        return Ok(IdlType::Vec(Box::new(parse_type(inner)?)));
+    }
+
+    if let Some(inner) = object.get("option") {
+        return Ok(IdlType::Option(Box::new(parse_type(inner)?)));
+    }
+
+    if let Some(array) = object.get("array").and_then(Value::as_array) {
+        if let [inner, len] = array.as_slice() {
+            let len = len.as_u64()
+                .ok_or_else(|| SdkError::InvalidData(format!("Unsupported array length: {}", len)))?;
+            return Ok(IdlType::Array(Box::new(parse_type(inner)?), len as usize));
+        }
+    }
+
+    // Legacy: {"defined": "Name"}, 0.30+: {"defined": {"name": "Name"}}
+    if let Some(defined) = object.get("defined") {
+        let name = defined.as_str()
+            .or_else(|| defined.get("name").and_then(Value::as_str))
+            .ok_or_else(|| SdkError::InvalidData(format!("Invalid defined type: {}", defined)))?;
+        return Ok(IdlType::Defined(name.to_string()));
+    }
+
+    Err(SdkError::InvalidData(format!("Unsupported IDL type: {}", ty)))
+}
+
+// Decoding
+
+struct Cursor<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, offset: 0 }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], SdkError> {
+        let end = self.offset.checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or_else(|| self.error(format!(
+                "need {} bytes, have {}",
+                len,
+                self.remaining(),
+            )))?;
+
+        let bytes = &self.data[self.offset..end];
+        self.offset = end;
+        Ok(bytes)
+    }
+
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N], SdkError> {
+        let mut out = [0u8; N];
+        out.copy_from_slice(self.take(N)?);
+        Ok(out)
+    }
+
+    fn remaining(&self) -> usize {
+        self.data.len() - self.offset
+    }
+
+    /// Error at the current offset; the field name is filled in by callers
+    fn error(&self, reason: String) -> SdkError {
+        SdkError::FieldError {
+            field: String::new(),
+            offset: self.offset,
+            reason,
+        }
+    }
+
+    /// Error about the single byte that was just consumed
+    fn error_at_last(&self, reason: String) -> SdkError {
+        SdkError::FieldError {
+            field: String::new(),
+            offset: self.offset.saturating_sub(1),
+            reason,
+        }
+    }
+}
+
+/// How deep decoding has nested, and how deep it may go
+#[derive(Debug, Clone, Copy)]
+struct Depth {
+    current: usize,
+    max: usize,
+}
+
+impl Depth {
+    fn new(max: usize) -> Self {
+        Self { current: 0, max }
+    }
+
+    fn deeper(self) -> Result<Self, SdkError> {
+        if self.current >= self.max {
+            return Err(SdkError::InvalidData(format!("Max nesting depth {} exceeded", self.max)));
+        }
+        Ok(Self { current: self.current + 1, ..self })
+    }
+}
+
+fn decode_scalar(ty: &IdlType, cursor: &mut Cursor) -> Result<Value, SdkError> {
+    Ok(match ty {
+        IdlType::Bool => match cursor.take_array::<1>()?[0] {
+            0 => Value::Bool(false),
+            1 => Value::Bool(true),
+            other => return Err(cursor.error_at_last(format!("Invalid bool value {}", other))),
+        },
+        IdlType::U8 => Value::from(u8::from_le_bytes(cursor.take_array()?)),
+        IdlType::U16 => Value::from(u16::from_le_bytes(cursor.take_array()?)),
+        IdlType::U32 => Value::from(u32::from_le_bytes(cursor.take_array()?)),
+        IdlType::U64 => Value::from(u64::from_le_bytes(cursor.take_array()?)),
+        // Exact with `arbitrary_precision`; `bignum_as_string` decides quoting on output
+        IdlType::U128 => Value::from(u128::from_le_bytes(cursor.take_array()?)),
+        IdlType::I8 => Value::from(i8::from_le_bytes(cursor.take_array()?)),
+        IdlType::I16 => Value::from(i16::from_le_bytes(cursor.take_array()?)),
+        IdlType::I32 => Value::from(i32::from_le_bytes(cursor.take_array()?)),
+        IdlType::I64 => Value::from(i64::from_le_bytes(cursor.take_array()?)),
+        IdlType::I128 => Value::from(i128::from_le_bytes(cursor.take_array()?)),
+        IdlType::F32 => Value::from(f32::from_le_bytes(cursor.take_array()?)),
+        IdlType::F64 => Value::from(f64::from_le_bytes(cursor.take_array()?)),
+        IdlType::String => {
+            let len = u32::from_le_bytes(cursor.take_array()?) as usize;
+            let start = cursor.offset;
+            let bytes = cursor.take(len)?;
+            let s = std::str::from_utf8(bytes).map_err(|e| SdkError::FieldError {
+                field: String::new(),
+                offset: start + e.valid_up_to(),
+                reason: "Invalid UTF-8".to_string(),
+            })?;
+            Value::String(s.to_string())
+        }
+        IdlType::Bytes => {
+            let len = u32::from_le_bytes(cursor.take_array()?) as usize;
+            Value::from(cursor.take(len)?.to_vec())
+        }
+        IdlType::Pubkey => Value::String(crate::bytes_to_base58(cursor.take(32)?)),
+        compound => unreachable!("{:?} is not a scalar type", compound),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A self-referential type: each `Node` optionally holds the next one
+    const LINKED_LIST_IDL: &str = r#"{
+        "address": "11111111111111111111111111111111",
+        "accounts": [{ "name": "Node", "discriminator": [1] }],
+        "instructions": [{
+            "name": "push",
+            "discriminator": [2],
+            "accounts": [],
+            "args": [{ "name": "node", "type": { "defined": { "name": "Node" } } }]
+        }],
+        "types": [{
+            "name": "Node",
+            "type": { "kind": "struct", "fields": [{ "name": "next", "type": { "option": { "defined": { "name": "Node" } } } }] }
+        }]
+    }"#;
+
+    /// Discriminator, then `nodes` nested nodes ending in `None`
+    fn chain(discriminator: u8, nodes: usize) -> Vec<u8> {
+        let mut data = vec![discriminator];
+        data.extend(std::iter::repeat_n(1, nodes));
+        data.push(0);
+        data
+    }
+
+    fn is_depth_error(error: &ParseError, max: usize) -> bool {
+        matches!(error, ParseError::InvalidData(msg) if msg.contains(&format!("Max nesting depth {} exceeded", max)))
+    }
+
+    #[test]
+    fn default_limit_stops_deep_untrusted_data() {
+        let idl = Idl::from_json(LINKED_LIST_IDL).unwrap();
+
+        let error = idl.decode_account(&idl.accounts[0], &chain(1, 10_000)).unwrap_err();
+        assert!(is_depth_error(&error, MAX_DECODE_DEPTH), "{:?}", error);
+    }
+
+    #[test]
+    fn undefined_type_reference_is_rejected() {
+        let idl = LINKED_LIST_IDL.replace(r#"{ "option": { "defined": { "name": "Node" } } }"#, r#"{ "defined": { "name": "Missing" } }"#);
+
+        let error = Idl::from_json(&idl).unwrap_err();
+        assert!(matches!(&error, SdkError::InvalidData(msg) if msg == "Unknown IDL type: Missing"), "{:?}", error);
+    }
+
+    #[test]
+    fn wide_integers_are_exact_numbers() {
+        let idl = Idl::from_json(r#"{
+            "address": "11111111111111111111111111111111",
+            "accounts": [{ "name": "Wide", "discriminator": [1] }],
+            "instructions": [],
+            "types": [{
+                "name": "Wide",
+                "type": { "kind": "struct", "fields": [
+                    { "name": "big", "type": "u128" },
+                    { "name": "signed", "type": "i128" }
+                ] }
+            }]
+        }"#).unwrap();
+        let mut data = vec![1];
+        data.extend_from_slice(&u128::MAX.to_le_bytes());
+        data.extend_from_slice(&i128::MIN.to_le_bytes());
+
+        let value = idl.decode_account(&idl.accounts[0], &data).unwrap();
+        assert!(value["big"].is_number());
+        assert_eq!(
+            serde_json::to_string(&value).unwrap(),
+            r#"{"big":340282366920938463463374607431768211455,"signed":-170141183460469231731687303715884105728}"#
+        );
+    }
+
+    #[test]
+    fn account_nesting_is_limited() {
+        let idl = Idl::from_json(LINKED_LIST_IDL).unwrap();
+        let account = &idl.accounts[0];
+
+        // Each nested node is two levels: the option and the defined type
+        assert!(idl.decode_account_with_max_depth(account, &chain(1, 1), 4).is_ok());
+        let error = idl.decode_account_with_max_depth(account, &chain(1, 2), 4).unwrap_err();
+        assert!(is_depth_error(&error, 4), "{:?}", error);
+    }
+
+    #[test]
+    fn instruction_nesting_is_limited() {
+        let idl = Idl::from_json(LINKED_LIST_IDL).unwrap();
+        let instruction = &idl.instructions[0];
+
+        assert!(idl.decode_instruction_with_max_depth(instruction, &chain(2, 1), 4).is_ok());
+        let error = idl.decode_instruction_with_max_depth(instruction, &chain(2, 2), 4).unwrap_err();
+        assert!(is_depth_error(&error, 4), "{:?}", error);
+    }
+}
@@ -11,8 +11,14 @@ pub trait InstructionParser: Sized {
     fn try_parse(data: &[u8]) -> Result<Self, ParseError>;
     
     /// Check if this parser can handle the instruction
+    /// 
+    /// When a discriminator is available only the leading bytes are
+    /// compared; otherwise this falls back to a full `try_parse`.
     fn can_parse(data: &[u8]) -> bool {
-        Self::try_parse(data).is_ok()
+        match Self::discriminator() {
+            Some(discriminator) => data.starts_with(&discriminator),
+            None => Self::try_parse(data).is_ok(),
+        }
     }
     
     /// Convert to JSON string
@@ -28,27 +28,53 @@ mod error;
 mod account;
 mod instruction;
 mod parser;
+mod compression;
+#[cfg(feature = "pyth")]
+mod pyth;
+mod registry;
+mod rpc_response;
 mod serialization;
 mod discriminator;
+mod dispatch;
+mod events;
+mod idl;
+mod options;
+mod token_2022;
 mod transformer;
 mod transaction;
 mod view;
 mod utils;
+mod value;
 
 // Convenience module
 pub mod prelude;
 
+// Test helpers (if feature enabled, and for the SDK's own tests)
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+
 // Public re-exports
 pub use error::*;
 pub use account::*;
 pub use instruction::*;
 pub use parser::*;
+pub use compression::*;
+#[cfg(feature = "pyth")]
+pub use pyth::*;
+pub use registry::*;
+pub use rpc_response::*;
 pub use serialization::*;
 pub use discriminator::*;
+pub use events::*;
+pub use idl::*;
+pub use options::*;
+pub use token_2022::*;
 pub use transformer::*;
 pub use transaction::*;
+#[allow(unused_imports)] // placeholder module
 pub use view::*;
 pub use utils::*;
+pub use value::*;
 
 // Macros (if feature enabled)
 #[cfg(feature = "macros")]
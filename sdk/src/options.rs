@@ -0,0 +1,192 @@
+//! SDK output options
+//!
+//! The WIT `ParseOptions` record can't grow without breaking linked
+//! components, so SDK-specific options travel as JSON in its `custom` field:
+//!
+//! ```json
+//! { "bignum_as_string": true }
+//! ```
+//!
+//! Unknown keys are ignored and missing keys take their defaults, so older
+//! and newer guests can read each other's options.
+//!
+//! ## Moving the options into WIT
+//!
+//! Adding fields to `parse-options` changes the record's canonical ABI, so a
+//! host built against the new WIT could no longer call components built
+//! against the old one. The plan is to version the interface instead:
+//!
+//! ```wit
+//! package component:solana-rpcx-bindings@0.2.0;
+//!
+//! enum key-case { snake, camel, pascal }
+//!
+//! record parse-options-v2 {
+//!     include-raw: bool,
+//!     pretty-json: bool,
+//!     bignum-as-string: bool,
+//!     key-case: option<key-case>,
+//!     include-discriminator: bool,
+//!     compressed: bool,
+//!     custom: option<string>,
+//! }
+//!
+//! record program-metadata-v2 {
+//!     name: option<string>,
+//!     program-id: option<string>,
+//!     project-url: option<string>,
+//!     version: option<string>,
+//!     supported-versions: list<string>,
+//! }
+//!
+//! interface program-parser-v2 {
+//!     parse-with-options: func(account: solana-account, options: parse-options-v2)
+//!         -> result<parsed-account, parse-error>;
+//!     get-program-metadata: func() -> option<program-metadata-v2>;
+//! }
+//! ```
+//!
+//! - Components keep exporting `program-parser@0.1.0` and may also export
+//!   `program-parser-v2`; hosts use v2 when the component exports it and
+//!   otherwise fall back to v1, encoding the same options into `custom`.
+//! - Both paths end up as an [`OutputOptions`], so guest code doesn't change.
+//! - Until then, `supported-versions` is available from
+//!   `Parser::metadata_json`.
+//! - `compressed` is reserved for state-compression leaves and has no JSON
+//!   counterpart yet.
+
+use serde::{Deserialize, Serialize};
+use crate::bindings::component::solana_rpcx_bindings::types::*;
+
+/// Options read from `ParseOptions.custom`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OutputOptions {
+    /// Emit integers outside JavaScript's safe range (±2^53 - 1) as strings
+    /// 
+    /// Applied as the data is written out, with the exact digits, so
+    /// `u128`/`i128` fields come out whole.
+    pub bignum_as_string: bool,
+    /// Return accounts whose JSON conversion failed instead of erroring,
+    /// with data `{"_serialization_error": "..."}`
+    pub resilient: bool,
+    /// Rewrite object keys to this casing, recursively
+    /// 
+    /// Keys that can't be field names (non-identifiers such as `"a-b"`, and
+    /// base58 pubkeys) are kept, as are the keys of an object that would end
+    /// up with duplicates. Identifier-like keys of a map are still recased.
+    pub key_case: Option<Casing>,
+    /// Add the matched discriminator (`_discriminator`, hex) and program id
+    /// (`_program`) to instruction output
+    pub include_discriminator: bool,
+    /// Sort object keys, recursively, so equal data serializes to equal bytes
+    pub sort_keys: bool,
+    /// Add the account's `_lamports`, `_data_len` and `_owner` to account
+    /// output (subject to `key_case` and `bignum_as_string` like other keys)
+    pub include_account_meta: bool,
+    /// Rewrite serde's externally tagged enums as `{"type": ..., "value": ...}`
+    /// 
+    /// Any object with exactly one PascalCase key (ASCII alphanumeric,
+    /// starting with an uppercase letter, and not a base58 pubkey) is taken
+    /// to be an enum variant: `{"Swap": {"amount": 5}}` becomes
+    /// `{"type": "Swap", "value": {"amount": 5}}`. Applied innermost first,
+    /// before `key_case`. Unit variants serialize as plain strings and can't
+    /// be told apart from other strings, so they are left as is. The JSON
+    /// carries no type information, so a map with a single such key, or a
+    /// struct whose one field is renamed to one, is rewritten too.
+    pub tagged_enums: bool,
+    /// Only match the discriminator, as [`Parser::classify`](crate::Parser::classify)
+    /// does: return the account type with data `{}` and skip deserialization
+    /// 
+    /// For a first pass over accounts fetched with an RPC `dataSlice`, whose
+    /// data is only a prefix. Parsers without a discriminator never match,
+    /// and the other options don't apply.
+    pub classify_only: bool,
+    /// Add the parser's program metadata (`name`, `version`, `program_id`,
+    /// `project_url`) to account and instruction output as
+    /// `_program_metadata`, for provenance; nothing is added when the parser
+    /// has no metadata
+    pub include_program_metadata: bool,
+}
+
+/// Casing for JSON object keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Casing {
+    /// `total_shares`
+    Snake,
+    /// `totalShares`
+    Camel,
+    /// `TotalShares`
+    Pascal,
+}
+
+impl Casing {
+    /// Convert a key to this casing
+    /// 
+    /// Leading underscores (as in `_discriminator`) are kept.
+    /// 
+    /// ```
+    /// use solana_rpcx_sdk::Casing;
+    /// 
+    /// assert_eq!(Casing::Camel.convert("total_shares"), "totalShares");
+    /// assert_eq!(Casing::Pascal.convert("total_shares"), "TotalShares");
+    /// assert_eq!(Casing::Snake.convert("totalShares"), "total_shares");
+    /// assert_eq!(Casing::Camel.convert("_serialization_error"), "_serializationError");
+    /// ```
+    pub fn convert(&self, key: &str) -> String {
+        let body = key.trim_start_matches('_');
+        let prefix = &key[..key.len() - body.len()];
+        let snake = crate::to_snake_case(body);
+        
+        let converted = match self {
+            Casing::Snake => snake,
+            Casing::Camel | Casing::Pascal => {
+                let mut out = String::with_capacity(snake.len());
+                for (i, word) in snake.split('_').filter(|w| !w.is_empty()).enumerate() {
+                    let mut chars = word.chars();
+                    if let Some(first) = chars.next() {
+                        if i == 0 && *self == Casing::Camel {
+                            out.push(first);
+                        } else {
+                            out.push(first.to_ascii_uppercase());
+                        }
+                        out.extend(chars);
+                    }
+                }
+                out
+            }
+        };
+        
+        format!("{}{}", prefix, converted)
+    }
+}
+
+impl OutputOptions {
+    /// Read the SDK options from `ParseOptions.custom`
+    /// 
+    /// A missing or empty `custom` field gives the defaults.
+    pub fn from_parse_options(options: &ParseOptions) -> Result<Self, ParseError> {
+        match options.custom.as_deref().map(str::trim) {
+            None | Some("") => Ok(Self::default()),
+            Some(custom) => serde_json::from_str(custom)
+                .map_err(|e| ParseError::InvalidData(format!("Invalid custom parse options: {}", e))),
+        }
+    }
+    
+    /// Encode as a `ParseOptions.custom` value
+    pub fn to_custom(&self) -> String {
+        serde_json::to_string(self).expect("OutputOptions is always serializable")
+    }
+    
+    /// Build a v1 `ParseOptions` carrying these options in `custom`
+    /// 
+    /// This is how a host passes the newer options to any component.
+    pub fn to_parse_options(&self, pretty_json: bool) -> ParseOptions {
+        ParseOptions {
+            include_raw: false,
+            pretty_json,
+            custom: Some(self.to_custom()),
+        }
+    }
+}
@@ -3,20 +3,50 @@
 //! The core of the SDK. Allows registration of different account and instruction
 //! types and dispatches parsing to the appropriate handler.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use crate::bindings::component::solana_rpcx_bindings::types::*;
 use crate::error::*;
+use crate::account::AccountParser;
+use crate::instruction::InstructionParser;
+use crate::compression::CompressedLeaf;
+use crate::discriminator::{DiscriminatorStrategy, NoDiscriminator, Prefix};
+use crate::serialization::SerializationFormat;
+use crate::dispatch::DiscriminatorIndex;
+use crate::idl::{Idl, IdlAccount, IdlInstruction, MAX_DECODE_DEPTH};
+use crate::value::{apply_value_options, inject_fields, to_json_string, ParsedValue};
+use crate::options::OutputOptions;
+
+/// Largest account or instruction data accepted by default (Solana's 10 MiB
+/// account size limit)
+pub const DEFAULT_MAX_DATA_LEN: usize = 10 * 1024 * 1024;
+
+/// Longest discriminator a parser may register
+/// 
+/// Real discriminators are 1 to 8 bytes (16 for some event layouts);
+/// anything past this is a misconfiguration such as a whole account body.
+pub const MAX_DISCRIMINATOR_LEN: usize = 32;
 
 /// Type alias for account parser functions
-pub type AccountParserFn = Box<dyn Fn(&[u8]) -> Result<ParsedAccount, ParseError> + Send + Sync>;
+/// 
+/// Parsers receive the whole account; most only look at `data`.
+pub type AccountParserFn = Box<dyn Fn(&SolanaAccount) -> Result<ParsedValue, ParseError> + Send + Sync>;
 
 /// Type alias for instruction parser functions
-pub type InstructionParserFn = Box<dyn Fn(&[u8]) -> Result<ParsedInstruction, ParseError> + Send + Sync>;
+/// 
+/// Parsers receive the whole instruction; most only look at `data`.
+pub type InstructionParserFn = Box<dyn Fn(&InstructionData) -> Result<ParsedInstruction, ParseError> + Send + Sync>;
+
+/// Type alias for the fallback run when no account parser matches
+pub type FallbackFn = Box<dyn Fn(&SolanaAccount) -> Result<ParsedAccount, ParseError> + Send + Sync>;
 
 /// Configuration for a single account parser
 pub struct AccountParserConfig {
     pub type_name: String,
     pub discriminator: Option<Vec<u8>>,
+    /// Exact data length this parser accepts, if it is bound to one
+    pub data_len: Option<usize>,
     pub parser: AccountParserFn,
 }
 
@@ -24,6 +54,9 @@ pub struct AccountParserConfig {
 pub struct InstructionParserConfig {
     pub name: String,
     pub discriminator: Option<Vec<u8>>,
+    /// Names for the instruction's accounts, by position; when set the
+    /// output gains an `_accounts` object mapping each name to its pubkey
+    pub account_names: Vec<String>,
     pub parser: InstructionParserFn,
 }
 
@@ -32,7 +65,22 @@ pub struct ParserBuilder {
     program_id: String,
     account_parsers: Vec<AccountParserConfig>,
     instruction_parsers: Vec<InstructionParserConfig>,
+    instruction_accounts: HashMap<String, Vec<String>>,
+    compressed_parsers: Vec<AccountParserConfig>,
+    event_parsers: Vec<AccountParserConfig>,
     metadata: Option<ProgramMetadata>,
+    supported_versions: Vec<String>,
+    fallback: Option<FallbackFn>,
+    type_field_name: Option<String>,
+    allow_mixed_discriminators: bool,
+    allow_any_program_id: bool,
+    ignore_owner: bool,
+    max_data_len: usize,
+    /// Shared with the IDL parsers, which read it when they run, so the
+    /// setting applies whether it's made before or after they're registered
+    max_decode_depth: Arc<AtomicUsize>,
+    #[cfg(feature = "schemars")]
+    schemas: HashMap<String, String>,
 }
 
 impl ParserBuilder {
@@ -42,8 +90,157 @@ impl ParserBuilder {
             program_id: program_id.into(),
             account_parsers: Vec::new(),
             instruction_parsers: Vec::new(),
+            instruction_accounts: HashMap::new(),
+            compressed_parsers: Vec::new(),
+            event_parsers: Vec::new(),
             metadata: None,
+            supported_versions: Vec::new(),
+            fallback: None,
+            type_field_name: None,
+            allow_mixed_discriminators: false,
+            allow_any_program_id: false,
+            ignore_owner: false,
+            max_data_len: DEFAULT_MAX_DATA_LEN,
+            max_decode_depth: Arc::new(AtomicUsize::new(MAX_DECODE_DEPTH)),
+            #[cfg(feature = "schemars")]
+            schemas: HashMap::new(),
+        }
+    }
+    
+    /// Set or replace the program id
+    /// 
+    /// For builders started with [`ParserBuilder::default`], whose program
+    /// id is empty until set.
+    pub fn with_program_id(mut self, program_id: impl Into<String>) -> Self {
+        self.program_id = program_id.into();
+        self
+    }
+    
+    /// Create a builder from an Anchor IDL JSON document
+    ///
+    /// Registers a parser for every entry in the IDL's `accounts` array,
+    /// decoding the Borsh body according to the IDL type layout. The program
+    /// id and metadata are taken from the IDL.
+    pub fn from_anchor_idl(idl_json: &str) -> Result<Self, SdkError> {
+        let idl = Idl::from_json(idl_json)?;
+        let program_id = idl.address.clone()
+            .ok_or_else(|| SdkError::InvalidData("IDL does not declare a program address".to_string()))?;
+        
+        let builder = Self::new(program_id.clone()).with_metadata(ProgramMetadata {
+            name: idl.name.clone(),
+            program_id: Some(program_id),
+            project_url: None,
+            version: idl.version.clone(),
+        });
+        
+        let idl = Arc::new(idl);
+        let max_decode_depth = Arc::clone(&builder.max_decode_depth);
+        let parsers = idl.accounts.iter().enumerate().map(|(index, account)| {
+            let idl_clone = Arc::clone(&idl);
+            let max_depth = Arc::clone(&max_decode_depth);
+            
+            let parser = data_parser(move |data: &[u8]| {
+                parse_idl_account(&idl_clone, &idl_clone.accounts[index], data, max_depth.load(Ordering::Relaxed))
+            });
+            
+            (account.name.clone(), Some(account.discriminator.clone()), parser)
+        });
+        
+        Ok(builder.register_accounts_iter(parsers))
+    }
+    
+    /// Register instruction parsers for every instruction in an Anchor IDL
+    ///
+    /// Each parser is keyed by the instruction's `"global"` discriminator and
+    /// produces a JSON object mapping argument name to decoded value.
+    pub fn instructions_from_anchor_idl(mut self, idl_json: &str) -> Result<Self, SdkError> {
+        let idl = Idl::from_json(idl_json)?;
+        
+        if let Some(address) = &idl.address {
+            if *address != self.program_id {
+                return Err(SdkError::InvalidData(format!(
+                    "IDL is for program {}, builder is for {}", address, self.program_id
+                )));
+            }
+        }
+        
+        let idl = Arc::new(idl);
+        for (index, instruction) in idl.instructions.iter().enumerate() {
+            let idl_clone = Arc::clone(&idl);
+            let max_depth = Arc::clone(&self.max_decode_depth);
+            
+            let parser = instruction_parser(move |data: &[u8]| {
+                parse_idl_instruction(&idl_clone, &idl_clone.instructions[index], data, max_depth.load(Ordering::Relaxed))
+            });
+            
+            self.instruction_parsers.push(InstructionParserConfig {
+                name: instruction.name.clone(),
+                discriminator: Some(instruction.discriminator.clone()),
+                account_names: instruction.accounts.clone(),
+                parser,
+            });
         }
+        
+        Ok(self)
+    }
+    
+    /// Register a type implementing [`AccountParser`]
+    ///
+    /// Wires `T::try_parse`, `T::to_value` and `T::discriminator` into an
+    /// account parser, so no closure is needed.
+    pub fn register<T>(mut self) -> Self
+    where
+        T: AccountParser + serde::Serialize + 'static,
+    {
+        let type_name = T::type_name().to_string();
+        let discriminator = T::discriminator();
+        let type_name_clone = type_name.clone();
+        let disc_clone = discriminator.clone();
+        
+        let parser = data_parser(move |data: &[u8]| {
+            let account = T::try_parse(data)?;
+            
+            Ok(match account.to_value() {
+                Ok(data) => ParsedValue::new(type_name_clone.clone(), data, disc_clone.clone()),
+                Err(e) => ParsedValue::serialization_failed(type_name_clone.clone(), disc_clone.clone(), e.to_string()),
+            })
+        });
+        
+        self.account_parsers.push(AccountParserConfig {
+            type_name,
+            discriminator,
+            parser,
+            data_len: None,
+        });
+        
+        self
+    }
+    
+    /// Register a type implementing [`InstructionParser`]
+    pub fn register_instruction<T>(mut self) -> Self
+    where
+        T: InstructionParser + serde::Serialize + 'static,
+    {
+        let name = T::instruction_name().to_string();
+        let name_clone = name.clone();
+        
+        let parser = instruction_parser(move |data: &[u8]| {
+            let instruction = T::try_parse(data)?;
+            
+            Ok(ParsedInstruction {
+                instruction_name: name_clone.clone(),
+                data: instruction.to_json()?,
+            })
+        });
+        
+        self.instruction_parsers.push(InstructionParserConfig {
+            name,
+            discriminator: T::discriminator(),
+            account_names: Vec::new(),
+            parser,
+        });
+        
+        self
     }
     
     /// Register an Anchor account (8-byte discriminator + Borsh)
@@ -57,64 +254,307 @@ impl ParserBuilder {
         let type_name_clone = type_name.clone(); // Tech debt: Lazy code
         let discriminator = crate::compute_anchor_discriminator("account", &type_name);
         
-        let parser: AccountParserFn = Box::new(move |data: &[u8]| {
-            if data.len() < 8 {
-                return Err(ParseError::InsufficientData("Account data too short".to_string()));
-            }
+        let parser = data_parser(move |data: &[u8]| {
+            ensure_not_empty(data)?;
+            check_discriminator(data, &discriminator, &type_name_clone)?;
             
-            if &data[0..8] != &discriminator {
-                return Err(ParseError::UnknownAccountType("Wrong discriminator".to_string()));
-            }
-            
-            let mut data_slice = &data[..];
+            let mut data_slice = data;
             let account = T::try_deserialize(&mut data_slice)
                 .map_err(|e| ParseError::DeserializationFailed(e.to_string()))?;
             
-            let json = to_json(&account)
-                .map_err(|e| ParseError::InvalidData(e))?;
-            
-            Ok(ParsedAccount {
-                account_type: type_name_clone.clone(),
-                data: json,
-                discriminator: Some(discriminator.to_vec()),
-            })
+            match to_json(&account) {
+                Ok(json) => ParsedValue::try_from(ParsedAccount {
+                    account_type: type_name_clone.clone(),
+                    data: json,
+                    discriminator: Some(discriminator.to_vec()),
+                }),
+                Err(e) => Ok(ParsedValue::serialization_failed(
+                    type_name_clone.clone(),
+                    Some(discriminator.to_vec()),
+                    e,
+                )),
+            }
         });
         
         self.account_parsers.push(AccountParserConfig {
             type_name,
             discriminator: Some(discriminator.to_vec()),
             parser,
+            data_len: None,
         });
         
         self
     }
     
+    /// Register an Anchor account and capture its JSON Schema
+    /// 
+    /// The schema is available at runtime via [`Parser::json_schema`].
+    #[cfg(all(feature = "anchor", feature = "schemars"))]
+    pub fn register_anchor_account_with_schema<T, F>(self, to_json: F) -> Self
+    where
+        T: anchor_lang::AccountDeserialize + schemars::JsonSchema + 'static,
+        F: Fn(&T) -> Result<String, String> + Send + Sync + 'static,
+    {
+        let type_name = extract_type_name::<T>();
+        self.register_anchor_account::<T, F>(to_json)
+            .with_json_schema::<T>(type_name)
+    }
+    
+    /// Attach a JSON Schema for `T` to a registered account type
+    #[cfg(feature = "schemars")]
+    pub fn with_json_schema<T: schemars::JsonSchema>(mut self, type_name: impl Into<String>) -> Self {
+        let schema = schemars::schema_for!(T);
+        let json = serde_json::to_string_pretty(&schema)
+            .expect("JSON Schema is always serializable");
+        self.schemas.insert(type_name.into(), json);
+        self
+    }
+    
     /// Register a native Borsh account (no discriminator)
-    pub fn register_borsh_account<T>(mut self, type_name: impl Into<String>) -> Self
+    /// 
+    /// Empty data is rejected up front rather than surfacing as a Borsh
+    /// end-of-input error:
+    /// 
+    /// ```
+    /// use solana_rpcx_sdk::prelude::*;
+    /// 
+    /// #[derive(BorshDeserialize, Serialize)]
+    /// struct Counter { count: u64 }
+    /// 
+    /// let program_id = "11111111111111111111111111111111";
+    /// let parser = ParserBuilder::new(program_id)
+    ///     .register_borsh_account::<Counter>("Counter")
+    ///     .build();
+    /// 
+    /// let account = |data: Vec<u8>| SolanaAccount {
+    ///     pubkey: program_id.to_string(),
+    ///     data,
+    ///     owner: program_id.to_string(),
+    ///     lamports: 0,
+    ///     executable: false,
+    ///     rent_epoch: 0,
+    /// };
+    /// 
+    /// assert!(matches!(
+    ///     parser.parse_account(&account(vec![])),
+    ///     Err(ParseError::InsufficientData(msg)) if msg == "account data is empty"
+    /// ));
+    /// 
+    /// // All-zero data is still valid Borsh for this layout
+    /// let parsed = parser.parse_account(&account(vec![0; 8])).unwrap();
+    /// assert_eq!(parsed.data, r#"{"count":0}"#);
+    /// ```
+    pub fn register_borsh_account<T>(self, type_name: impl Into<String>) -> Self
+    where
+        T: borsh::BorshDeserialize + serde::Serialize + 'static,
+    {
+        self.register_account_with_strategy::<T, _>(type_name, NoDiscriminator)
+    }
+    
+    /// Register account with custom discriminator
+    pub fn register_account_with_discriminator<T>(
+        self,
+        type_name: impl Into<String>,
+        discriminator: Vec<u8>,
+    ) -> Self
     where
         T: borsh::BorshDeserialize + serde::Serialize + 'static,
+    {
+        self.register_account_with_strategy::<T, _>(type_name, Prefix(discriminator))
+    }
+    
+    /// Register an account from a discriminator strategy and a serialization format
+    /// 
+    /// The most general registration: `strategy` decides which accounts
+    /// match and `format` decodes the bytes after the discriminator. The
+    /// format-specific `register_*` methods remain as shorthands; adding a
+    /// format only takes a [`SerializationFormat`] impl.
+    /// 
+    /// ```
+    /// use solana_rpcx_sdk::prelude::*;
+    /// use solana_rpcx_sdk::{BorshFormat, U8Tag};
+    /// 
+    /// #[derive(BorshDeserialize, Serialize)]
+    /// struct Counter { count: u64 }
+    /// 
+    /// let program_id = "11111111111111111111111111111111";
+    /// let parser = ParserBuilder::new(program_id)
+    ///     .register_account("Counter", U8Tag(1), BorshFormat::<Counter>::new())
+    ///     // Any closure from bytes to a JSON string is a format
+    ///     .register_account("Note", U8Tag(2), |body: &[u8]| {
+    ///         String::from_utf8(body.to_vec())
+    ///             .map(|text| serde_json::json!({ "text": text }).to_string())
+    ///             .map_err(|e| ParseError::DeserializationFailed(e.to_string()))
+    ///     })
+    ///     .build();
+    /// let account = |data: Vec<u8>| SolanaAccount {
+    ///     pubkey: program_id.to_string(),
+    ///     data,
+    ///     owner: program_id.to_string(),
+    ///     lamports: 0,
+    ///     executable: false,
+    ///     rent_epoch: 0,
+    /// };
+    /// 
+    /// let mut counter = vec![1];
+    /// counter.extend_from_slice(&3u64.to_le_bytes());
+    /// assert_eq!(parser.parse_account(&account(counter)).unwrap().data, r#"{"count":3}"#);
+    /// assert_eq!(parser.parse_account(&account(b"\x02gm".to_vec())).unwrap().data, r#"{"text":"gm"}"#);
+    /// ```
+    pub fn register_account<S, F>(
+        mut self,
+        type_name: impl Into<String>,
+        strategy: S,
+        format: F,
+    ) -> Self
+    where
+        S: DiscriminatorStrategy + 'static,
+        F: SerializationFormat + 'static,
     {
         let type_name = type_name.into();
         let type_name_clone = type_name.clone();
+        let discriminator = strategy.prefix();
         
-        let parser: AccountParserFn = Box::new(move |data: &[u8]| {
-            parse_borsh_account::<T>(data, &type_name_clone)
+        let parser = data_parser(move |data: &[u8]| {
+            let offset = check_strategy(data, &strategy, &type_name_clone)?;
+            let value = format.deserialize_to_value(&data[offset..])?;
+            Ok(ParsedValue::new(type_name_clone.clone(), value, strategy.prefix()))
         });
         
         self.account_parsers.push(AccountParserConfig {
             type_name,
-            discriminator: None,
+            discriminator,
             parser,
+            data_len: None,
         });
         
         self
     }
     
-    /// Register account with custom discriminator
-    pub fn register_account_with_discriminator<T>(
+    /// Register many account parsers at once, in iteration order
+    /// 
+    /// The low-level, bulk counterpart of the `register_*` methods, for
+    /// generated parsers, e.g. from walking an IDL (which is how
+    /// [`from_anchor_idl`](Self::from_anchor_idl) registers its accounts).
+    /// Each item is `(type_name, discriminator, parser)`; the parser's
+    /// [`ParsedValue`] is used as is, so it should carry `type_name`.
+    /// 
+    /// ```
+    /// use solana_rpcx_sdk::prelude::*;
+    /// use solana_rpcx_sdk::AccountParserFn;
+    /// 
+    /// let program_id = "11111111111111111111111111111111";
+    /// let parsers = ["Config", "State"].into_iter().enumerate().map(|(tag, name)| {
+    ///     let tag = tag as u8;
+    ///     let parser: AccountParserFn = Box::new(move |account: &SolanaAccount| {
+    ///         let json = serde_json::json!({ "len": account.data.len() });
+    ///         Ok(ParsedValue::new(name, json, Some(vec![tag])))
+    ///     });
+    ///     (name.to_string(), Some(vec![tag]), parser)
+    /// });
+    /// let parser = ParserBuilder::new(program_id).register_accounts_iter(parsers).build();
+    /// 
+    /// let parsed = parser.parse_account(&SolanaAccount {
+    ///     pubkey: program_id.to_string(),
+    ///     data: vec![1, 0, 0],
+    ///     owner: program_id.to_string(),
+    ///     lamports: 0,
+    ///     executable: false,
+    ///     rent_epoch: 0,
+    /// }).unwrap();
+    /// assert_eq!(parsed.account_type, "State");
+    /// assert_eq!(parsed.data, r#"{"len":3}"#);
+    /// ```
+    pub fn register_accounts_iter<I>(mut self, parsers: I) -> Self
+    where
+        I: IntoIterator<Item = (String, Option<Vec<u8>>, AccountParserFn)>,
+    {
+        self.account_parsers.extend(parsers.into_iter().map(|(type_name, discriminator, parser)| {
+            AccountParserConfig {
+                type_name,
+                discriminator,
+                data_len: None,
+                parser,
+            }
+        }));
+        
+        self
+    }
+    
+    /// Register a Borsh account recognized by a [`DiscriminatorStrategy`]
+    /// 
+    /// The general form of the `register_*` shorthands: the strategy decides
+    /// which accounts match, and the Borsh body is read after the bytes it
+    /// extracts. Strategies with a fixed [`prefix`](DiscriminatorStrategy::prefix)
+    /// are indexed like any discriminator; others are tried on every account.
+    /// 
+    /// ```
+    /// use solana_rpcx_sdk::prelude::*;
+    /// use solana_rpcx_sdk::{U16LE, U8Tag};
+    /// 
+    /// #[derive(BorshDeserialize, Serialize)]
+    /// struct Pool { fee_bps: u16 }
+    /// 
+    /// #[derive(BorshDeserialize, Serialize)]
+    /// struct Position { liquidity: u64 }
+    /// 
+    /// let program_id = "11111111111111111111111111111111";
+    /// let parser = ParserBuilder::new(program_id)
+    ///     .register_account_with_strategy::<Pool, _>("Pool", U8Tag(1))
+    ///     .register_account_with_strategy::<Position, _>("Position", U16LE(0x0102))
+    ///     .build();
+    /// let account = |data: Vec<u8>| SolanaAccount {
+    ///     pubkey: program_id.to_string(),
+    ///     data,
+    ///     owner: program_id.to_string(),
+    ///     lamports: 0,
+    ///     executable: false,
+    ///     rent_epoch: 0,
+    /// };
+    /// 
+    /// assert_eq!(parser.parse_account(&account(vec![1, 30, 0])).unwrap().account_type, "Pool");
+    /// 
+    /// let mut data = vec![0x02, 0x01];
+    /// data.extend_from_slice(&500u64.to_le_bytes());
+    /// assert_eq!(parser.parse_account(&account(data)).unwrap().data, r#"{"liquidity":500}"#);
+    /// ```
+    pub fn register_account_with_strategy<T, S>(
+        mut self,
+        type_name: impl Into<String>,
+        strategy: S,
+    ) -> Self
+    where
+        T: borsh::BorshDeserialize + serde::Serialize + 'static,
+        S: DiscriminatorStrategy + 'static,
+    {
+        let type_name = type_name.into();
+        let type_name_clone = type_name.clone();
+        let discriminator = strategy.prefix();
+        
+        let parser = data_parser(move |data: &[u8]| {
+            parse_account_with_strategy::<T>(data, &type_name_clone, &strategy)
+        });
+        
+        self.account_parsers.push(AccountParserConfig {
+            type_name,
+            discriminator,
+            parser,
+            data_len: None,
+        });
+        
+        self
+    }
+    
+    /// Register an account bound to both a discriminator and an exact data length
+    /// 
+    /// Use this when versions of an account share a discriminator and differ
+    /// only in size (e.g. a field appended in v2). Dispatch skips parsers whose
+    /// length doesn't match, so each version can be registered separately.
+    pub fn register_account_with_disc_and_len<T>(
         mut self,
         type_name: impl Into<String>,
         discriminator: Vec<u8>,
+        data_len: usize,
     ) -> Self
     where
         T: borsh::BorshDeserialize + serde::Serialize + 'static,
@@ -124,7 +564,13 @@ impl ParserBuilder {
         let type_name_clone = type_name.clone();
         let disc_clone = discriminator.clone();
         
-        let parser: AccountParserFn = Box::new(move |data: &[u8]| {
+        let parser = data_parser(move |data: &[u8]| {
+            ensure_not_empty(data)?;
+            
+            if data.len() != data_len {
+                return Err(length_error(data, data_len, &disc_clone, &type_name_clone));
+            }
+            
             parse_account_with_discriminator::<T>(
                 data,
                 &type_name_clone,
@@ -136,6 +582,79 @@ impl ParserBuilder {
         self.account_parsers.push(AccountParserConfig {
             type_name,
             discriminator: Some(discriminator),
+            data_len: Some(data_len),
+            parser,
+        });
+        
+        self
+    }
+    
+    /// Register a fixed-layout Pinocchio account (`#[repr(C)]`, no Borsh)
+    /// 
+    /// Pinocchio programs usually tag state with a short discriminator
+    /// (commonly a single `u8`) at offset 0, followed by the struct bytes.
+    /// Pass an empty `discriminator` for accounts identified by size alone.
+    /// The account must be exactly `discriminator.len() + size_of::<T>()`
+    /// bytes; alignment is not required.
+    /// 
+    /// ```
+    /// use solana_rpcx_sdk::prelude::*;
+    /// use solana_rpcx_sdk::Pubkey;
+    /// 
+    /// #[repr(C)]
+    /// #[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable, Serialize)]
+    /// struct Vault {
+    ///     owner: Pubkey,
+    ///     amount: u64,
+    ///     bump: u8,
+    ///     #[serde(skip)]
+    ///     _padding: [u8; 7],
+    /// }
+    /// 
+    /// let program_id = "11111111111111111111111111111111";
+    /// let parser = ParserBuilder::new(program_id)
+    ///     .register_pinocchio_account::<Vault>("Vault", vec![1])
+    ///     .build();
+    /// 
+    /// let mut data = vec![1];
+    /// data.extend_from_slice(&[0; 32]);
+    /// data.extend_from_slice(&500u64.to_le_bytes());
+    /// data.push(254);
+    /// data.extend_from_slice(&[0; 7]);
+    /// 
+    /// let parsed = parser.parse_account(&SolanaAccount {
+    ///     pubkey: program_id.to_string(),
+    ///     data,
+    ///     owner: program_id.to_string(),
+    ///     lamports: 0,
+    ///     executable: false,
+    ///     rent_epoch: 0,
+    /// }).unwrap();
+    /// assert_eq!(parsed.account_type, "Vault");
+    /// assert_eq!(parsed.data, r#"{"amount":500,"bump":254,"owner":"11111111111111111111111111111111"}"#);
+    /// ```
+    #[cfg(feature = "pinocchio")]
+    pub fn register_pinocchio_account<T>(
+        mut self,
+        type_name: impl Into<String>,
+        discriminator: Vec<u8>,
+    ) -> Self
+    where
+        T: bytemuck::Pod + serde::Serialize + 'static,
+    {
+        let type_name = type_name.into();
+        let type_name_clone = type_name.clone();
+        let disc_clone = discriminator.clone();
+        let data_len = discriminator.len() + std::mem::size_of::<T>();
+        
+        let parser = data_parser(move |data: &[u8]| {
+            parse_pod_account::<T>(data, &type_name_clone, &disc_clone)
+        });
+        
+        self.account_parsers.push(AccountParserConfig {
+            type_name,
+            discriminator: if discriminator.is_empty() { None } else { Some(discriminator) },
+            data_len: Some(data_len),
             parser,
         });
         
@@ -143,6 +662,9 @@ impl ParserBuilder {
     }
     
     /// Register a fully custom account parser
+    /// 
+    /// The returned `data` must be valid JSON; it is parsed once into the
+    /// internal [`ParsedValue`] representation.
     pub fn register_custom_account<F>(
         mut self,
         type_name: impl Into<String>,
@@ -155,13 +677,167 @@ impl ParserBuilder {
         self.account_parsers.push(AccountParserConfig {
             type_name: type_name.into(),
             discriminator,
-            parser: Box::new(parser),
+            parser: data_parser(move |data: &[u8]| parser(data).and_then(ParsedValue::try_from)),
+            data_len: None,
+        });
+        
+        self
+    }
+    
+    /// Register a fully custom account parser that sees the whole account
+    /// 
+    /// Like [`register_custom_account`](Self::register_custom_account), but
+    /// the closure gets the `SolanaAccount`, for output that needs the
+    /// pubkey, owner or lamports. Dispatch still happens on `discriminator`.
+    /// 
+    /// ```
+    /// use solana_rpcx_sdk::prelude::*;
+    /// 
+    /// let program_id = "11111111111111111111111111111111";
+    /// let parser = ParserBuilder::new(program_id)
+    ///     .register_custom_account_full("Config", Some(vec![1]), |account: &SolanaAccount| {
+    ///         Ok(ParsedAccount {
+    ///             account_type: "Config".to_string(),
+    ///             data: serde_json::json!({
+    ///                 "address": account.pubkey,
+    ///                 "rent_exempt_lamports": account.lamports,
+    ///             }).to_string(),
+    ///             discriminator: Some(vec![1]),
+    ///         })
+    ///     })
+    ///     .build();
+    /// 
+    /// let parsed = parser.parse_account(&SolanaAccount {
+    ///     pubkey: "SysvarRent111111111111111111111111111111111".to_string(),
+    ///     data: vec![1, 0, 0],
+    ///     owner: program_id.to_string(),
+    ///     lamports: 1_000,
+    ///     executable: false,
+    ///     rent_epoch: 0,
+    /// }).unwrap();
+    /// assert_eq!(
+    ///     parsed.data,
+    ///     r#"{"address":"SysvarRent111111111111111111111111111111111","rent_exempt_lamports":1000}"#
+    /// );
+    /// ```
+    pub fn register_custom_account_full<F>(
+        mut self,
+        type_name: impl Into<String>,
+        discriminator: Option<Vec<u8>>,
+        parser: F,
+    ) -> Self
+    where
+        F: Fn(&SolanaAccount) -> Result<ParsedAccount, ParseError> + Send + Sync + 'static,
+    {
+        self.account_parsers.push(AccountParserConfig {
+            type_name: type_name.into(),
+            discriminator,
+            parser: Box::new(move |account: &SolanaAccount| parser(account).and_then(ParsedValue::try_from)),
+            data_len: None,
+        });
+        
+        self
+    }
+    
+    /// Register a compressed account type, parsed from Merkle tree leaves
+    /// 
+    /// Compressed parsers are kept apart from normal account parsers and only
+    /// used by [`Parser::parse_compressed_leaf`]; see [`CompressedLeaf`] for
+    /// the assumed leaf layout.
+    /// Pass an empty `discriminator` for leaves without one.
+    /// 
+    /// ```
+    /// use solana_rpcx_sdk::prelude::*;
+    /// 
+    /// #[derive(BorshDeserialize, Serialize)]
+    /// struct Note { amount: u64 }
+    /// 
+    /// let parser = ParserBuilder::new("11111111111111111111111111111111")
+    ///     .register_compressed_account::<Note>("Note", vec![4])
+    ///     .build();
+    /// 
+    /// let mut leaf = vec![0xab; 32];
+    /// leaf.push(4);
+    /// leaf.extend_from_slice(&7u64.to_le_bytes());
+    /// let parsed = parser.parse_compressed_leaf(&leaf).unwrap();
+    /// assert_eq!(parsed.account_type, "Note");
+    /// assert_eq!(parsed.data, format!(r#"{{"_data_hash":"{}","amount":7}}"#, "ab".repeat(32)));
+    /// ```
+    pub fn register_compressed_account<T>(
+        mut self,
+        type_name: impl Into<String>,
+        discriminator: Vec<u8>,
+    ) -> Self
+    where
+        T: borsh::BorshDeserialize + serde::Serialize + 'static,
+    {
+        let type_name = type_name.into();
+        let type_name_clone = type_name.clone();
+        let disc_clone = discriminator.clone();
+        
+        let parser = data_parser(move |data: &[u8]| {
+            parse_account_with_discriminator::<T>(data, &type_name_clone, &disc_clone, disc_clone.len())
+        });
+        
+        self.compressed_parsers.push(AccountParserConfig {
+            type_name,
+            discriminator: (!discriminator.is_empty()).then_some(discriminator),
+            parser,
+            data_len: None,
+        });
+        
+        self
+    }
+    
+    /// Register an Anchor event, emitted with `emit!` or `emit_cpi!`
+    /// 
+    /// The discriminator is `sha256("event:<name>")[..8]`, so `name` must be
+    /// the event struct's name as declared. Event parsers are kept apart from
+    /// account and instruction parsers and only used by
+    /// [`Parser::parse_event`] and [`Parser::parse_event_logs`].
+    /// 
+    /// ```
+    /// use solana_rpcx_sdk::prelude::*;
+    /// 
+    /// #[derive(BorshDeserialize, Serialize)]
+    /// struct Deposited { amount: u64 }
+    /// 
+    /// let parser = ParserBuilder::new("11111111111111111111111111111111")
+    ///     .register_anchor_event::<Deposited>("Deposited")
+    ///     .build();
+    /// 
+    /// let mut data = compute_anchor_discriminator("event", "Deposited").to_vec();
+    /// data.extend_from_slice(&5u64.to_le_bytes());
+    /// let event = parser.parse_event(&data).unwrap();
+    /// assert_eq!(event.account_type, "Deposited");
+    /// assert_eq!(event.data, r#"{"amount":5}"#);
+    /// ```
+    pub fn register_anchor_event<T>(mut self, name: impl Into<String>) -> Self
+    where
+        T: borsh::BorshDeserialize + serde::Serialize + 'static,
+    {
+        let name = name.into();
+        let name_clone = name.clone();
+        let discriminator = crate::compute_anchor_discriminator("event", &name);
+        
+        let parser = data_parser(move |data: &[u8]| {
+            parse_account_with_discriminator::<T>(data, &name_clone, &discriminator, discriminator.len())
+        });
+        
+        self.event_parsers.push(AccountParserConfig {
+            type_name: name,
+            discriminator: Some(discriminator.to_vec()),
+            parser,
+            data_len: None,
         });
         
         self
     }
     
     /// Register an Anchor instruction
+    /// 
+    /// The discriminator is computed from the snake_case form of `name`, as
+    /// Anchor does, so `initializePool` and `initialize_pool` are equivalent.
     pub fn register_anchor_instruction<T>(
         mut self,
         name: impl Into<String>,
@@ -170,22 +846,164 @@ impl ParserBuilder {
         T: borsh::BorshDeserialize + serde::Serialize + 'static,
     {
         let name = name.into();
-        let discriminator = crate::compute_anchor_discriminator("global", &name);
+        let discriminator = crate::anchor_instruction_discriminator(&name);
         let name_clone = name.clone();
         
-        let parser: InstructionParserFn = Box::new(move |data: &[u8]| {
+        let parser = instruction_parser(move |data: &[u8]| {
             parse_anchor_instruction::<T>(data, &name_clone, &discriminator)
         });
         
         self.instruction_parsers.push(InstructionParserConfig {
             name,
             discriminator: Some(discriminator.to_vec()),
+            account_names: Vec::new(),
+            parser,
+        });
+        
+        self
+    }
+    
+    /// Register a MessagePack account, for sidecar accounts holding `rmp-serde` blobs
+    /// 
+    /// The account has no discriminator, so register it after any
+    /// discriminated parsers. Trailing bytes after the MessagePack value are
+    /// ignored.
+    /// 
+    /// ```
+    /// use solana_rpcx_sdk::prelude::*;
+    /// 
+    /// #[derive(Deserialize, Serialize)]
+    /// struct Profile { handle: String, followers: u32 }
+    /// 
+    /// let program_id = "11111111111111111111111111111111";
+    /// let parser = ParserBuilder::new(program_id)
+    ///     .register_msgpack_account::<Profile>("Profile")
+    ///     .build();
+    /// 
+    /// let mut data = rmp_serde::to_vec(&Profile { handle: "gm".to_string(), followers: 7 }).unwrap();
+    /// data.resize(64, 0); // padded to the allocated size
+    /// let parsed = parser.parse_account(&SolanaAccount {
+    ///     pubkey: program_id.to_string(),
+    ///     data,
+    ///     owner: program_id.to_string(),
+    ///     lamports: 0,
+    ///     executable: false,
+    ///     rent_epoch: 0,
+    /// }).unwrap();
+    /// assert_eq!(parsed.data, r#"{"followers":7,"handle":"gm"}"#);
+    /// ```
+    #[cfg(feature = "msgpack")]
+    pub fn register_msgpack_account<T>(self, type_name: impl Into<String>) -> Self
+    where
+        T: serde::de::DeserializeOwned + serde::Serialize + 'static,
+    {
+        self.register_account(type_name, NoDiscriminator, crate::MsgpackFormat::<T>::new())
+    }
+    
+    /// Register a MessagePack instruction, decoded from the whole instruction data
+    #[cfg(feature = "msgpack")]
+    pub fn register_msgpack_instruction<T>(
+        mut self,
+        name: impl Into<String>,
+    ) -> Self
+    where
+        T: serde::de::DeserializeOwned + serde::Serialize + 'static,
+    {
+        let name = name.into();
+        let name_clone = name.clone();
+        let format = crate::MsgpackFormat::<T>::new();
+        
+        let parser = instruction_parser(move |data: &[u8]| {
+            Ok(ParsedInstruction {
+                instruction_name: name_clone.clone(),
+                data: format.deserialize_to_json(data)?,
+            })
+        });
+        
+        self.instruction_parsers.push(InstructionParserConfig {
+            name,
+            discriminator: None,
+            account_names: Vec::new(),
             parser,
         });
         
         self
     }
     
+    /// Register a protobuf account decoded with `prost`
+    /// 
+    /// `T` must derive `prost::Message` (for decoding) as well as
+    /// `serde::Serialize` (for the JSON output). The message is read from the
+    /// bytes after `discriminator`; pass an empty one for accounts without.
+    /// 
+    /// ```
+    /// use solana_rpcx_sdk::prelude::*;
+    /// use prost::Message;
+    /// 
+    /// #[derive(Clone, PartialEq, prost::Message, Serialize)]
+    /// struct PriceUpdate {
+    ///     #[prost(string, tag = "1")]
+    ///     symbol: String,
+    ///     #[prost(int64, tag = "2")]
+    ///     price: i64,
+    /// }
+    /// 
+    /// let program_id = "11111111111111111111111111111111";
+    /// let parser = ParserBuilder::new(program_id)
+    ///     .register_protobuf_account::<PriceUpdate>("PriceUpdate", vec![0x50])
+    ///     .build();
+    /// 
+    /// let mut data = vec![0x50];
+    /// PriceUpdate { symbol: "SOL".to_string(), price: 15_000 }.encode(&mut data).unwrap();
+    /// let parsed = parser.parse_account(&SolanaAccount {
+    ///     pubkey: program_id.to_string(),
+    ///     data,
+    ///     owner: program_id.to_string(),
+    ///     lamports: 0,
+    ///     executable: false,
+    ///     rent_epoch: 0,
+    /// }).unwrap();
+    /// assert_eq!(parsed.data, r#"{"price":15000,"symbol":"SOL"}"#);
+    /// ```
+    #[cfg(feature = "protobuf")]
+    pub fn register_protobuf_account<T>(
+        self,
+        type_name: impl Into<String>,
+        discriminator: Vec<u8>,
+    ) -> Self
+    where
+        T: prost::Message + Default + serde::Serialize + 'static,
+    {
+        let format = crate::ProtobufFormat::<T>::new();
+        if discriminator.is_empty() {
+            self.register_account(type_name, NoDiscriminator, format)
+        } else {
+            self.register_account(type_name, Prefix(discriminator), format)
+        }
+    }
+    
+    /// Register Pyth's oracle price account as `"PythPriceAccount"`
+    /// 
+    /// Decodes the header and aggregate price with [`parse_pyth_price`](crate::parse_pyth_price).
+    /// Pyth accounts have no discriminator, only a magic number, so this
+    /// parser is tried on every account (register it after discriminated
+    /// parsers) and fails with `InvalidData` when the magic or account type
+    /// doesn't match. The builder's program id must be the Pyth oracle
+    /// program that owns the price accounts.
+    #[cfg(feature = "pyth")]
+    pub fn register_pyth_price_account(mut self) -> Self {
+        self.account_parsers.push(AccountParserConfig {
+            type_name: "PythPriceAccount".to_string(),
+            discriminator: None,
+            data_len: None,
+            parser: data_parser(|data: &[u8]| {
+                crate::parse_pyth_price(data).map(|price| ParsedValue::new("PythPriceAccount", price, None))
+            }),
+        });
+        
+        self
+    }
+    
     /// Register a native Borsh instruction
     pub fn register_borsh_instruction<T>(
         mut self,
@@ -197,13 +1015,14 @@ impl ParserBuilder {
         let name = name.into();
         let name_clone = name.clone();
         
-        let parser: InstructionParserFn = Box::new(move |data: &[u8]| {
+        let parser = instruction_parser(move |data: &[u8]| {
             parse_borsh_instruction::<T>(data, &name_clone)
         });
         
         self.instruction_parsers.push(InstructionParserConfig {
             name,
             discriminator: None,
+            account_names: Vec::new(),
             parser,
         });
         
@@ -223,74 +1042,1477 @@ impl ParserBuilder {
         self.instruction_parsers.push(InstructionParserConfig {
             name: name.into(),
             discriminator,
-            parser: Box::new(parser),
+            account_names: Vec::new(),
+            parser: instruction_parser(parser),
         });
         
         self
     }
     
-    /// Set program metadata
-    pub fn with_metadata(mut self, metadata: ProgramMetadata) -> Self {
-        self.metadata = Some(metadata);
-        self
+    /// Register an instruction nested under an outer variant tag
+    /// 
+    /// For programs whose instruction enum nests another, e.g. a governance
+    /// or AMM program where the first byte picks a group (`Pool`, `Admin`)
+    /// and an inner discriminator picks the instruction within it. `parser`
+    /// gets the data after both levels.
+    /// 
+    /// Both levels resolve in one step: `outer_tag` followed by
+    /// `inner_discriminator` is the instruction's discriminator, so dispatch
+    /// only tries parsers whose tag and inner discriminator both prefix the
+    /// data, and the build fails if two nested instructions share both. An
+    /// empty `inner_discriminator` registers a parser for the whole group;
+    /// as with any shorter discriminator, register it after the group's
+    /// nested instructions (or call [`sort_by_specificity`](Self::sort_by_specificity))
+    /// so it doesn't catch their data first.
+    /// 
+    /// ```
+    /// use solana_rpcx_sdk::prelude::*;
+    /// 
+    /// let program_id = "11111111111111111111111111111111";
+    /// let amount = |name: &'static str| move |body: &[u8]| {
+    ///     let amount = u64::try_from_slice(body)
+    ///         .map_err(|e| ParseError::DeserializationFailed(e.to_string()))?;
+    ///     Ok(ParsedInstruction {
+    ///         instruction_name: name.to_string(),
+    ///         data: serde_json::json!({ "amount": amount }).to_string(),
+    ///     })
+    /// };
+    /// let parser = ParserBuilder::new(program_id)
+    ///     // Pool::Deposit and Pool::Withdraw
+    ///     .register_nested_instruction("deposit", 1, vec![0], amount("deposit"))
+    ///     .register_nested_instruction("withdraw", 1, vec![1], amount("withdraw"))
+    ///     // Admin::SetFee, whose inner tag collides with Pool::Withdraw
+    ///     .register_nested_instruction("set_fee", 2, vec![1], amount("set_fee"))
+    ///     .build();
+    /// 
+    /// let instruction = |data: Vec<u8>| InstructionData {
+    ///     program_id: program_id.to_string(),
+    ///     data,
+    ///     accounts: Vec::new(),
+    /// };
+    /// let mut data = vec![2, 1];
+    /// data.extend_from_slice(&30u64.to_le_bytes());
+    /// let parsed = parser.parse_instruction(&instruction(data)).unwrap();
+    /// assert_eq!(parsed.instruction_name, "set_fee");
+    /// assert_eq!(parsed.data, r#"{"amount":30}"#);
+    /// 
+    /// assert!(parser.parse_instruction(&instruction(vec![1, 7])).is_err());
+    /// ```
+    pub fn register_nested_instruction<F>(
+        self,
+        name: impl Into<String>,
+        outer_tag: u8,
+        inner_discriminator: Vec<u8>,
+        parser: F,
+    ) -> Self
+    where
+        F: Fn(&[u8]) -> Result<ParsedInstruction, ParseError> + Send + Sync + 'static,
+    {
+        let mut discriminator = vec![outer_tag];
+        discriminator.extend_from_slice(&inner_discriminator);
+        let body_offset = discriminator.len();
+        
+        self.register_custom_instruction(name, Some(discriminator), move |data: &[u8]| {
+            parser(&data[body_offset..])
+        })
     }
     
-    /// Build the final parser
-    pub fn build(self) -> Parser {
-        Parser {
-            program_id: self.program_id,
-            account_parsers: self.account_parsers,
-            instruction_parsers: self.instruction_parsers,
-            metadata: self.metadata,
-        }
-    }
-}
+    /// Register a custom instruction parser that sees the whole instruction
+    /// 
+    /// Like [`register_custom_instruction`](Self::register_custom_instruction),
+    /// but the closure also gets `program_id` and the account pubkeys, for
+    /// output that names the accounts involved.
+    /// 
+    /// ```
+    /// use solana_rpcx_sdk::prelude::*;
+    /// 
+    /// let program_id = "11111111111111111111111111111111";
+    /// let parser = ParserBuilder::new(program_id)
+    ///     .register_custom_instruction_full("close", Some(vec![9]), |ix: &InstructionData| {
+    ///         let destination = ix.accounts.get(1)
+    ///             .ok_or_else(|| ParseError::InsufficientData("missing destination".to_string()))?;
+    ///         Ok(ParsedInstruction {
+    ///             instruction_name: "close".to_string(),
+    ///             data: serde_json::json!({ "destination": destination }).to_string(),
+    ///         })
+    ///     })
+    ///     .build();
+    /// 
+    /// let parsed = parser.parse_instruction(&InstructionData {
+    ///     program_id: program_id.to_string(),
+    ///     data: vec![9],
+    ///     accounts: vec!["Acct".to_string(), "Dest".to_string()],
+    /// }).unwrap();
+    /// assert_eq!(parsed.data, r#"{"destination":"Dest"}"#);
+    /// ```
+    pub fn register_custom_instruction_full<F>(
+        mut self,
+        name: impl Into<String>,
+        discriminator: Option<Vec<u8>>,
+        parser: F,
+    ) -> Self
+    where
+        F: Fn(&InstructionData) -> Result<ParsedInstruction, ParseError> + Send + Sync + 'static,
+    {
+        self.instruction_parsers.push(InstructionParserConfig {
+            name: name.into(),
+            discriminator,
+            account_names: Vec::new(),
+            parser: Box::new(parser),
+        });
+        
+        self
+    }
+    
+    /// Name the accounts of a registered instruction, by position
+    /// 
+    /// Parsed output then carries an `_accounts` object mapping each name to
+    /// the pubkey at that index of `InstructionData.accounts`; names past the
+    /// end of the list are left out. Instructions from an Anchor IDL are named
+    /// from the IDL automatically, and this overrides them. Building fails
+    /// with [`BuildError::UnknownInstruction`] if `name` is never registered.
+    /// 
+    /// ```
+    /// use solana_rpcx_sdk::prelude::*;
+    /// 
+    /// #[derive(BorshDeserialize, Serialize)]
+    /// struct Transfer { amount: u64 }
+    /// 
+    /// let program_id = "11111111111111111111111111111111";
+    /// let parser = ParserBuilder::new(program_id)
+    ///     .register_borsh_instruction::<Transfer>("transfer")
+    ///     .instruction_accounts("transfer", ["from", "to"])
+    ///     .build();
+    /// 
+    /// let parsed = parser.parse_instruction(&InstructionData {
+    ///     program_id: program_id.to_string(),
+    ///     data: 5u64.to_le_bytes().to_vec(),
+    ///     accounts: vec!["Alice".to_string(), "Bob".to_string()],
+    /// }).unwrap();
+    /// assert_eq!(parsed.data, r#"{"_accounts":{"from":"Alice","to":"Bob"},"amount":5}"#);
+    /// ```
+    pub fn instruction_accounts<I, S>(mut self, name: impl Into<String>, account_names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.instruction_accounts.insert(
+            name.into(),
+            account_names.into_iter().map(Into::into).collect(),
+        );
+        self
+    }
+    
+    /// Set program metadata
+    pub fn with_metadata(mut self, metadata: ProgramMetadata) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+    
+    /// Set the program name in the metadata
+    /// 
+    /// Like the other field setters, this creates the metadata (with
+    /// `program_id` filled in) if none was set, and leaves other fields alone.
+    /// 
+    /// ```
+    /// use solana_rpcx_sdk::prelude::*;
+    /// 
+    /// let parser = ParserBuilder::new("11111111111111111111111111111111")
+    ///     .program_name("Counter")
+    ///     .version("2.0.0")
+    ///     .build();
+    /// 
+    /// let metadata = parser.get_metadata().unwrap();
+    /// assert_eq!(metadata.name.as_deref(), Some("Counter"));
+    /// assert_eq!(metadata.program_id.as_deref(), Some("11111111111111111111111111111111"));
+    /// assert_eq!(metadata.version.as_deref(), Some("2.0.0"));
+    /// assert_eq!(metadata.project_url, None);
+    /// ```
+    pub fn program_name(mut self, name: impl Into<String>) -> Self {
+        self.metadata_mut().name = Some(name.into());
+        self
+    }
+    
+    /// Set the version in the metadata
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.metadata_mut().version = Some(version.into());
+        self
+    }
+    
+    /// Set the project URL in the metadata
+    pub fn project_url(mut self, url: impl Into<String>) -> Self {
+        self.metadata_mut().project_url = Some(url.into());
+        self
+    }
+    
+    /// Advertise the account layout versions this parser understands
+    /// 
+    /// The WIT `ProgramMetadata` record has no field for this, so it is
+    /// reported by [`Parser::supported_versions`] and
+    /// [`Parser::metadata_json`] rather than `get_metadata`.
+    pub fn supported_versions<I, S>(mut self, versions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.supported_versions = versions.into_iter().map(Into::into).collect();
+        self
+    }
+    
+    fn metadata_mut(&mut self) -> &mut ProgramMetadata {
+        let program_id = &self.program_id;
+        self.metadata.get_or_insert_with(|| ProgramMetadata {
+            name: None,
+            program_id: Some(program_id.clone()),
+            project_url: None,
+            version: None,
+        })
+    }
+    
+    /// Order parsers by how specific their discriminator is
+    /// 
+    /// After sorting, dispatch tries parsers in this order, for accounts and
+    /// instructions alike:
+    /// 1. Parsers with longer discriminators before shorter ones
+    /// 2. Parsers with any discriminator before parsers without one
+    /// 3. Accounts bound to a data length before ones that aren't
+    /// 4. Registration order among parsers that are otherwise equal
+    /// 
+    /// This makes dispatch independent of the order `register_*` calls were
+    /// made in.
+    pub fn sort_by_specificity(mut self) -> Self {
+        let specificity = |d: &Option<Vec<u8>>| std::cmp::Reverse(d.as_ref().map(|d| d.len() + 1).unwrap_or(0));
+        
+        self.account_parsers.sort_by_key(|c| (specificity(&c.discriminator), c.data_len.is_none()));
+        self.instruction_parsers.sort_by_key(|c| specificity(&c.discriminator));
+        self
+    }
+    
+    /// Allow parsers without a discriminator to be registered before
+    /// parsers with one
+    /// 
+    /// A discriminator-less parser accepts any data it can deserialize, so it
+    /// can shadow the correct parser. Register such parsers last (or on their
+    /// own) instead of using this unless you really need the ordering.
+    pub fn allow_mixed_discriminators(mut self) -> Self {
+        self.allow_mixed_discriminators = true;
+        self
+    }
+    
+    /// Embed the account type in the data JSON under `name`
+    /// 
+    /// Useful when the data is consumed without the surrounding
+    /// `ParsedAccount`, e.g. `"__typename"` for a GraphQL layer. Data that
+    /// isn't an object is wrapped as `{"value": ..., <name>: ...}`.
+    pub fn with_type_field_name(mut self, name: impl Into<String>) -> Self {
+        self.type_field_name = Some(name.into());
+        self
+    }
+    
+    /// Embed the account type in the data JSON under `"account_type"`
+    pub fn embed_account_type(self) -> Self {
+        self.with_type_field_name("account_type")
+    }
+    
+    /// Skip checking that the program id is a base58 pubkey
+    /// 
+    /// Handy for tests that use placeholder ids like `"test-program"`.
+    pub fn allow_any_program_id(mut self) -> Self {
+        self.allow_any_program_id = true;
+        self
+    }
+    
+    /// Don't require `account.owner` to be this program in `parse_account`
+    /// 
+    /// Dispatch then relies on discriminators alone, for data sources whose
+    /// owner field is missing or normalized (e.g. historical snapshots).
+    /// Discriminator-less parsers will accept any account they can
+    /// deserialize, so only use this when the input is known to belong to
+    /// the program. [`Parser::can_parse`] still checks the owner, since hosts
+    /// use it for routing.
+    /// 
+    /// ```
+    /// use solana_rpcx_sdk::prelude::*;
+    /// 
+    /// #[derive(BorshDeserialize, Serialize)]
+    /// struct Counter { count: u64 }
+    /// 
+    /// let parser = ParserBuilder::new("11111111111111111111111111111111")
+    ///     .register_account_with_discriminator::<Counter>("Counter", vec![7])
+    ///     .ignore_owner()
+    ///     .build();
+    /// 
+    /// let mut data = vec![7];
+    /// data.extend_from_slice(&3u64.to_le_bytes());
+    /// let parsed = parser.parse_account(&SolanaAccount {
+    ///     pubkey: String::new(),
+    ///     data,
+    ///     owner: String::new(), // not recorded in the snapshot
+    ///     lamports: 0,
+    ///     executable: false,
+    ///     rent_epoch: 0,
+    /// }).unwrap();
+    /// assert_eq!(parsed.data, r#"{"count":3}"#);
+    /// ```
+    pub fn ignore_owner(mut self) -> Self {
+        self.ignore_owner = true;
+        self
+    }
+    
+    /// Reject account and instruction data longer than `len` bytes
+    /// 
+    /// Checked before any parser runs, so oversized input fails fast with
+    /// `ParseError::InvalidData`. Defaults to [`DEFAULT_MAX_DATA_LEN`].
+    /// Independently of this, Borsh length prefixes that claim more bytes
+    /// than remain are rejected before the collection is read.
+    /// 
+    /// ```
+    /// use solana_rpcx_sdk::prelude::*;
+    /// 
+    /// #[derive(BorshDeserialize, Serialize)]
+    /// struct Blob { bytes: Vec<u8> }
+    /// 
+    /// let program_id = "11111111111111111111111111111111";
+    /// let parser = ParserBuilder::new(program_id)
+    ///     .register_borsh_account::<Blob>("Blob")
+    ///     .max_data_len(1024)
+    ///     .build();
+    /// let account = |data: Vec<u8>| SolanaAccount {
+    ///     pubkey: program_id.to_string(),
+    ///     data,
+    ///     owner: program_id.to_string(),
+    ///     lamports: 0,
+    ///     executable: false,
+    ///     rent_epoch: 0,
+    /// };
+    /// 
+    /// // A length prefix of u32::MAX with 4 bytes behind it
+    /// let bomb = vec![0xff, 0xff, 0xff, 0xff, 1, 2, 3, 4];
+    /// assert!(matches!(parser.parse_account(&account(bomb)), Err(ParseError::InvalidData(_))));
+    /// 
+    /// assert!(matches!(parser.parse_account(&account(vec![1; 2048])), Err(ParseError::InvalidData(_))));
+    /// ```
+    pub fn max_data_len(mut self, len: usize) -> Self {
+        self.max_data_len = len;
+        self
+    }
+    
+    /// Limit how deeply IDL-driven parsers nest while decoding
+    /// 
+    /// Each defined type, vec, option and array counts as a level. Data
+    /// nested deeper fails with `InvalidData("Max nesting depth N exceeded")`
+    /// instead of overflowing the stack, which self-referential types in an
+    /// untrusted IDL could otherwise do. Defaults to [`MAX_DECODE_DEPTH`];
+    /// applies to parsers from [`from_anchor_idl`](Self::from_anchor_idl) and
+    /// [`instructions_from_anchor_idl`](Self::instructions_from_anchor_idl),
+    /// whichever order they're registered in.
+    /// 
+    /// ```
+    /// use solana_rpcx_sdk::prelude::*;
+    /// 
+    /// let idl = r#"{
+    ///     "address": "11111111111111111111111111111111",
+    ///     "accounts": [{ "name": "Node", "discriminator": [1] }],
+    ///     "types": [{
+    ///         "name": "Node",
+    ///         "type": { "kind": "struct", "fields": [{ "name": "next", "type": { "option": { "defined": { "name": "Node" } } } }] }
+    ///     }]
+    /// }"#;
+    /// let parser = ParserBuilder::from_anchor_idl(idl).unwrap().max_decode_depth(4).build();
+    /// 
+    /// let account = |data: Vec<u8>| SolanaAccount {
+    ///     pubkey: String::new(),
+    ///     data,
+    ///     owner: "11111111111111111111111111111111".to_string(),
+    ///     lamports: 0,
+    ///     executable: false,
+    ///     rent_epoch: 0,
+    /// };
+    /// // One nested node: option, then the defined type
+    /// assert!(parser.parse_account(&account(vec![1, 1, 0])).is_ok());
+    /// assert!(matches!(
+    ///     parser.parse_account(&account(vec![1, 1, 1, 1, 1, 0])),
+    ///     Err(ParseError::InvalidData(msg)) if msg.contains("Max nesting depth 4 exceeded")
+    /// ));
+    /// ```
+    pub fn max_decode_depth(self, depth: usize) -> Self {
+        self.max_decode_depth.store(depth, Ordering::Relaxed);
+        self
+    }
+    
+    /// Set a fallback used when no registered account parser matches
+    /// 
+    /// Only runs for accounts owned by this program (unless
+    /// [`ignore_owner`](Self::ignore_owner) is set); a wrong owner is still
+    /// an error.
+    pub fn with_fallback<F>(mut self, fallback: F) -> Self
+    where
+        F: Fn(&SolanaAccount) -> Result<ParsedAccount, ParseError> + Send + Sync + 'static,
+    {
+        self.fallback = Some(Box::new(fallback));
+        self
+    }
+    
+    /// Fall back to an `"Unknown"` account recording the owner, data length
+    /// and leading discriminator bytes as hex
+    pub fn with_unknown_fallback(self) -> Self {
+        self.with_fallback(unknown_account)
+    }
+    
+    /// Build the final parser, rejecting conflicting registrations
+    /// 
+    /// Fails if the program id isn't a valid base58 pubkey, unless
+    /// [`ParserBuilder::allow_any_program_id`] was called, since such a parser
+    /// would never match an account owner. Also fails if two account parsers
+    /// (or two instruction parsers) share a discriminator or a name, since
+    /// dispatch would silently pick whichever was registered first. Also fails
+    /// if a parser without a discriminator comes before one with a
+    /// discriminator, unless [`ParserBuilder::allow_mixed_discriminators`] was
+    /// called, and if a discriminator is empty (it would match every account;
+    /// register without one instead) or longer than [`MAX_DISCRIMINATOR_LEN`].
+    /// 
+    /// ```
+    /// use solana_rpcx_sdk::prelude::*;
+    /// use solana_rpcx_sdk::BuildError;
+    /// 
+    /// #[derive(BorshDeserialize, Serialize)]
+    /// struct Counter { count: u64 }
+    /// 
+    /// let result = ParserBuilder::new("11111111111111111111111111111111")
+    ///     .register_account_with_discriminator::<Counter>("Counter", vec![])
+    ///     .try_build();
+    /// assert!(matches!(result, Err(BuildError::InvalidDiscriminator { len: 0, .. })));
+    /// ```
+    pub fn try_build(mut self) -> Result<Parser, BuildError> {
+        if !self.allow_any_program_id {
+            crate::base58_to_pubkey(&self.program_id).map_err(|reason| BuildError::InvalidProgramId {
+                program_id: self.program_id.clone(),
+                reason,
+            })?;
+        }
+        
+        check_conflicts(self.account_parsers.iter().map(|c| (&c.type_name, &c.discriminator, c.data_len)))?;
+        check_conflicts(self.instruction_parsers.iter().map(|c| (&c.name, &c.discriminator, None)))?;
+        check_conflicts(self.compressed_parsers.iter().map(|c| (&c.type_name, &c.discriminator, None)))?;
+        check_conflicts(self.event_parsers.iter().map(|c| (&c.type_name, &c.discriminator, None)))?;
+        
+        if !self.allow_mixed_discriminators {
+            check_shadowing(self.account_parsers.iter().map(|c| (&c.type_name, &c.discriminator)))?;
+            check_shadowing(self.instruction_parsers.iter().map(|c| (&c.name, &c.discriminator)))?;
+            check_shadowing(self.compressed_parsers.iter().map(|c| (&c.type_name, &c.discriminator)))?;
+        }
+        
+        for (name, account_names) in self.instruction_accounts.drain() {
+            let config = self.instruction_parsers.iter_mut()
+                .find(|c| c.name == name)
+                .ok_or(BuildError::UnknownInstruction(name))?;
+            config.account_names = account_names;
+        }
+        
+        let account_index = DiscriminatorIndex::new(self.account_parsers.iter().map(|c| &c.discriminator));
+        let instruction_index = DiscriminatorIndex::new(self.instruction_parsers.iter().map(|c| &c.discriminator));
+        let compressed_index = DiscriminatorIndex::new(self.compressed_parsers.iter().map(|c| &c.discriminator));
+        let event_index = DiscriminatorIndex::new(self.event_parsers.iter().map(|c| &c.discriminator));
+        
+        Ok(Parser {
+            program_id: self.program_id,
+            account_index,
+            instruction_index,
+            compressed_index,
+            event_index,
+            account_parsers: self.account_parsers,
+            instruction_parsers: self.instruction_parsers,
+            compressed_parsers: self.compressed_parsers,
+            event_parsers: self.event_parsers,
+            metadata: self.metadata,
+            supported_versions: self.supported_versions,
+            fallback: self.fallback,
+            type_field_name: self.type_field_name,
+            ignore_owner: self.ignore_owner,
+            max_data_len: self.max_data_len,
+            #[cfg(feature = "schemars")]
+            schemas: self.schemas,
+        })
+    }
+    
+    /// Build the final parser
+    /// 
+    /// # Panics
+    /// If the registrations conflict; see [`ParserBuilder::try_build`].
+    pub fn build(self) -> Parser {
+        self.try_build()
+            .unwrap_or_else(|e| panic!("Invalid parser configuration: {}", e))
+    }
+}
+
+/// What [`Parser::parse_any`] recognised the bytes as
+#[derive(Debug, Clone)]
+pub enum ParseKind {
+    Account(ParsedAccount),
+    Instruction(ParsedInstruction),
+    Unknown,
+}
+
+/// A registered type as reported by [`Parser::get_type_info`]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct TypeInfo {
+    pub name: String,
+    pub kind: TypeKind,
+    /// Discriminator as lowercase hex
+    pub discriminator: Option<String>,
+    pub has_discriminator: bool,
+}
+
+/// Which registry a [`TypeInfo`] comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TypeKind {
+    Account,
+    Instruction,
+    CompressedAccount,
+    Event,
+}
+
+/// Per-item results of [`Parser::parse_instructions_summary`] plus counts
+#[derive(Debug, Clone)]
+pub struct BatchResult {
+    /// One result per input instruction, in input order
+    pub results: Vec<Result<ParsedInstruction, ParseError>>,
+    pub succeeded: usize,
+    pub failed: usize,
+}
+
+impl BatchResult {
+    /// True if every instruction parsed
+    pub fn is_clean(&self) -> bool {
+        self.failed == 0
+    }
+    
+    /// Failed items with their input index
+    pub fn errors(&self) -> impl Iterator<Item = (usize, &ParseError)> + '_ {
+        self.results
+            .iter()
+            .enumerate()
+            .filter_map(|(index, result)| result.as_ref().err().map(|e| (index, e)))
+    }
+}
+
+impl Default for ParserBuilder {
+    /// A builder with no program id; set one with
+    /// [`with_program_id`](ParserBuilder::with_program_id) before building
+    fn default() -> Self {
+        Self::new(String::new())
+    }
+}
 
 /// Runtime parser that dispatches to registered handlers
 pub struct Parser {
     program_id: String,
+    account_index: DiscriminatorIndex,
+    instruction_index: DiscriminatorIndex,
+    compressed_index: DiscriminatorIndex,
+    event_index: DiscriminatorIndex,
     account_parsers: Vec<AccountParserConfig>,
     instruction_parsers: Vec<InstructionParserConfig>,
+    compressed_parsers: Vec<AccountParserConfig>,
+    event_parsers: Vec<AccountParserConfig>,
     metadata: Option<ProgramMetadata>,
+    supported_versions: Vec<String>,
+    fallback: Option<FallbackFn>,
+    type_field_name: Option<String>,
+    ignore_owner: bool,
+    max_data_len: usize,
+    #[cfg(feature = "schemars")]
+    schemas: HashMap<String, String>,
+}
+
+impl Default for Parser {
+    /// An empty parser with no program id, which matches nothing
+    fn default() -> Self {
+        ParserBuilder::default().allow_any_program_id().build()
+    }
 }
 
 impl Parser {
+    /// Program id this parser handles accounts for
+    pub fn program_id(&self) -> &str {
+        &self.program_id
+    }
+    
+    /// Combine two parsers for the same program
+    /// 
+    /// `other`'s account, instruction, compressed and event parsers are appended to
+    /// this parser's, so on overlapping matches `self`'s are tried first.
+    /// Fails if the program ids differ (an empty id, as in
+    /// [`Parser::default`], takes the other's) or if the combined parsers
+    /// share a name or discriminator, as [`ParserBuilder::try_build`] would.
+    /// 
+    /// Settings prefer `self`: its metadata, fallback, type field, owner
+    /// check and data limit win, and `other`'s are used only where `self`
+    /// has none. Supported versions are combined.
+    /// 
+    /// ```
+    /// use solana_rpcx_sdk::prelude::*;
+    /// use solana_rpcx_sdk::BuildError;
+    /// 
+    /// #[derive(BorshDeserialize, Serialize)]
+    /// struct Pool { fee_bps: u16 }
+    /// 
+    /// #[derive(BorshDeserialize, Serialize)]
+    /// struct Position { liquidity: u64 }
+    /// 
+    /// let program_id = "11111111111111111111111111111111";
+    /// let pools = ParserBuilder::new(program_id)
+    ///     .register_account_with_discriminator::<Pool>("Pool", vec![1])
+    ///     .program_name("amm")
+    ///     .build();
+    /// let positions = ParserBuilder::new(program_id)
+    ///     .register_account_with_discriminator::<Position>("Position", vec![2])
+    ///     .program_name("amm-positions")
+    ///     .build();
+    /// 
+    /// let parser = pools.merge(positions).unwrap();
+    /// assert_eq!(parser.get_supported_types(), ["Pool", "Position"]);
+    /// assert_eq!(parser.get_metadata().unwrap().name.as_deref(), Some("amm"));
+    /// 
+    /// let clash = ParserBuilder::new(program_id)
+    ///     .register_account_with_discriminator::<Position>("PositionV2", vec![2])
+    ///     .build();
+    /// assert!(matches!(parser.merge(clash), Err(BuildError::DuplicateDiscriminator { .. })));
+    /// ```
+    pub fn merge(mut self, other: Parser) -> Result<Parser, BuildError> {
+        let program_id = match (self.program_id.is_empty(), other.program_id.is_empty()) {
+            (true, _) => other.program_id,
+            (_, true) => self.program_id,
+            _ if self.program_id == other.program_id => self.program_id,
+            _ => return Err(BuildError::ProgramIdMismatch {
+                first: self.program_id,
+                second: other.program_id,
+            }),
+        };
+        
+        self.account_parsers.extend(other.account_parsers);
+        self.instruction_parsers.extend(other.instruction_parsers);
+        self.compressed_parsers.extend(other.compressed_parsers);
+        self.event_parsers.extend(other.event_parsers);
+        
+        check_conflicts(self.account_parsers.iter().map(|c| (&c.type_name, &c.discriminator, c.data_len)))?;
+        check_conflicts(self.instruction_parsers.iter().map(|c| (&c.name, &c.discriminator, None)))?;
+        check_conflicts(self.compressed_parsers.iter().map(|c| (&c.type_name, &c.discriminator, None)))?;
+        check_conflicts(self.event_parsers.iter().map(|c| (&c.type_name, &c.discriminator, None)))?;
+        
+        for version in other.supported_versions {
+            if !self.supported_versions.contains(&version) {
+                self.supported_versions.push(version);
+            }
+        }
+        #[cfg(feature = "schemars")]
+        for (type_name, schema) in other.schemas {
+            self.schemas.entry(type_name).or_insert(schema);
+        }
+        
+        Ok(Parser {
+            program_id,
+            account_index: DiscriminatorIndex::new(self.account_parsers.iter().map(|c| &c.discriminator)),
+            instruction_index: DiscriminatorIndex::new(self.instruction_parsers.iter().map(|c| &c.discriminator)),
+            compressed_index: DiscriminatorIndex::new(self.compressed_parsers.iter().map(|c| &c.discriminator)),
+            event_index: DiscriminatorIndex::new(self.event_parsers.iter().map(|c| &c.discriminator)),
+            account_parsers: self.account_parsers,
+            instruction_parsers: self.instruction_parsers,
+            compressed_parsers: self.compressed_parsers,
+            event_parsers: self.event_parsers,
+            metadata: self.metadata.or(other.metadata),
+            supported_versions: self.supported_versions,
+            fallback: self.fallback.or(other.fallback),
+            type_field_name: self.type_field_name.or(other.type_field_name),
+            ignore_owner: self.ignore_owner,
+            max_data_len: self.max_data_len,
+            #[cfg(feature = "schemars")]
+            schemas: self.schemas,
+        })
+    }
+    
+    /// Whether no account, instruction, compressed account or event types are registered
+    /// 
+    /// An empty parser fails every account with `UnknownAccountType`, or
+    /// hands it to the fallback if one is set.
+    /// 
+    /// ```
+    /// use solana_rpcx_sdk::prelude::*;
+    /// 
+    /// let program_id = "11111111111111111111111111111111";
+    /// let parser = ParserBuilder::default().with_program_id(program_id).build();
+    /// assert!(parser.is_empty());
+    /// 
+    /// let result = parser.parse_account(&SolanaAccount {
+    ///     pubkey: program_id.to_string(),
+    ///     data: vec![0; 8],
+    ///     owner: program_id.to_string(),
+    ///     lamports: 0,
+    ///     executable: false,
+    ///     rent_epoch: 0,
+    /// });
+    /// assert!(matches!(result, Err(ParseError::UnknownAccountType(msg)) if msg == "No account types are registered"));
+    /// assert!(Parser::default().is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.account_parsers.is_empty()
+            && self.instruction_parsers.is_empty()
+            && self.compressed_parsers.is_empty()
+            && self.event_parsers.is_empty()
+    }
+    
     /// Parse an account using registered parsers
+    /// 
+    /// If nothing matches and the discriminator bytes are all zero, the error
+    /// is a closed account (see [`is_closed_account`](crate::is_closed_account)).
     pub fn parse_account(&self, account: &SolanaAccount) -> Result<ParsedAccount, ParseError> {
+        self.parse_account_value(account)?.into_parsed_account(false)
+    }
+    
+    /// Parse base64-encoded account data, e.g. copied from an RPC response
+    /// 
+    /// Builds a minimal account internally: empty pubkey, zero lamports.
+    pub fn parse_account_base64(&self, owner: &str, data_b64: &str) -> Result<ParsedAccount, ParseError> {
+        use base64::{engine::general_purpose, Engine as _};
+        
+        let data = general_purpose::STANDARD
+            .decode(data_b64.trim())
+            .map_err(|e| ParseError::InvalidData(format!("Invalid base64: {}", e)))?;
+        
+        self.parse_account(&SolanaAccount {
+            pubkey: String::new(),
+            data,
+            owner: owner.to_string(),
+            lamports: 0,
+            executable: false,
+            rent_epoch: 0,
+        })
+    }
+    
+    /// Parse hex-encoded account data, e.g. copied from Solana Explorer or a log
+    /// 
+    /// An optional `0x` prefix and any whitespace (line breaks, byte-group
+    /// spacing) are ignored. Builds a minimal account like
+    /// [`parse_account_base64`](Self::parse_account_base64).
+    /// 
+    /// ```
+    /// use solana_rpcx_sdk::prelude::*;
+    /// 
+    /// #[derive(BorshDeserialize, Serialize)]
+    /// struct Counter { count: u64 }
+    /// 
+    /// let program_id = "11111111111111111111111111111111";
+    /// let parser = ParserBuilder::new(program_id)
+    ///     .register_account_with_discriminator::<Counter>("Counter", vec![7])
+    ///     .build();
+    /// 
+    /// let parsed = parser.parse_account_hex(program_id, "0x07 0300 0000\n0000 0000").unwrap();
+    /// assert_eq!(parsed.data, r#"{"count":3}"#);
+    /// assert!(matches!(parser.parse_account_hex(program_id, "07zz"), Err(ParseError::InvalidData(_))));
+    /// ```
+    pub fn parse_account_hex(&self, owner: &str, data_hex: &str) -> Result<ParsedAccount, ParseError> {
+        let digits: String = data_hex.split_whitespace().collect();
+        let data = crate::hex_to_bytes(&digits).map_err(ParseError::InvalidData)?;
+        
+        self.parse_account(&SolanaAccount {
+            pubkey: String::new(),
+            data,
+            owner: owner.to_string(),
+            lamports: 0,
+            executable: false,
+            rent_epoch: 0,
+        })
+    }
+    
+    /// Parse an account, honouring `options.pretty_json` and any
+    /// [`OutputOptions`] in `options.custom`
+    /// 
+    /// The data is serialized straight to the requested format, without a
+    /// compact round trip first.
+    /// 
+    /// ```
+    /// use solana_rpcx_sdk::prelude::*;
+    /// 
+    /// #[derive(BorshDeserialize, Serialize)]
+    /// struct Counter { count: u64 }
+    /// 
+    /// let program_id = "11111111111111111111111111111111";
+    /// let parser = ParserBuilder::new(program_id)
+    ///     .register_borsh_account::<Counter>("Counter")
+    ///     .build();
+    /// let account = SolanaAccount {
+    ///     pubkey: program_id.to_string(),
+    ///     data: 1u64.to_le_bytes().to_vec(),
+    ///     owner: program_id.to_string(),
+    ///     lamports: 890_880,
+    ///     executable: false,
+    ///     rent_epoch: 0,
+    /// };
+    /// 
+    /// let options = OutputOptions { include_account_meta: true, ..Default::default() };
+    /// let parsed = parser.parse_account_with_options(&account, &options.to_parse_options(false)).unwrap();
+    /// assert_eq!(
+    ///     parsed.data,
+    ///     r#"{"_data_len":8,"_lamports":890880,"_owner":"11111111111111111111111111111111","count":1}"#
+    /// );
+    /// ```
+    /// 
+    /// `include_program_metadata` stamps each record with the parser's
+    /// metadata:
+    /// 
+    /// ```
+    /// use solana_rpcx_sdk::prelude::*;
+    /// 
+    /// #[derive(BorshDeserialize, Serialize)]
+    /// struct Counter { count: u64 }
+    /// 
+    /// let program_id = "11111111111111111111111111111111";
+    /// let parser = ParserBuilder::new(program_id)
+    ///     .register_account_with_discriminator::<Counter>("Counter", vec![7])
+    ///     .program_name("counter")
+    ///     .version("1.2.0")
+    ///     .build();
+    /// 
+    /// let mut data = vec![7];
+    /// data.extend_from_slice(&3u64.to_le_bytes());
+    /// let account = SolanaAccount {
+    ///     pubkey: program_id.to_string(),
+    ///     data,
+    ///     owner: program_id.to_string(),
+    ///     lamports: 0,
+    ///     executable: false,
+    ///     rent_epoch: 0,
+    /// };
+    /// 
+    /// let options = OutputOptions { include_program_metadata: true, ..Default::default() };
+    /// let parsed = parser.parse_account_with_options(&account, &options.to_parse_options(false)).unwrap();
+    /// assert_eq!(
+    ///     parsed.data,
+    ///     r#"{"_program_metadata":{"name":"counter","program_id":"11111111111111111111111111111111","project_url":null,"version":"1.2.0"},"count":3}"#
+    /// );
+    /// ```
+    pub fn parse_account_with_options(
+        &self,
+        account: &SolanaAccount,
+        options: &ParseOptions,
+    ) -> Result<ParsedAccount, ParseError> {
+        let output_options = OutputOptions::from_parse_options(options)?;
+        if output_options.classify_only {
+            return self.classify_account(account);
+        }
+        
+        let mut value = self.parse_account_value(account)?;
+        if output_options.include_account_meta {
+            inject_fields(&mut value.data, vec![
+                ("_lamports", serde_json::json!(account.lamports)),
+                ("_data_len", serde_json::json!(account.data.len())),
+                ("_owner", serde_json::json!(account.owner)),
+            ]);
+        }
+        if output_options.include_program_metadata {
+            self.inject_program_metadata(&mut value.data);
+        }
+        value.apply_options(&output_options);
+        value.into_parsed_account(options.pretty_json)
+    }
+    
+    /// Parse an account into a [`ParsedValue`], leaving the data as JSON
+    pub fn parse_account_value(&self, account: &SolanaAccount) -> Result<ParsedValue, ParseError> {
+        let value = self.dispatch_account(account)?;
+        Ok(self.embed_type_field(value))
+    }
+    
+    /// Add `_program_metadata`, if there is metadata
+    /// 
+    /// Like [`metadata_json`](Self::metadata_json), a missing program id is
+    /// filled in with the parser's.
+    fn inject_program_metadata(&self, data: &mut serde_json::Value) {
+        if let Some(metadata) = &self.metadata {
+            inject_fields(data, vec![("_program_metadata", serde_json::json!({
+                "name": metadata.name,
+                "version": metadata.version,
+                "program_id": metadata.program_id.as_ref().unwrap_or(&self.program_id),
+                "project_url": metadata.project_url,
+            }))]);
+        }
+    }
+    
+    fn embed_type_field(&self, mut value: ParsedValue) -> ParsedValue {
+        if let Some(field) = &self.type_field_name {
+            let account_type = serde_json::Value::String(value.account_type.clone());
+            inject_fields(&mut value.data, vec![(field, account_type)]);
+        }
+        value
+    }
+    
+    /// The account type `prefix` belongs to, judging by discriminator alone
+    /// 
+    /// The cheap first pass over `getProgramAccounts` results fetched with a
+    /// `dataSlice`: nothing is deserialized, so `prefix` only needs to cover
+    /// the discriminator. `None` if `owner` isn't this program (unless
+    /// [`ignore_owner`](ParserBuilder::ignore_owner) is set), if no
+    /// discriminator matches, or if the prefix is shorter than the
+    /// discriminator it starts. Parsers without a discriminator never match.
+    /// 
+    /// ```
+    /// use solana_rpcx_sdk::prelude::*;
+    /// 
+    /// #[derive(BorshDeserialize, Serialize)]
+    /// struct Vault { amount: u64 }
+    /// 
+    /// let program_id = "11111111111111111111111111111111";
+    /// let discriminator = compute_anchor_discriminator("account", "Vault");
+    /// let parser = ParserBuilder::new(program_id)
+    ///     .register_account_with_discriminator::<Vault>("Vault", discriminator.to_vec())
+    ///     .build();
+    /// 
+    /// assert_eq!(parser.classify(program_id, &discriminator), Some("Vault".to_string()));
+    /// // Extra bytes are fine, the body isn't read
+    /// assert_eq!(parser.classify(program_id, &[&discriminator[..], &[0xff; 4]].concat()), Some("Vault".to_string()));
+    /// // Truncated prefixes can't be classified
+    /// assert_eq!(parser.classify(program_id, &discriminator[..7]), None);
+    /// assert_eq!(parser.classify(program_id, &[]), None);
+    /// assert_eq!(parser.classify(program_id, &[0; 8]), None);
+    /// assert_eq!(parser.classify("SysvarRent111111111111111111111111111111111", &discriminator), None);
+    /// ```
+    pub fn classify(&self, owner: &str, prefix: &[u8]) -> Option<String> {
+        if !self.ignore_owner && owner != self.program_id {
+            return None;
+        }
+        self.classify_prefix(prefix).map(|config| config.type_name.clone())
+    }
+    
+    /// The type name of `account`, matched by discriminator without
+    /// deserializing the body
+    /// 
+    /// A much cheaper `parse_account` for indexers that only need the type.
+    /// Unlike [`classify`](Self::classify) it takes the whole account: the
+    /// owner is checked, as are the data length limit and any exact length a
+    /// parser is bound to, and all-zero discriminator bytes are reported as a
+    /// closed account. Parsers without a discriminator can't be identified
+    /// this way and never match; nor does the fallback run.
+    /// 
+    /// ```
+    /// use solana_rpcx_sdk::prelude::*;
+    /// 
+    /// #[derive(BorshDeserialize, Serialize)]
+    /// struct Vault { amount: u64 }
+    /// 
+    /// let program_id = "11111111111111111111111111111111";
+    /// let parser = ParserBuilder::new(program_id)
+    ///     .register_account_with_discriminator::<Vault>("Vault", vec![3])
+    ///     .build();
+    /// let account = |owner: &str, data: Vec<u8>| SolanaAccount {
+    ///     pubkey: String::new(),
+    ///     data,
+    ///     owner: owner.to_string(),
+    ///     lamports: 0,
+    ///     executable: false,
+    ///     rent_epoch: 0,
+    /// };
+    /// 
+    /// // The body is never read, so a truncated one still identifies
+    /// assert_eq!(parser.identify(&account(program_id, vec![3, 1])).unwrap(), "Vault");
+    /// assert!(matches!(parser.identify(&account(program_id, vec![4])), Err(ParseError::UnknownAccountType(_))));
+    /// assert!(parser.identify(&account("SysvarRent111111111111111111111111111111111", vec![3])).is_err());
+    /// ```
+    pub fn identify(&self, account: &SolanaAccount) -> Result<String, ParseError> {
+        if !self.ignore_owner && account.owner != self.program_id {
+            return Err(ParseError::UnknownAccountType(
+                format!("Wrong owner: expected {}, got {}", self.program_id, account.owner)
+            ));
+        }
+        
+        self.check_data_len(&account.data)?;
+        
+        if self.account_parsers.is_empty() {
+            return Err(ParseError::UnknownAccountType("No account types are registered".to_string()));
+        }
+        
+        let matched = self.account_index.candidates(&account.data)
+            .into_iter()
+            .map(|position| &self.account_parsers[position])
+            .find(|config| {
+                config.discriminator.is_some()
+                    && config.data_len.is_none_or(|len| len == account.data.len())
+            });
+        if let Some(config) = matched {
+            return Ok(config.type_name.clone());
+        }
+        
+        if looks_closed(&account.data, self.account_index.max_len()) {
+            return Err(SdkError::ClosedAccount(format!(
+                "{} has all-zero discriminator bytes", account.pubkey
+            )).into());
+        }
+        
+        Err(no_match_error("account", &account.data))
+    }
+    
+    /// First parser whose discriminator `prefix` starts with
+    fn classify_prefix(&self, prefix: &[u8]) -> Option<&AccountParserConfig> {
+        self.account_index.candidates(prefix)
+            .into_iter()
+            .map(|position| &self.account_parsers[position])
+            .find(|config| config.discriminator.is_some())
+    }
+    
+    /// Match `account` by discriminator alone, for `classify_only`
+    /// 
+    /// The data may be a prefix of the account, so data length limits and
+    /// closed-account detection don't apply.
+    fn classify_account(&self, account: &SolanaAccount) -> Result<ParsedAccount, ParseError> {
+        if !self.ignore_owner && account.owner != self.program_id {
+            return Err(ParseError::UnknownAccountType(
+                format!("Wrong owner: expected {}, got {}", self.program_id, account.owner)
+            ));
+        }
+        
+        let config = self.classify_prefix(&account.data)
+            .ok_or_else(|| no_match_error("account", &account.data))?;
+        
+        Ok(ParsedAccount {
+            account_type: config.type_name.clone(),
+            data: "{}".to_string(),
+            discriminator: config.discriminator.clone(),
+        })
+    }
+    
+    /// Find the account parser that accepts `account`, falling back if set
+    fn dispatch_account(&self, account: &SolanaAccount) -> Result<ParsedValue, ParseError> {
         // Check owner matches
-        if account.owner != self.program_id {
+        if !self.ignore_owner && account.owner != self.program_id {
             return Err(ParseError::UnknownAccountType(
                 format!("Wrong owner: expected {}, got {}", self.program_id, account.owner)
             ));
         }
         
-        // Try each parser in order
+        self.check_data_len(&account.data)?;
+        
+        if self.account_parsers.is_empty() && self.fallback.is_none() {
+            return Err(ParseError::UnknownAccountType("No account types are registered".to_string()));
+        }
+        
+        // Try each parser whose discriminator matches, in order
         let mut last_error = None;
-        for config in &self.account_parsers {
-            match (config.parser)(&account.data) {
+        for position in self.account_index.candidates(&account.data) {
+            match (self.account_parsers[position].parser)(account) {
                 Ok(result) => return Ok(result),
                 Err(e) => last_error = Some(e),
             }
         }
         
-        Err(last_error.unwrap_or_else(|| {
-            ParseError::UnknownAccountType("No parser could handle this account".to_string())
-        }))
+        // Closed accounts are reported even when a fallback is set
+        if looks_closed(&account.data, self.account_index.max_len()) {
+            return Err(SdkError::ClosedAccount(format!(
+                "{} has all-zero discriminator bytes", account.pubkey
+            )).into());
+        }
+        
+        if let Some(fallback) = &self.fallback {
+            return fallback(account).and_then(ParsedValue::try_from);
+        }
+        
+        Err(last_error.unwrap_or_else(|| no_match_error("account", &account.data)))
+    }
+    
+    /// Parse a compressed account leaf with the compressed parsers
+    /// 
+    /// The leaf's data hash is added to the output as `_data_hash` (hex); it
+    /// is not checked against the data. Unlike `parse_account` there is no
+    /// owner check, closed-account detection or fallback.
+    pub fn parse_compressed_leaf(&self, leaf: &[u8]) -> Result<ParsedAccount, ParseError> {
+        self.check_data_len(leaf)?;
+        let leaf = CompressedLeaf::split(leaf)?;
+        
+        let account = SolanaAccount {
+            pubkey: String::new(),
+            data: leaf.data.to_vec(),
+            owner: self.program_id.clone(),
+            lamports: 0,
+            executable: false,
+            rent_epoch: 0,
+        };
+        
+        let mut last_error = None;
+        for position in self.compressed_index.candidates(leaf.data) {
+            match (self.compressed_parsers[position].parser)(&account) {
+                Ok(mut value) => {
+                    inject_fields(&mut value.data, vec![
+                        ("_data_hash", serde_json::json!(crate::bytes_to_hex(&leaf.data_hash))),
+                    ]);
+                    return self.embed_type_field(value).into_parsed_account(false);
+                }
+                Err(e) => last_error = Some(e),
+            }
+        }
+        
+        Err(last_error.unwrap_or_else(|| no_match_error("compressed account", leaf.data)))
+    }
+    
+    /// Parse event data (discriminator and Borsh payload) with the event parsers
+    /// 
+    /// For `emit_cpi!` events taken from instruction data, strip the leading
+    /// [`ANCHOR_EVENT_IX_TAG`](crate::ANCHOR_EVENT_IX_TAG) first. The event
+    /// name is returned as `account_type`.
+    pub fn parse_event(&self, data: &[u8]) -> Result<ParsedAccount, ParseError> {
+        self.check_data_len(data)?;
+        if self.event_parsers.is_empty() {
+            return Err(ParseError::UnknownAccountType("No event types are registered".to_string()));
+        }
+        
+        let event = SolanaAccount {
+            pubkey: String::new(),
+            data: data.to_vec(),
+            owner: self.program_id.clone(),
+            lamports: 0,
+            executable: false,
+            rent_epoch: 0,
+        };
+        
+        let mut last_error = None;
+        for position in self.event_index.candidates(data) {
+            match (self.event_parsers[position].parser)(&event) {
+                Ok(value) => return self.embed_type_field(value).into_parsed_account(false),
+                Err(e) => last_error = Some(e),
+            }
+        }
+        
+        Err(last_error.unwrap_or_else(|| no_match_error("event", data)))
+    }
+    
+    /// Parse the events this program logged in a transaction's `logMessages`
+    /// 
+    /// Only `Program data:` lines written while this program was executing
+    /// are parsed (see [`program_data_logs`](crate::program_data_logs)), in
+    /// log order. A line that isn't valid base64 or matches no event gets an
+    /// error result rather than stopping the scan.
+    /// 
+    /// ```
+    /// use solana_rpcx_sdk::prelude::*;
+    /// 
+    /// #[derive(BorshDeserialize, Serialize)]
+    /// struct Deposited { amount: u64 }
+    /// 
+    /// let program_id = "11111111111111111111111111111111";
+    /// let parser = ParserBuilder::new(program_id)
+    ///     .register_anchor_event::<Deposited>("Deposited")
+    ///     .build();
+    /// 
+    /// // "Deposited" with amount 5, base64
+    /// let logs = [
+    ///     format!("Program {} invoke [1]", program_id),
+    ///     "Program data: b40aLaEjZDkFAAAAAAAAAA==".to_string(),
+    ///     "Program data: not base64!".to_string(),
+    ///     format!("Program {} success", program_id),
+    /// ];
+    /// let events = parser.parse_event_logs(&logs);
+    /// assert_eq!(events.len(), 2);
+    /// assert_eq!(events[0].log_index, 1);
+    /// assert_eq!(events[0].result.as_ref().unwrap().data, r#"{"amount":5}"#);
+    /// assert!(events[1].result.is_err());
+    /// ```
+    pub fn parse_event_logs<S: AsRef<str>>(&self, logs: &[S]) -> Vec<crate::LogEvent> {
+        crate::program_data_logs(logs)
+            .into_iter()
+            .filter(|log| log.program_id.as_deref() == Some(self.program_id.as_str()))
+            .map(|log| crate::LogEvent {
+                result: log.data.and_then(|data| self.parse_event(&data)),
+                log_index: log.log_index,
+                program_id: log.program_id,
+            })
+            .collect()
+    }
+    
+    /// Lazily parse a stream of accounts
+    /// 
+    /// Accounts are parsed one at a time as the iterator is advanced, so memory
+    /// stays bounded even for very large `getProgramAccounts` results. Each
+    /// item carries its own result; a failure doesn't end the stream.
+    pub fn parse_stream<'a, I>(
+        &'a self,
+        accounts: I,
+    ) -> impl Iterator<Item = Result<ParsedAccount, ParseError>> + 'a
+    where
+        I: IntoIterator<Item = SolanaAccount>,
+        I::IntoIter: 'a,
+    {
+        accounts.into_iter().map(move |account| self.parse_account(&account))
+    }
+    
+    /// Parse a batch of accounts across rayon's global thread pool
+    /// 
+    /// Results come back in the same order as `accounts`, so index `i` of the
+    /// output always corresponds to `accounts[i]`.
+    #[cfg(feature = "rayon")]
+    pub fn parse_accounts_parallel(
+        &self,
+        accounts: &[SolanaAccount],
+    ) -> Vec<Result<ParsedAccount, ParseError>> {
+        use rayon::prelude::*;
+        
+        accounts.par_iter().map(|account| self.parse_account(account)).collect()
+    }
+    
+    /// Parse a batch of accounts and group the results by account type
+    /// 
+    /// Accounts that fail to parse (wrong owner, no matching parser, closed)
+    /// are kept under `"Unknown"`, described by their owner, data length,
+    /// leading bytes and the error. Within a group, accounts keep their
+    /// order in `accounts`.
+    /// 
+    /// ```
+    /// use solana_rpcx_sdk::prelude::*;
+    /// 
+    /// #[derive(BorshDeserialize, Serialize)]
+    /// struct Counter { count: u64 }
+    /// 
+    /// let program_id = "11111111111111111111111111111111";
+    /// let parser = ParserBuilder::new(program_id)
+    ///     .register_account_with_discriminator::<Counter>("Counter", vec![1])
+    ///     .build();
+    /// 
+    /// let account = |data: Vec<u8>| SolanaAccount {
+    ///     pubkey: "Acct".to_string(),
+    ///     data,
+    ///     owner: program_id.to_string(),
+    ///     lamports: 0,
+    ///     executable: false,
+    ///     rent_epoch: 0,
+    /// };
+    /// let mut counter = vec![1];
+    /// counter.extend_from_slice(&7u64.to_le_bytes());
+    /// 
+    /// let groups = parser.parse_grouped(&[account(counter.clone()), account(vec![9; 3]), account(counter)]);
+    /// assert_eq!(groups["Counter"].len(), 2);
+    /// 
+    /// let unknown: serde_json::Value = serde_json::from_str(&groups["Unknown"][0].data).unwrap();
+    /// assert_eq!(unknown["data_len"], 3);
+    /// assert_eq!(unknown["owner"], program_id);
+    /// ```
+    pub fn parse_grouped(&self, accounts: &[SolanaAccount]) -> HashMap<String, Vec<ParsedAccount>> {
+        let mut groups: HashMap<String, Vec<ParsedAccount>> = HashMap::new();
+        
+        for account in accounts {
+            let parsed = self.parse_account(account)
+                .unwrap_or_else(|e| describe_unknown(account, Some(&e)));
+            groups.entry(parsed.account_type.clone()).or_default().push(parsed);
+        }
+        
+        groups
     }
     
     /// Parse an instruction using registered parsers
     pub fn parse_instruction(&self, instruction: &InstructionData) -> Result<ParsedInstruction, ParseError> {
+        self.dispatch_instruction(instruction).map(|(parsed, _)| parsed)
+    }
+    
+    /// Parse several instructions, one result per item
+    pub fn parse_instructions(&self, instructions: &[InstructionData]) -> Vec<Result<ParsedInstruction, ParseError>> {
+        instructions.iter().map(|instruction| self.parse_instruction(instruction)).collect()
+    }
+    
+    /// Parse several instructions and count successes and failures
+    /// 
+    /// A quick way to tell whether a whole transaction parsed cleanly.
+    /// 
+    /// ```
+    /// use solana_rpcx_sdk::prelude::*;
+    /// 
+    /// #[derive(BorshDeserialize, Serialize)]
+    /// struct Increment { by: u64 }
+    /// 
+    /// let program_id = "11111111111111111111111111111111";
+    /// let parser = ParserBuilder::new(program_id)
+    ///     .register_anchor_instruction::<Increment>("increment")
+    ///     .build();
+    /// 
+    /// let instruction = |data: Vec<u8>| InstructionData {
+    ///     program_id: program_id.to_string(),
+    ///     data,
+    ///     accounts: vec![],
+    /// };
+    /// let mut increment = anchor_instruction_discriminator("increment").to_vec();
+    /// increment.extend_from_slice(&1u64.to_le_bytes());
+    /// 
+    /// let batch = parser.parse_instructions_summary(&[instruction(increment), instruction(vec![0; 4])]);
+    /// assert_eq!((batch.succeeded, batch.failed), (1, 1));
+    /// assert!(!batch.is_clean());
+    /// assert_eq!(batch.errors().map(|(index, _)| index).collect::<Vec<_>>(), [1]);
+    /// ```
+    pub fn parse_instructions_summary(&self, instructions: &[InstructionData]) -> BatchResult {
+        let results = self.parse_instructions(instructions);
+        let succeeded = results.iter().filter(|result| result.is_ok()).count();
+        
+        BatchResult {
+            failed: results.len() - succeeded,
+            succeeded,
+            results,
+        }
+    }
+    
+    /// Parse the instructions of a decoded transaction that call this program
+    /// 
+    /// Returns a JSON array with one record per instruction, in order (see
+    /// [`instruction_records`](crate::instruction_records)); instructions for
+    /// other programs are kept as raw hex. Fails if an instruction's accounts
+    /// can't be resolved, e.g. when a v0 message's lookup tables weren't
+    /// loaded.
+    pub fn parse_transaction(
+        &self,
+        transaction: &crate::DecodedTransaction,
+        loaded: &crate::LoadedAddresses,
+    ) -> Result<serde_json::Value, ParseError> {
+        self.parse_transaction_with_inner(transaction, &[], loaded)
+    }
+    
+    /// [`parse_transaction`](Self::parse_transaction), also parsing the
+    /// instructions each one invoked through CPI
+    /// 
+    /// `inner` comes from the transaction's status meta (see
+    /// [`inner_instructions_from_meta`](crate::inner_instructions_from_meta)).
+    /// Inner records follow their parent and carry its index as
+    /// `parent_index` (see [`transaction_records`](crate::transaction_records)).
+    pub fn parse_transaction_with_inner(
+        &self,
+        transaction: &crate::DecodedTransaction,
+        inner: &[crate::InnerInstructions],
+        loaded: &crate::LoadedAddresses,
+    ) -> Result<serde_json::Value, ParseError> {
+        let instructions = transaction.resolve_instructions(loaded)?;
+        let inner = transaction.resolve_inner_instructions(inner, loaded)?;
+        Ok(crate::transaction_records(&instructions, &inner, |instruction| {
+            self.can_parse(&instruction.program_id, &instruction.data)
+                .then(|| self.parse_instruction(instruction))
+        }))
+    }
+    
+    /// Parse an instruction, honouring `options.pretty_json` and any
+    /// [`OutputOptions`] in `options.custom`
+    /// 
+    /// With `include_discriminator`, `_discriminator` and `_program` are
+    /// added to the data so a transaction trace can be reconstructed.
+    pub fn parse_instruction_with_options(
+        &self,
+        instruction: &InstructionData,
+        options: &ParseOptions,
+    ) -> Result<ParsedInstruction, ParseError> {
+        let output_options = OutputOptions::from_parse_options(options)?;
+        let (mut parsed, config) = self.dispatch_instruction(instruction)?;
+        
+        let mut data: serde_json::Value = serde_json::from_str(&parsed.data)
+            .map_err(|e| ParseError::InvalidData(format!("Parser returned invalid JSON: {}", e)))?;
+        if output_options.include_program_metadata {
+            self.inject_program_metadata(&mut data);
+        }
+        apply_value_options(&mut data, &output_options);
+        
+        if output_options.include_discriminator {
+            let discriminator = config.discriminator.as_deref().map(crate::bytes_to_hex);
+            inject_fields(&mut data, vec![
+                ("_discriminator", serde_json::json!(discriminator)),
+                ("_program", serde_json::json!(instruction.program_id)),
+            ]);
+        }
+        
+        parsed.data = to_json_string(&data, options.pretty_json, output_options.bignum_as_string)?;
+        
+        Ok(parsed)
+    }
+    
+    /// Best-effort parse of bytes that may be account data or instruction data
+    /// 
+    /// Tries the account parsers, then the instruction parsers, and reports
+    /// which matched. No owner check, closed-account detection or fallback
+    /// is applied. Bytes are ambiguous when an account and an instruction
+    /// share a discriminator (or either has none): the account reading wins,
+    /// so only rely on this when the program's discriminators are disjoint.
+    /// 
+    /// ```
+    /// use solana_rpcx_sdk::prelude::*;
+    /// 
+    /// #[derive(BorshDeserialize, Serialize)]
+    /// struct Counter { count: u64 }
+    /// 
+    /// #[derive(BorshDeserialize, Serialize)]
+    /// struct Increment { by: u64 }
+    /// 
+    /// let parser = ParserBuilder::new("11111111111111111111111111111111")
+    ///     .register_account_with_discriminator::<Counter>("Counter", vec![1])
+    ///     .register_anchor_instruction::<Increment>("increment")
+    ///     .build();
+    /// 
+    /// let mut data = anchor_instruction_discriminator("increment").to_vec();
+    /// data.extend_from_slice(&5u64.to_le_bytes());
+    /// assert!(matches!(parser.parse_any(&data), ParseKind::Instruction(ix) if ix.instruction_name == "increment"));
+    /// assert!(matches!(parser.parse_any(&[9, 9]), ParseKind::Unknown));
+    /// ```
+    pub fn parse_any(&self, data: &[u8]) -> ParseKind {
+        if self.check_data_len(data).is_err() {
+            return ParseKind::Unknown;
+        }
+        
+        let account = SolanaAccount {
+            pubkey: String::new(),
+            data: data.to_vec(),
+            owner: self.program_id.clone(),
+            lamports: 0,
+            executable: false,
+            rent_epoch: 0,
+        };
+        for position in self.account_index.candidates(data) {
+            let parsed = (self.account_parsers[position].parser)(&account)
+                .and_then(|value| self.embed_type_field(value).into_parsed_account(false));
+            if let Ok(account) = parsed {
+                return ParseKind::Account(account);
+            }
+        }
+        
+        let instruction = InstructionData {
+            program_id: self.program_id.clone(),
+            data: account.data,
+            accounts: Vec::new(),
+        };
+        for position in self.instruction_index.candidates(&instruction.data) {
+            if let Ok(instruction) = (self.instruction_parsers[position].parser)(&instruction) {
+                return ParseKind::Instruction(instruction);
+            }
+        }
+        
+        ParseKind::Unknown
+    }
+    
+    /// Find the instruction parser that accepts `instruction`
+    fn dispatch_instruction(
+        &self,
+        instruction: &InstructionData,
+    ) -> Result<(ParsedInstruction, &InstructionParserConfig), ParseError> {
+        self.check_data_len(&instruction.data)?;
+        
+        if self.instruction_parsers.is_empty() {
+            return Err(ParseError::UnknownAccountType("No instruction types are registered".to_string()));
+        }
+        
         let mut last_error = None;
-        for config in &self.instruction_parsers {
-            match (config.parser)(&instruction.data) {
-                Ok(result) => return Ok(result),
+        for position in self.instruction_index.candidates(&instruction.data) {
+            let config = &self.instruction_parsers[position];
+            match (config.parser)(instruction) {
+                Ok(result) => return name_accounts(result, config, instruction).map(|result| (result, config)),
                 Err(e) => last_error = Some(e),
             }
         }
         
-        Err(last_error.unwrap_or_else(|| {
-            ParseError::UnknownAccountType("No instruction parser matched".to_string())
-        }))
+        Err(last_error.unwrap_or_else(|| no_match_error("instruction", &instruction.data)))
+    }
+    
+    fn check_data_len(&self, data: &[u8]) -> Result<(), ParseError> {
+        if data.len() > self.max_data_len {
+            return Err(ParseError::InvalidData(format!(
+                "Data length {} exceeds the {} byte limit", data.len(), self.max_data_len
+            )));
+        }
+        Ok(())
     }
     
     /// Check if this parser can handle the given owner/data
@@ -298,6 +2520,101 @@ impl Parser {
         owner == self.program_id
     }
     
+    /// Check a batch of `(owner, data_preview)` pairs in one call
+    /// 
+    /// Equivalent to calling [`can_parse`](Self::can_parse) on each item;
+    /// results are in input order.
+    pub fn can_parse_batch<O, D>(&self, items: &[(O, D)]) -> Vec<bool>
+    where
+        O: AsRef<str>,
+        D: AsRef<[u8]>,
+    {
+        items
+            .iter()
+            .map(|(owner, data)| self.can_parse(owner.as_ref(), data.as_ref()))
+            .collect()
+    }
+    
+    /// The discriminator registered for `type_name`
+    /// 
+    /// `None` if no account parser has that name or it has no discriminator.
+    /// For building fixture data without recomputing what the builder
+    /// derived, e.g. with `testing::MockAccount::discriminator`.
+    /// 
+    /// ```
+    /// use solana_rpcx_sdk::prelude::*;
+    /// use solana_rpcx_sdk::Anchor8;
+    /// 
+    /// #[derive(BorshDeserialize, Serialize)]
+    /// struct Counter { count: u64 }
+    /// 
+    /// let parser = ParserBuilder::new("11111111111111111111111111111111")
+    ///     .register_account_with_strategy::<Counter, _>("Counter", Anchor8::account("Counter"))
+    ///     .build();
+    /// 
+    /// assert_eq!(
+    ///     parser.discriminator_for("Counter"),
+    ///     Some(compute_anchor_discriminator("account", "Counter").to_vec()),
+    /// );
+    /// assert_eq!(parser.discriminator_for("Vault"), None);
+    /// ```
+    pub fn discriminator_for(&self, type_name: &str) -> Option<Vec<u8>> {
+        self.account_parsers.iter()
+            .find(|c| c.type_name == type_name)
+            .and_then(|c| c.discriminator.clone())
+    }
+    
+    /// Check that `data` starts with the discriminator registered for `type_name`
+    /// 
+    /// A debugging aid for "why doesn't this account parse as X?": mismatches
+    /// report both the expected and the actual bytes as hex. Fails if no
+    /// account parser is named `type_name` or if it has no discriminator.
+    /// 
+    /// ```
+    /// use solana_rpcx_sdk::prelude::*;
+    /// 
+    /// #[derive(BorshDeserialize, Serialize)]
+    /// struct Counter { count: u64 }
+    /// 
+    /// let parser = ParserBuilder::new("11111111111111111111111111111111")
+    ///     .register_account_with_discriminator::<Counter>("Counter", vec![0xca, 0xfe])
+    ///     .build();
+    /// 
+    /// assert!(parser.verify_discriminator("Counter", &[0xca, 0xfe, 1, 0]).is_ok());
+    /// 
+    /// let err = parser.verify_discriminator("Counter", &[0xbe, 0xef, 1, 0]).unwrap_err();
+    /// assert!(matches!(err, ParseError::UnknownAccountType(msg)
+    ///     if msg == "Discriminator mismatch for Counter: expected cafe, got beef"));
+    /// ```
+    pub fn verify_discriminator(&self, type_name: &str, data: &[u8]) -> Result<(), ParseError> {
+        let config = self.account_parsers.iter()
+            .find(|c| c.type_name == type_name)
+            .ok_or_else(|| ParseError::UnknownAccountType(format!(
+                "No account type named {}; registered: {}", type_name, self.get_supported_types().join(", ")
+            )))?;
+        
+        let expected = config.discriminator.as_deref().ok_or_else(|| ParseError::InvalidData(format!(
+            "{} has no discriminator to verify", type_name
+        )))?;
+        
+        if data.len() < expected.len() {
+            return Err(ParseError::InsufficientData(format!(
+                "Discriminator for {} is {} bytes ({}), data has {} ({})",
+                type_name, expected.len(), crate::bytes_to_hex(expected), data.len(), crate::bytes_to_hex(data)
+            )));
+        }
+        
+        let actual = &data[..expected.len()];
+        if actual != expected {
+            return Err(ParseError::UnknownAccountType(format!(
+                "Discriminator mismatch for {}: expected {}, got {}",
+                type_name, crate::bytes_to_hex(expected), crate::bytes_to_hex(actual)
+            )));
+        }
+        
+        Ok(())
+    }
+    
     /// Get list of supported account types
     pub fn get_supported_types(&self) -> Vec<String> {
         self.account_parsers
@@ -306,14 +2623,177 @@ impl Parser {
             .collect()
     }
     
+    /// Every registered type with its discriminator, in dispatch order
+    /// 
+    /// Accounts come first, then instructions, compressed accounts and events.
+    /// For tooling such as type pickers; see [`type_info_json`](Self::type_info_json)
+    /// to pass it over WIT.
+    pub fn get_type_info(&self) -> Vec<TypeInfo> {
+        let info = |kind: TypeKind, name: &String, discriminator: &Option<Vec<u8>>| TypeInfo {
+            name: name.clone(),
+            kind,
+            discriminator: discriminator.as_deref().map(crate::bytes_to_hex),
+            has_discriminator: discriminator.is_some(),
+        };
+        
+        self.account_parsers.iter().map(|c| info(TypeKind::Account, &c.type_name, &c.discriminator))
+            .chain(self.instruction_parsers.iter().map(|c| info(TypeKind::Instruction, &c.name, &c.discriminator)))
+            .chain(self.compressed_parsers.iter().map(|c| info(TypeKind::CompressedAccount, &c.type_name, &c.discriminator)))
+            .chain(self.event_parsers.iter().map(|c| info(TypeKind::Event, &c.type_name, &c.discriminator)))
+            .collect()
+    }
+    
+    /// [`get_type_info`](Self::get_type_info) as a JSON array
+    /// 
+    /// The WIT `get-supported-types` only returns names, so components pass
+    /// this through `view("type_info", ..)` instead.
+    /// 
+    /// ```
+    /// use solana_rpcx_sdk::prelude::*;
+    /// 
+    /// #[derive(BorshDeserialize, Serialize)]
+    /// struct Counter { count: u64 }
+    /// 
+    /// let parser = ParserBuilder::new("11111111111111111111111111111111")
+    ///     .register_account_with_discriminator::<Counter>("Counter", vec![0xca, 0xfe])
+    ///     .register_borsh_account::<Counter>("LegacyCounter")
+    ///     .build();
+    /// 
+    /// assert_eq!(
+    ///     parser.type_info_json(),
+    ///     r#"[{"name":"Counter","kind":"account","discriminator":"cafe","has_discriminator":true},"#.to_string()
+    ///         + r#"{"name":"LegacyCounter","kind":"account","discriminator":null,"has_discriminator":false}]"#
+    /// );
+    /// ```
+    pub fn type_info_json(&self) -> String {
+        serde_json::to_string(&self.get_type_info()).expect("TypeInfo is always serializable")
+    }
+    
     /// Get program metadata
     pub fn get_metadata(&self) -> Option<ProgramMetadata> {
         self.metadata.clone()
     }
+    
+    /// Account layout versions this parser supports, if any were declared
+    pub fn supported_versions(&self) -> &[String] {
+        &self.supported_versions
+    }
+    
+    /// Metadata plus `supported_versions` as a JSON object
+    /// 
+    /// For hosts that need more than the WIT record carries, e.g. returned
+    /// from a `view("metadata", ..)` handler.
+    /// 
+    /// ```
+    /// use solana_rpcx_sdk::prelude::*;
+    /// 
+    /// let parser = ParserBuilder::new("11111111111111111111111111111111")
+    ///     .program_name("Counter")
+    ///     .supported_versions(["v1", "v2"])
+    ///     .build();
+    /// 
+    /// assert_eq!(parser.supported_versions(), ["v1", "v2"]);
+    /// assert_eq!(
+    ///     parser.metadata_json(),
+    ///     r#"{"name":"Counter","program_id":"11111111111111111111111111111111","project_url":null,"supported_versions":["v1","v2"],"version":null}"#
+    /// );
+    /// ```
+    pub fn metadata_json(&self) -> String {
+        let field = |get: fn(&ProgramMetadata) -> &Option<String>| {
+            self.metadata.as_ref().and_then(|m| get(m).clone())
+        };
+        
+        serde_json::json!({
+            "name": field(|m| &m.name),
+            "program_id": field(|m| &m.program_id).unwrap_or_else(|| self.program_id.clone()),
+            "project_url": field(|m| &m.project_url),
+            "version": field(|m| &m.version),
+            "supported_versions": self.supported_versions,
+        })
+        .to_string()
+    }
+    
+    /// Replace the program metadata
+    pub fn set_metadata(&mut self, metadata: Option<ProgramMetadata>) {
+        self.metadata = metadata;
+    }
+    
+    /// Mutable access to the program metadata, e.g. to adjust one field
+    pub fn metadata_mut(&mut self) -> &mut Option<ProgramMetadata> {
+        &mut self.metadata
+    }
+    
+    /// JSON Schema document for a registered account type, if one was captured
+    #[cfg(feature = "schemars")]
+    pub fn json_schema(&self, type_name: &str) -> Option<String> {
+        self.schemas.get(type_name).cloned()
+    }
 }
 
 // Helper functions
 
+fn check_conflicts<'a>(
+    parsers: impl Iterator<Item = (&'a String, &'a Option<Vec<u8>>, Option<usize>)>,
+) -> Result<(), BuildError> {
+    let mut names = HashSet::new();
+    let mut discriminators: HashMap<(&[u8], Option<usize>), &str> = HashMap::new();
+    
+    for (name, discriminator, data_len) in parsers {
+        if !names.insert(name.as_str()) {
+            return Err(BuildError::DuplicateName(name.clone()));
+        }
+        
+        if let Some(discriminator) = discriminator {
+            if discriminator.is_empty() || discriminator.len() > MAX_DISCRIMINATOR_LEN {
+                return Err(BuildError::InvalidDiscriminator {
+                    name: name.clone(),
+                    len: discriminator.len(),
+                });
+            }
+            
+            if let Some(first) = discriminators.insert((discriminator, data_len), name) {
+                return Err(match data_len {
+                    Some(data_len) => BuildError::DuplicateLayout {
+                        discriminator: discriminator.clone(),
+                        data_len,
+                        first: first.to_string(),
+                        second: name.clone(),
+                    },
+                    None => BuildError::DuplicateDiscriminator {
+                        discriminator: discriminator.clone(),
+                        first: first.to_string(),
+                        second: name.clone(),
+                    },
+                });
+            }
+        }
+    }
+    
+    Ok(())
+}
+
+fn check_shadowing<'a>(
+    parsers: impl Iterator<Item = (&'a String, &'a Option<Vec<u8>>)>,
+) -> Result<(), BuildError> {
+    let mut undiscriminated: Option<&String> = None;
+    
+    for (name, discriminator) in parsers {
+        match (discriminator, undiscriminated) {
+            (None, None) => undiscriminated = Some(name),
+            (Some(_), Some(first)) => {
+                return Err(BuildError::ShadowedParser {
+                    undiscriminated: first.clone(),
+                    shadowed: name.clone(),
+                });
+            }
+            _ => {}
+        }
+    }
+    
+    Ok(())
+}
+
+#[cfg(feature = "anchor")]
 fn extract_type_name<T>() -> String {
     std::any::type_name::<T>()
         .split("::")
@@ -322,53 +2802,241 @@ fn extract_type_name<T>() -> String {
         .to_string()
 }
 
-fn parse_anchor_account<T>(
-    data: &[u8],
-    type_name: &str,
-    discriminator: &[u8; 8],
-) -> Result<ParsedAccount, ParseError>
+/// Closed or never-initialized accounts have no data at all
+fn ensure_not_empty(data: &[u8]) -> Result<(), ParseError> {
+    if data.is_empty() {
+        return Err(ParseError::InsufficientData("account data is empty".to_string()));
+    }
+    Ok(())
+}
+
+/// Whether unmatched data looks like a closed account
+/// 
+/// Checks the first `discriminator_len` bytes, or all of the data when no
+/// parser has a discriminator.
+fn looks_closed(data: &[u8], discriminator_len: Option<usize>) -> bool {
+    let len = discriminator_len.unwrap_or(data.len()).min(data.len());
+    len > 0 && data[..len].iter().all(|b| *b == 0)
+}
+
+/// Error for data whose leading bytes match no registered discriminator
+fn no_match_error(kind: &str, data: &[u8]) -> ParseError {
+    if data.is_empty() {
+        return ParseError::InsufficientData(format!("{} data is empty", kind));
+    }
+    
+    let prefix = &data[..data.len().min(8)];
+    ParseError::UnknownAccountType(format!(
+        "No {} parser matches discriminator {}", kind, crate::bytes_to_hex(prefix)
+    ))
+}
+
+/// Adapt a data-only parser to [`AccountParserFn`]
+fn data_parser<F>(parser: F) -> AccountParserFn
 where
-    T: borsh::BorshDeserialize + serde::Serialize,
+    F: Fn(&[u8]) -> Result<ParsedValue, ParseError> + Send + Sync + 'static,
 {
-    if data.len() < 8 {
-        return Err(ParseError::InsufficientData("Account data too short".to_string()));
+    Box::new(move |account: &SolanaAccount| parser(&account.data))
+}
+
+/// Adapt a data-only parser to [`InstructionParserFn`]
+fn instruction_parser<F>(parser: F) -> InstructionParserFn
+where
+    F: Fn(&[u8]) -> Result<ParsedInstruction, ParseError> + Send + Sync + 'static,
+{
+    Box::new(move |instruction: &InstructionData| parser(&instruction.data))
+}
+
+/// Add the `_accounts` object for an instruction with named accounts
+fn name_accounts(
+    mut parsed: ParsedInstruction,
+    config: &InstructionParserConfig,
+    instruction: &InstructionData,
+) -> Result<ParsedInstruction, ParseError> {
+    if config.account_names.is_empty() {
+        return Ok(parsed);
     }
     
-    if &data[0..8] != discriminator {
-        return Err(ParseError::UnknownAccountType("Wrong discriminator".to_string()));
+    let accounts: serde_json::Map<String, serde_json::Value> = config.account_names.iter()
+        .zip(&instruction.accounts)
+        .map(|(name, pubkey)| (name.clone(), serde_json::json!(pubkey)))
+        .collect();
+    
+    let mut data: serde_json::Value = serde_json::from_str(&parsed.data)
+        .map_err(|e| ParseError::InvalidData(format!("Parser returned invalid JSON: {}", e)))?;
+    inject_fields(&mut data, vec![("_accounts", serde_json::Value::Object(accounts))]);
+    parsed.data = data.to_string();
+    
+    Ok(parsed)
+}
+
+/// Default fallback: describe the account instead of failing
+fn unknown_account(account: &SolanaAccount) -> Result<ParsedAccount, ParseError> {
+    Ok(describe_unknown(account, None))
+}
+
+/// An `"Unknown"` account with the owner, data length, leading bytes and,
+/// when parsing failed, why
+fn describe_unknown(account: &SolanaAccount, error: Option<&ParseError>) -> ParsedAccount {
+    let discriminator: Vec<u8> = account.data.iter().take(8).copied().collect();
+    
+    let mut json = serde_json::json!({
+        "owner": account.owner,
+        "data_len": account.data.len(),
+        "discriminator": crate::bytes_to_hex(&discriminator),
+    });
+    if let Some(error) = error {
+        json["error"] = serde_json::json!({ "kind": error.code(), "message": error.message() });
     }
     
-    let account = T::try_from_slice(&data[8..])
-        .map_err(|e| ParseError::DeserializationFailed(e.to_string()))?;
+    ParsedAccount {
+        account_type: "Unknown".to_string(),
+        data: json.to_string(),
+        discriminator: Some(discriminator),
+    }
+}
+
+/// Reader over a byte slice that remembers how far it got
+/// 
+/// Borsh sizes the buffer for a `Vec<u8>`/`String` body from its length
+/// prefix, so a read asking for more than remains means the prefix is bogus.
+/// Such reads fail immediately (recorded in `overrun`) instead of letting
+/// Borsh keep growing the buffer.
+struct CountingReader<'a> {
+    data: &'a [u8],
+    position: usize,
+    overrun: Option<usize>,
+}
+
+impl std::io::Read for CountingReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let remaining = &self.data[self.position..];
+        if buf.len() > remaining.len() {
+            self.overrun = Some(buf.len());
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "length exceeds remaining data"));
+        }
+        buf.copy_from_slice(&remaining[..buf.len()]);
+        self.position += buf.len();
+        Ok(buf.len())
+    }
+}
+
+/// Borsh-deserialize `data[offset..]`, reporting the byte offset on failure
+pub(crate) fn deserialize_borsh<T>(data: &[u8], offset: usize, type_name: &str) -> Result<T, ParseError>
+where
+    T: borsh::BorshDeserialize,
+{
+    let mut reader = CountingReader { data, position: offset, overrun: None };
     
-    let json = serde_json::to_string(&account)
-        .map_err(|e| ParseError::InvalidData(e.to_string()))?;
+    let result = T::deserialize_reader(&mut reader);
+    if let Some(requested) = reader.overrun {
+        return Err(ParseError::InvalidData(format!(
+            "Field `{}` at offset {}: needs at least {} bytes, only {} remain",
+            type_name, reader.position, requested, data.len() - reader.position
+        )));
+    }
     
-    Ok(ParsedAccount {
-        account_type: type_name.to_string(),
-        data: json,
-        discriminator: Some(discriminator.to_vec()),
-    })
+    let value = result.map_err(|e| SdkError::FieldError {
+        field: type_name.to_string(),
+        offset: reader.position,
+        reason: e.to_string(),
+    })?;
+    
+    if reader.position != data.len() {
+        return Err(SdkError::FieldError {
+            field: type_name.to_string(),
+            offset: reader.position,
+            reason: format!("{} trailing bytes not consumed", data.len() - reader.position),
+        }.into());
+    }
+    
+    Ok(value)
 }
 
-fn parse_borsh_account<T>(
+fn parse_account_with_strategy<T>(
     data: &[u8],
     type_name: &str,
-) -> Result<ParsedAccount, ParseError>
+    strategy: &dyn DiscriminatorStrategy,
+) -> Result<ParsedValue, ParseError>
 where
     T: borsh::BorshDeserialize + serde::Serialize,
 {
-    let account = T::try_from_slice(data)
-        .map_err(|e| ParseError::DeserializationFailed(e.to_string()))?;
+    let offset = check_strategy(data, strategy, type_name)?;
+    let account = deserialize_borsh::<T>(data, offset, type_name)?;
     
-    let json = serde_json::to_string(&account)
-        .map_err(|e| ParseError::InvalidData(e.to_string()))?;
+    Ok(ParsedValue::from_serialize(type_name, &account, strategy.prefix()))
+}
+
+/// Check `data` against a strategy and return where the body starts
+fn check_strategy(data: &[u8], strategy: &dyn DiscriminatorStrategy, type_name: &str) -> Result<usize, ParseError> {
+    ensure_not_empty(data)?;
     
-    Ok(ParsedAccount {
-        account_type: type_name.to_string(),
-        data: json,
-        discriminator: None,
-    })
+    if !strategy.matches(data) {
+        return Err(match strategy.prefix() {
+            Some(prefix) => discriminator_error(data, &prefix, type_name),
+            None => ParseError::UnknownAccountType(format!(
+                "Wrong discriminator for {}: data is {} bytes starting {}",
+                type_name, data.len(), crate::bytes_to_hex(&data[..data.len().min(8)])
+            )),
+        });
+    }
+    
+    Ok(strategy.extract(data).map_or(0, |discriminator| discriminator.len()))
+}
+
+/// Check `data` starts with `expected`
+fn check_discriminator(data: &[u8], expected: &[u8], type_name: &str) -> Result<(), ParseError> {
+    if data.starts_with(expected) {
+        Ok(())
+    } else {
+        Err(discriminator_error(data, expected, type_name))
+    }
+}
+
+/// Wrong length for a parser bound to one, noting whether the discriminator
+/// matched too
+fn length_error(data: &[u8], expected_len: usize, discriminator: &[u8], type_name: &str) -> ParseError {
+    let actual = &data[..data.len().min(discriminator.len())];
+    let discriminator_note = if actual == discriminator {
+        "discriminator matches".to_string()
+    } else {
+        format!(
+            "discriminator is also wrong: expected {}, got {}",
+            crate::bytes_to_hex(discriminator), crate::bytes_to_hex(actual)
+        )
+    };
+    
+    ParseError::UnknownAccountType(format!(
+        "Wrong data length for {}: expected {}, got {} ({})",
+        type_name, expected_len, data.len(), discriminator_note
+    ))
+}
+
+/// One diagnostic for data that doesn't start with `expected`
+/// 
+/// Gives the data length, the expected discriminator and the bytes actually
+/// there, so a short account of the wrong type isn't reported as merely
+/// short. The variant is `InsufficientData` when the data is shorter than
+/// the discriminator and `UnknownAccountType` otherwise.
+fn discriminator_error(data: &[u8], expected: &[u8], type_name: &str) -> ParseError {
+    let actual = &data[..data.len().min(expected.len())];
+    let detail = format!(
+        "expected {}-byte discriminator {} at offset 0, data is {} bytes starting {}",
+        expected.len(),
+        crate::bytes_to_hex(expected),
+        data.len(),
+        if actual.is_empty() { "(empty)".to_string() } else { crate::bytes_to_hex(actual) },
+    );
+    
+    if data.len() >= expected.len() {
+        ParseError::UnknownAccountType(format!("Wrong discriminator for {}: {}", type_name, detail))
+    } else if expected.starts_with(actual) {
+        ParseError::InsufficientData(format!("Data too short for {}: {}", type_name, detail))
+    } else {
+        ParseError::InsufficientData(format!(
+            "Data too short for {}, and the bytes present don't match: {}", type_name, detail
+        ))
+    }
 }
 
 fn parse_account_with_discriminator<T>(
@@ -376,28 +3044,73 @@ fn parse_account_with_discriminator<T>(
     type_name: &str,
     discriminator: &[u8],
     disc_size: usize,
-) -> Result<ParsedAccount, ParseError>
+) -> Result<ParsedValue, ParseError>
 where
     T: borsh::BorshDeserialize + serde::Serialize,
 {
-    if data.len() < disc_size {
-        return Err(ParseError::InsufficientData("Data too short for discriminator".to_string()));
-    }
+    ensure_not_empty(data)?;
+    check_discriminator(data, discriminator, type_name)?;
     
-    if &data[0..disc_size] != discriminator {
-        return Err(ParseError::UnknownAccountType("Wrong discriminator".to_string()));
+    let account = deserialize_borsh::<T>(data, disc_size, type_name)?;
+    
+    Ok(ParsedValue::from_serialize(type_name, &account, Some(discriminator.to_vec())))
+}
+
+#[cfg(feature = "pinocchio")]
+fn parse_pod_account<T>(
+    data: &[u8],
+    type_name: &str,
+    discriminator: &[u8],
+) -> Result<ParsedValue, ParseError>
+where
+    T: bytemuck::Pod + serde::Serialize,
+{
+    ensure_not_empty(data)?;
+    
+    let expected = discriminator.len() + std::mem::size_of::<T>();
+    if data.len() != expected {
+        return Err(length_error(data, expected, discriminator, type_name));
     }
     
-    let account = T::try_from_slice(&data[disc_size..])
-        .map_err(|e| ParseError::DeserializationFailed(e.to_string()))?;
+    check_discriminator(data, discriminator, type_name)?;
+    
+    let account: T = bytemuck::try_pod_read_unaligned(&data[discriminator.len()..])
+        .map_err(|e| ParseError::DeserializationFailed(format!("{}: {}", type_name, e)))?;
     
-    let json = serde_json::to_string(&account)
+    let discriminator = if discriminator.is_empty() { None } else { Some(discriminator.to_vec()) };
+    Ok(ParsedValue::from_serialize(type_name, &account, discriminator))
+}
+
+fn parse_idl_account(
+    idl: &Idl,
+    account: &IdlAccount,
+    data: &[u8],
+    max_depth: usize,
+) -> Result<ParsedValue, ParseError> {
+    ensure_not_empty(data)?;
+    check_discriminator(data, &account.discriminator, &account.name)?;
+    
+    let value = idl.decode_account_with_max_depth(account, data, max_depth)?;
+    
+    Ok(ParsedValue::new(account.name.clone(), value, Some(account.discriminator.clone())))
+}
+
+fn parse_idl_instruction(
+    idl: &Idl,
+    instruction: &IdlInstruction,
+    data: &[u8],
+    max_depth: usize,
+) -> Result<ParsedInstruction, ParseError> {
+    check_discriminator(data, &instruction.discriminator, &instruction.name)?;
+    
+    let value = idl.decode_instruction_with_max_depth(instruction, data, max_depth)?;
+    
+    let json = serde_json::to_string(&value)
         .map_err(|e| ParseError::InvalidData(e.to_string()))?;
     
-    Ok(ParsedAccount {
-        account_type: type_name.to_string(),
+    Ok(ParsedInstruction {
+        instruction_name: instruction.name.clone(),
         data: json,
-        discriminator: Some(discriminator.to_vec()),
     })
 }
 
@@ -409,16 +3122,9 @@ fn parse_anchor_instruction<T>(
 where
     T: borsh::BorshDeserialize + serde::Serialize,
 {
-    if data.len() < 8 {
-        return Err(ParseError::InsufficientData("Instruction data too short".to_string()));
-    }
+    check_discriminator(data, discriminator, name)?;
     
-    if &data[0..8] != discriminator {
-        return Err(ParseError::UnknownAccountType("Wrong discriminator".to_string()));
-    }
-    
-    let instruction = T::try_from_slice(&data[8..])
-        .map_err(|e| ParseError::DeserializationFailed(e.to_string()))?;
+    let instruction = deserialize_borsh::<T>(data, 8, name)?;
     
     let json = serde_json::to_string(&instruction)
         .map_err(|e| ParseError::InvalidData(e.to_string()))?;
@@ -436,8 +3142,7 @@ fn parse_borsh_instruction<T>(
 where
     T: borsh::BorshDeserialize + serde::Serialize,
 {
-    let instruction = T::try_from_slice(data)
-        .map_err(|e| ParseError::DeserializationFailed(e.to_string()))?;
+    let instruction = deserialize_borsh::<T>(data, 0, name)?;
     
     let json = serde_json::to_string(&instruction)
         .map_err(|e| ParseError::InvalidData(e.to_string()))?;
@@ -446,4 +3151,272 @@ where
         instruction_name: name.to_string(),
         data: json,
     })
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use borsh::{BorshDeserialize, BorshSerialize};
+    use serde::Serialize;
+    use crate::testing::{mock_account, MockAccount};
+    
+    const PROGRAM_ID: &str = "11111111111111111111111111111111";
+    
+    #[derive(Debug, BorshSerialize, BorshDeserialize, Serialize)]
+    struct Counter {
+        count: u64,
+    }
+    
+    #[derive(Debug, BorshSerialize, BorshDeserialize, Serialize)]
+    struct Blob {
+        bytes: Vec<u8>,
+    }
+    
+    fn build_error(builder: ParserBuilder) -> BuildError {
+        match builder.try_build() {
+            Ok(_) => panic!("expected the registrations to be rejected"),
+            Err(e) => e,
+        }
+    }
+    
+    #[test]
+    fn trailing_bytes_are_rejected() {
+        let mut data = borsh::to_vec(&Counter { count: 1 }).unwrap();
+        data.push(0);
+        
+        let error = deserialize_borsh::<Counter>(&data, 0, "Counter").unwrap_err();
+        assert!(
+            matches!(&error, ParseError::DeserializationFailed(msg) if msg == "Field `Counter` at offset 8: 1 trailing bytes not consumed"),
+            "{:?}", error
+        );
+    }
+    
+    #[test]
+    fn duplicate_discriminators_are_rejected() {
+        let error = build_error(ParserBuilder::new(PROGRAM_ID)
+            .register_account_with_discriminator::<Counter>("Counter", vec![1])
+            .register_account_with_discriminator::<Blob>("Blob", vec![1]));
+        
+        assert_eq!(error, BuildError::DuplicateDiscriminator {
+            discriminator: vec![1],
+            first: "Counter".to_string(),
+            second: "Blob".to_string(),
+        });
+    }
+    
+    #[test]
+    fn duplicate_names_are_rejected() {
+        let error = build_error(ParserBuilder::new(PROGRAM_ID)
+            .register_account_with_discriminator::<Counter>("Counter", vec![1])
+            .register_account_with_discriminator::<Counter>("Counter", vec![2]));
+        
+        assert_eq!(error, BuildError::DuplicateName("Counter".to_string()));
+    }
+    
+    #[test]
+    fn account_and_instruction_conflicts_are_checked_separately() {
+        let parser = ParserBuilder::new(PROGRAM_ID)
+            .register_account_with_discriminator::<Counter>("counter", crate::compute_anchor_discriminator("global", "counter").to_vec())
+            .register_anchor_instruction::<Counter>("counter")
+            .try_build();
+        
+        assert!(parser.is_ok());
+    }
+    
+    #[test]
+    fn undiscriminated_parser_before_discriminated_one_is_rejected() {
+        let error = build_error(ParserBuilder::new(PROGRAM_ID)
+            .register_borsh_account::<Counter>("Legacy")
+            .register_account_with_discriminator::<Counter>("Counter", vec![1]));
+        assert_eq!(error, BuildError::ShadowedParser {
+            undiscriminated: "Legacy".to_string(),
+            shadowed: "Counter".to_string(),
+        });
+        
+        let error = build_error(ParserBuilder::new(PROGRAM_ID)
+            .register_borsh_instruction::<Counter>("legacy")
+            .register_anchor_instruction::<Counter>("increment"));
+        assert!(matches!(error, BuildError::ShadowedParser { .. }), "{:?}", error);
+    }
+    
+    #[test]
+    fn undiscriminated_parser_is_allowed_last_or_when_mixing_is_allowed() {
+        let last = ParserBuilder::new(PROGRAM_ID)
+            .register_account_with_discriminator::<Counter>("Counter", vec![1])
+            .register_borsh_account::<Counter>("Legacy")
+            .try_build();
+        assert!(last.is_ok());
+        
+        let mixed = ParserBuilder::new(PROGRAM_ID)
+            .register_borsh_account::<Counter>("Legacy")
+            .register_account_with_discriminator::<Counter>("Counter", vec![1])
+            .allow_mixed_discriminators()
+            .try_build();
+        assert!(mixed.is_ok());
+    }
+    
+    #[test]
+    fn shared_discriminator_needs_distinct_lengths() {
+        let parser = ParserBuilder::new(PROGRAM_ID)
+            .register_account_with_disc_and_len::<Counter>("CounterV1", vec![1], 9)
+            .register_account_with_disc_and_len::<Blob>("BlobV1", vec![1], 13)
+            .try_build();
+        assert!(parser.is_ok());
+        
+        let error = build_error(ParserBuilder::new(PROGRAM_ID)
+            .register_account_with_disc_and_len::<Counter>("CounterV1", vec![1], 9)
+            .register_account_with_disc_and_len::<Counter>("CounterV2", vec![1], 9));
+        assert_eq!(error, BuildError::DuplicateLayout {
+            discriminator: vec![1],
+            data_len: 9,
+            first: "CounterV1".to_string(),
+            second: "CounterV2".to_string(),
+        });
+    }
+    
+    #[test]
+    fn all_zero_discriminator_is_reported_as_closed() {
+        let parser = ParserBuilder::new(PROGRAM_ID)
+            .register_account_with_discriminator::<Counter>("Counter", vec![1])
+            .with_unknown_fallback()
+            .build();
+        
+        let error = parser.parse_account(&mock_account(PROGRAM_ID, [0; 9])).unwrap_err();
+        assert!(crate::is_closed_account(&error), "{:?}", error);
+        assert!(
+            matches!(&error, ParseError::UnknownAccountType(msg) if msg.ends_with("has all-zero discriminator bytes")),
+            "{:?}", error
+        );
+    }
+    
+    #[test]
+    fn unmatched_non_zero_discriminator_is_not_closed() {
+        let parser = ParserBuilder::new(PROGRAM_ID)
+            .register_account_with_discriminator::<Counter>("Counter", vec![1])
+            .build();
+        
+        let error = parser.parse_account(&mock_account(PROGRAM_ID, [2; 9])).unwrap_err();
+        assert!(!crate::is_closed_account(&error), "{:?}", error);
+    }
+    
+    #[test]
+    fn wrong_owner_is_rejected_before_dispatch() {
+        let parser = ParserBuilder::new(PROGRAM_ID)
+            .register_borsh_account::<Counter>("Counter")
+            .build();
+        let account = mock_account("SysvarRent111111111111111111111111111111111", 5u64.to_le_bytes());
+        
+        let error = parser.parse_account(&account).unwrap_err();
+        assert!(matches!(&error, ParseError::UnknownAccountType(msg) if msg.starts_with("Wrong owner")), "{:?}", error);
+    }
+    
+    #[test]
+    fn ignore_owner_dispatches_on_the_data_alone() {
+        let parser = ParserBuilder::new(PROGRAM_ID)
+            .register_borsh_account::<Counter>("Counter")
+            .ignore_owner()
+            .build();
+        let account = mock_account("SysvarRent111111111111111111111111111111111", 5u64.to_le_bytes());
+        
+        assert_eq!(parser.parse_account(&account).unwrap().data, r#"{"count":5}"#);
+        assert!(!parser.can_parse(&account.owner, &account.data));
+    }
+    
+    #[test]
+    fn bogus_length_prefix_fails_without_reading_past_the_data() {
+        let mut data = u32::MAX.to_le_bytes().to_vec();
+        data.extend_from_slice(&[1, 2, 3, 4]);
+        
+        let error = deserialize_borsh::<Blob>(&data, 0, "Blob").unwrap_err();
+        assert!(
+            matches!(&error, ParseError::InvalidData(msg) if msg.contains("Field `Blob` at offset 4") && msg.contains("only 4 remain")),
+            "{:?}", error
+        );
+    }
+    
+    #[test]
+    fn bogus_length_prefix_is_reported_through_parse_account() {
+        let parser = ParserBuilder::new(PROGRAM_ID)
+            .register_account_with_discriminator::<Blob>("Blob", vec![1])
+            .build();
+        let account = MockAccount::new()
+            .owner(PROGRAM_ID)
+            .discriminator([1])
+            .data([0xff, 0xff, 0xff, 0x7f, 9])
+            .build();
+        
+        let error = parser.parse_account(&account).unwrap_err();
+        assert!(matches!(&error, ParseError::InvalidData(msg) if msg.contains("only 1 remain")), "{:?}", error);
+    }
+    
+    #[test]
+    fn data_over_the_limit_is_rejected_before_dispatch() {
+        let parser = ParserBuilder::new(PROGRAM_ID)
+            .register_borsh_account::<Blob>("Blob")
+            .max_data_len(8)
+            .build();
+        
+        let error = parser.parse_account(&mock_account(PROGRAM_ID, [0; 9])).unwrap_err();
+        assert!(
+            matches!(&error, ParseError::InvalidData(msg) if msg == "Data length 9 exceeds the 8 byte limit"),
+            "{:?}", error
+        );
+        assert!(parser.parse_account(&mock_account(PROGRAM_ID, [0; 4])).is_ok());
+    }
+    
+    #[test]
+    fn empty_and_oversized_discriminators_are_rejected() {
+        let error = build_error(ParserBuilder::new(PROGRAM_ID)
+            .register_account_with_discriminator::<Counter>("Counter", vec![]));
+        assert_eq!(error, BuildError::InvalidDiscriminator { name: "Counter".to_string(), len: 0 });
+        
+        let error = build_error(ParserBuilder::new(PROGRAM_ID)
+            .register_account_with_discriminator::<Counter>("Counter", vec![1; MAX_DISCRIMINATOR_LEN + 1]));
+        assert_eq!(error, BuildError::InvalidDiscriminator {
+            name: "Counter".to_string(),
+            len: MAX_DISCRIMINATOR_LEN + 1,
+        });
+    }
+    
+    #[test]
+    fn discriminator_of_the_maximum_length_is_accepted() {
+        let parser = ParserBuilder::new(PROGRAM_ID)
+            .register_account_with_discriminator::<Counter>("Counter", vec![1; MAX_DISCRIMINATOR_LEN])
+            .try_build();
+        
+        assert!(parser.is_ok());
+    }
+    
+    fn classifier() -> ParserBuilder {
+        ParserBuilder::new(PROGRAM_ID)
+            .register_account_with_discriminator::<Counter>("Counter", vec![1, 2, 3, 4])
+    }
+    
+    #[test]
+    fn classify_needs_the_whole_discriminator() {
+        let parser = classifier().build();
+        
+        assert_eq!(parser.classify(PROGRAM_ID, &[1, 2, 3, 4]), Some("Counter".to_string()));
+        assert_eq!(parser.classify(PROGRAM_ID, &[1, 2, 3, 4, 0xff]), Some("Counter".to_string()));
+        for truncated in [&[1, 2, 3][..], &[1], &[]] {
+            assert_eq!(parser.classify(PROGRAM_ID, truncated), None, "{:?}", truncated);
+        }
+    }
+    
+    #[test]
+    fn classify_checks_the_owner_unless_ignored() {
+        let other = "SysvarRent111111111111111111111111111111111";
+        
+        assert_eq!(classifier().build().classify(other, &[1, 2, 3, 4]), None);
+        assert_eq!(classifier().ignore_owner().build().classify(other, &[1, 2, 3, 4]), Some("Counter".to_string()));
+    }
+    
+    #[test]
+    fn classify_never_matches_undiscriminated_parsers() {
+        let parser = ParserBuilder::new(PROGRAM_ID)
+            .register_borsh_account::<Counter>("Counter")
+            .build();
+        
+        assert_eq!(parser.classify(PROGRAM_ID, &borsh::to_vec(&Counter { count: 1 }).unwrap()), None);
+    }
+}
@@ -22,10 +22,13 @@ pub use crate::bindings::exports::component::solana_rpcx_bindings::{
 
 // Re-export SDK types
 pub use crate::{
-    Parser, ParserBuilder,
+    Parser, ParserBuilder, ParserRegistry, ParseKind, BatchResult, ParsedValue, ParsedAccountExt, OutputOptions, Casing,
     AccountParser, InstructionParser,
     AccountParserConfig, InstructionParserConfig,
-    compute_anchor_discriminator,
+    DiscriminatorStrategy,
+    Transformer, TransformerBuilder,
+    TransformerRequestBuilder, TransformerRequestExt,
+    compute_anchor_discriminator, anchor_instruction_discriminator,
 };
 
 // Re-export common external types
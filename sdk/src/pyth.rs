@@ -0,0 +1,130 @@
+//! Pyth price account parsing
+//!
+//! Pyth's on-chain oracle (the v2 `pyth-client` program) stores prices in a
+//! fixed `repr(C)` layout rather than Borsh, behind a magic number shared by
+//! all of its account types:
+//!
+//! ```text
+//! offset  0  magic u32 (0xa1b2c3d4)    offset  20  exponent i32
+//!         4  version u32                       24  component count u32
+//!         8  account type u32 (3)              32  last slot u64
+//!        12  size u32                          40  valid slot u64
+//!        16  price type u32                    96  timestamp i64
+//!       112  product account [u8; 32]         208  aggregate price info
+//! ```
+
+use serde_json::{json, Value};
+use crate::bindings::component::solana_rpcx_bindings::types::*;
+use crate::{bytes_to_base58, format_fixed_point};
+
+/// Magic number at the start of every Pyth oracle account
+pub const PYTH_MAGIC: u32 = 0xa1b2_c3d4;
+
+/// Account type tag of a price account (mapping is 1, product 2)
+pub const PYTH_PRICE_ACCOUNT_TYPE: u32 = 3;
+
+/// Bytes up to the end of the aggregate price, the part that gets decoded
+///
+/// The per-publisher components that follow are not.
+pub const PYTH_PRICE_HEADER_LEN: usize = 240;
+
+/// Offset of the aggregate `PriceInfo` (price, conf, status, corp_act, pub_slot)
+const AGGREGATE_OFFSET: usize = 208;
+
+/// Decode the header and aggregate price of a Pyth price account
+///
+/// `price` and `confidence` are decimal strings with the account's exponent
+/// already applied (see [`format_fixed_point`]); `exponent` is included for
+/// consumers that want the raw integers back.
+///
+/// ```
+/// use solana_rpcx_sdk::{parse_pyth_price, PYTH_PRICE_HEADER_LEN};
+///
+/// let mut data = vec![0u8; PYTH_PRICE_HEADER_LEN];
+/// data[0..4].copy_from_slice(&0xa1b2c3d4u32.to_le_bytes());
+/// data[4..8].copy_from_slice(&2u32.to_le_bytes());
+/// data[8..12].copy_from_slice(&3u32.to_le_bytes());
+/// data[20..24].copy_from_slice(&(-8i32).to_le_bytes());
+/// data[208..216].copy_from_slice(&15_012_345_678i64.to_le_bytes());
+/// data[216..224].copy_from_slice(&7_500_000u64.to_le_bytes());
+/// data[224..228].copy_from_slice(&1u32.to_le_bytes());
+///
+/// let price = parse_pyth_price(&data).unwrap();
+/// assert_eq!(price["price"], "150.12345678");
+/// assert_eq!(price["confidence"], "0.07500000");
+/// assert_eq!(price["status"], "trading");
+///
+/// data[0] = 0;
+/// assert!(parse_pyth_price(&data).is_err());
+/// ```
+pub fn parse_pyth_price(data: &[u8]) -> Result<Value, ParseError> {
+    if data.len() < PYTH_PRICE_HEADER_LEN {
+        return Err(ParseError::InsufficientData(format!(
+            "Pyth price account too short: need {} bytes, have {}", PYTH_PRICE_HEADER_LEN, data.len()
+        )));
+    }
+
+    let magic = read_u32(data, 0);
+    if magic != PYTH_MAGIC {
+        return Err(ParseError::InvalidData(format!(
+            "Not a Pyth account: magic {:#010x} at offset 0, expected {:#010x}", magic, PYTH_MAGIC
+        )));
+    }
+
+    let account_type = read_u32(data, 8);
+    if account_type != PYTH_PRICE_ACCOUNT_TYPE {
+        return Err(ParseError::InvalidData(format!(
+            "Pyth account type {} at offset 8 is not a price account ({})", account_type, PYTH_PRICE_ACCOUNT_TYPE
+        )));
+    }
+
+    let exponent = read_u32(data, 20) as i32;
+    let aggregate = &data[AGGREGATE_OFFSET..PYTH_PRICE_HEADER_LEN];
+
+    Ok(json!({
+        "version": read_u32(data, 4),
+        "price_type": price_type(read_u32(data, 16)),
+        "exponent": exponent,
+        "num_components": read_u32(data, 24),
+        "last_slot": read_u64(data, 32),
+        "valid_slot": read_u64(data, 40),
+        "timestamp": read_u64(data, 96) as i64,
+        "product": bytes_to_base58(&data[112..144]),
+        "price": format_fixed_point(read_u64(aggregate, 0) as i64 as i128, exponent),
+        "confidence": format_fixed_point(read_u64(aggregate, 8) as i128, exponent),
+        "status": price_status(read_u32(aggregate, 16)),
+        "publish_slot": read_u64(aggregate, 24),
+    }))
+}
+
+fn price_type(tag: u32) -> Value {
+    match tag {
+        0 => json!("unknown"),
+        1 => json!("price"),
+        other => json!(other),
+    }
+}
+
+fn price_status(tag: u32) -> Value {
+    let name = match tag {
+        0 => "unknown",
+        1 => "trading",
+        2 => "halted",
+        3 => "auction",
+        4 => "ignored",
+        other => return json!(other),
+    };
+    json!(name)
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    let mut buf = [0u8; 4];
+    buf.copy_from_slice(&bytes[offset..offset + 4]);
+    u32::from_le_bytes(buf)
+}
+
+fn read_u64(bytes: &[u8], offset: usize) -> u64 {
+    let mut buf = [0u8; 8];
+    buf.copy_from_slice(&bytes[offset..offset + 8]);
+    u64::from_le_bytes(buf)
+}
@@ -0,0 +1,190 @@
+//! Registry of parsers keyed by program id
+//!
+//! Lets a single host hold parsers for many programs and route each account
+//! to the right one by its owner.
+
+use std::collections::HashMap;
+use crate::bindings::component::solana_rpcx_bindings::types::*;
+use serde_json::Value;
+use crate::events::{program_data_logs, LogEvent};
+use crate::parser::Parser;
+use crate::utils::known_program_name;
+use crate::transaction::{named_transaction_records, DecodedTransaction, InnerInstructions, LoadedAddresses};
+
+/// Maps program id to the `Parser` for that program
+#[derive(Default)]
+pub struct ParserRegistry {
+    parsers: HashMap<String, Parser>,
+    program_names: HashMap<String, String>,
+}
+
+impl ParserRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Register a parser under its own program id
+    /// 
+    /// Returns the parser previously registered for that program, if any.
+    pub fn register(&mut self, parser: Parser) -> Option<Parser> {
+        self.parsers.insert(parser.program_id().to_string(), parser)
+    }
+    
+    /// Get the parser for a program
+    pub fn get(&self, program_id: &str) -> Option<&Parser> {
+        self.parsers.get(program_id)
+    }
+    
+    /// Parse an account with the parser registered for its owner
+    pub fn parse(&self, account: &SolanaAccount) -> Result<ParsedAccount, ParseError> {
+        self.get(&account.owner)
+            .ok_or_else(|| ParseError::UnknownAccountType(
+                format!("No parser registered for program {}", account.owner)
+            ))?
+            .parse_account(account)
+    }
+    
+    /// Parse an instruction with the parser registered for its program
+    pub fn parse_instruction(&self, instruction: &InstructionData) -> Result<ParsedInstruction, ParseError> {
+        self.get(&instruction.program_id)
+            .ok_or_else(|| ParseError::UnknownAccountType(
+                format!("No parser registered for program {}", instruction.program_id)
+            ))?
+            .parse_instruction(instruction)
+    }
+    
+    /// Parse every instruction of a decoded transaction with the parser for
+    /// its program
+    /// 
+    /// Returns a JSON array with one record per instruction (see
+    /// [`instruction_records`](crate::instruction_records)); instructions for
+    /// unregistered programs are kept as raw hex. `program_name` comes from
+    /// [`program_name`](Self::program_name).
+    pub fn parse_transaction(
+        &self,
+        transaction: &DecodedTransaction,
+        loaded: &LoadedAddresses,
+    ) -> Result<Value, ParseError> {
+        self.parse_transaction_with_inner(transaction, &[], loaded)
+    }
+    
+    /// [`parse_transaction`](Self::parse_transaction), also parsing CPIs
+    /// from the transaction's status meta, each matched to the parser for
+    /// its own program
+    /// 
+    /// Inner records follow their parent and carry its index as
+    /// `parent_index` (see [`transaction_records`](crate::transaction_records)).
+    pub fn parse_transaction_with_inner(
+        &self,
+        transaction: &DecodedTransaction,
+        inner: &[InnerInstructions],
+        loaded: &LoadedAddresses,
+    ) -> Result<Value, ParseError> {
+        let instructions = transaction.resolve_instructions(loaded)?;
+        let inner = transaction.resolve_inner_instructions(inner, loaded)?;
+        Ok(named_transaction_records(&instructions, &inner, |program_id| self.program_name(program_id), |instruction| {
+            self.get(&instruction.program_id).map(|parser| parser.parse_instruction(instruction))
+        }))
+    }
+    
+    /// Parse the events every registered program logged in a transaction's
+    /// `logMessages`
+    /// 
+    /// Each `Program data:` line goes to the parser for the program that was
+    /// executing when it was written; lines from other programs are skipped.
+    pub fn parse_event_logs<S: AsRef<str>>(&self, logs: &[S]) -> Vec<LogEvent> {
+        program_data_logs(logs)
+            .into_iter()
+            .filter_map(|log| {
+                let parser = self.get(log.program_id.as_deref()?)?;
+                Some(LogEvent {
+                    result: log.data.and_then(|data| parser.parse_event(&data)),
+                    log_index: log.log_index,
+                    program_id: log.program_id,
+                })
+            })
+            .collect()
+    }
+    
+    /// Name a program for [`program_name`](Self::program_name)
+    /// 
+    /// The program doesn't need a registered parser. Returns the name it
+    /// replaced, if any.
+    pub fn set_program_name(&mut self, program_id: impl Into<String>, name: impl Into<String>) -> Option<String> {
+        self.program_names.insert(program_id.into(), name.into())
+    }
+    
+    /// Human-readable name of a program, for labelling output
+    /// 
+    /// A name set with [`set_program_name`](Self::set_program_name) takes
+    /// precedence over the built-in [`known_program_name`] table.
+    /// 
+    /// ```
+    /// use solana_rpcx_sdk::ParserRegistry;
+    /// 
+    /// let mut registry = ParserRegistry::new();
+    /// let whirlpools = "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc";
+    /// assert_eq!(registry.program_name(whirlpools), None);
+    /// 
+    /// registry.set_program_name(whirlpools, "Orca Whirlpools");
+    /// assert_eq!(registry.program_name(whirlpools), Some("Orca Whirlpools"));
+    /// assert_eq!(registry.program_name("11111111111111111111111111111111"), Some("System Program"));
+    /// ```
+    pub fn program_name(&self, program_id: &str) -> Option<&str> {
+        self.program_names
+            .get(program_id)
+            .map(String::as_str)
+            .or_else(|| known_program_name(program_id))
+    }
+    
+    /// Program ids with a registered parser
+    pub fn program_ids(&self) -> impl Iterator<Item = &str> {
+        self.parsers.keys().map(String::as_str)
+    }
+    
+    /// Number of registered parsers
+    pub fn len(&self) -> usize {
+        self.parsers.len()
+    }
+    
+    /// Whether no parsers are registered
+    pub fn is_empty(&self) -> bool {
+        self.parsers.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    const SYSTEM_PROGRAM: &str = "11111111111111111111111111111111";
+    const CUSTOM_PROGRAM: &str = "Prog1111111111111111111111111111111111111111";
+    
+    #[test]
+    fn set_names_take_precedence_over_built_in_ones() {
+        let mut registry = ParserRegistry::new();
+        assert_eq!(registry.program_name(SYSTEM_PROGRAM), Some("System Program"));
+        
+        assert_eq!(registry.set_program_name(SYSTEM_PROGRAM, "System"), None);
+        assert_eq!(registry.program_name(SYSTEM_PROGRAM), Some("System"));
+    }
+    
+    #[test]
+    fn set_program_name_returns_the_replaced_name() {
+        let mut registry = ParserRegistry::new();
+        registry.set_program_name(CUSTOM_PROGRAM, "v1");
+        
+        assert_eq!(registry.set_program_name(CUSTOM_PROGRAM, "v2"), Some("v1".to_string()));
+        assert_eq!(registry.program_name(CUSTOM_PROGRAM), Some("v2"));
+    }
+    
+    #[test]
+    fn names_are_per_registry() {
+        let mut named = ParserRegistry::new();
+        named.set_program_name(CUSTOM_PROGRAM, "Mine");
+        
+        assert_eq!(named.program_name(CUSTOM_PROGRAM), Some("Mine"));
+        assert_eq!(ParserRegistry::new().program_name(CUSTOM_PROGRAM), None);
+    }
+}
@@ -0,0 +1,298 @@
+//! Decoding accounts from JSON-RPC responses
+//!
+//! Turns raw `getAccountInfo` / `getMultipleAccounts` responses into
+//! `SolanaAccount`s ready to hand to a [`Parser`](crate::Parser).
+
+use base64::{engine::general_purpose, Engine as _};
+use serde_json::Value;
+use crate::bindings::component::solana_rpcx_bindings::types::*;
+use crate::error::SdkError;
+
+/// Decode the accounts in a `getAccountInfo` or `getMultipleAccounts` response
+/// 
+/// RPC responses don't echo the addresses back, so `pubkeys` must list them
+/// in request order. Accounts that don't exist come back as `None`.
+/// Supports the `base64` and `base58` encodings, plus the legacy bare
+/// base58 string.
+/// 
+/// ```
+/// use solana_rpcx_sdk::accounts_from_rpc_response;
+/// 
+/// let response = r#"{
+///     "jsonrpc": "2.0",
+///     "result": {
+///         "context": { "apiVersion": "2.2.3", "slot": 341197053 },
+///         "value": [
+///             {
+///                 "data": ["AQID", "base64"],
+///                 "executable": false,
+///                 "lamports": 1461600,
+///                 "owner": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+///                 "rentEpoch": 18446744073709551615,
+///                 "space": 3
+///             },
+///             null,
+///             {
+///                 "data": ["5T", "base58"],
+///                 "executable": false,
+///                 "lamports": 890880,
+///                 "owner": "11111111111111111111111111111111",
+///                 "rentEpoch": 18446744073709551615,
+///                 "space": 2
+///             }
+///         ]
+///     },
+///     "id": 1
+/// }"#;
+/// 
+/// let accounts = accounts_from_rpc_response(response, &["A", "B", "C"]).unwrap();
+/// assert_eq!(accounts.len(), 3);
+/// 
+/// let first = accounts[0].as_ref().unwrap();
+/// assert_eq!(first.pubkey, "A");
+/// assert_eq!(first.data, vec![1, 2, 3]);
+/// assert_eq!(first.rent_epoch, u64::MAX);
+/// 
+/// assert!(accounts[1].is_none());
+/// assert_eq!(accounts[2].as_ref().unwrap().data, vec![1, 2]);
+/// ```
+pub fn accounts_from_rpc_response<S: AsRef<str>>(
+    response: &str,
+    pubkeys: &[S],
+) -> Result<Vec<Option<SolanaAccount>>, SdkError> {
+    let json: Value = serde_json::from_str(response)
+        .map_err(|e| SdkError::InvalidData(format!("Invalid RPC response: {}", e)))?;
+    
+    if let Some(error) = json.get("error") {
+        return Err(SdkError::InvalidData(format!("RPC error: {}", error)));
+    }
+    
+    let value = json
+        .get("result")
+        .and_then(|r| r.get("value"))
+        .ok_or_else(|| SdkError::InvalidData("Missing result.value in RPC response".to_string()))?;
+    
+    let values: Vec<&Value> = match value {
+        Value::Array(items) => items.iter().collect(),
+        single => vec![single],
+    };
+    
+    if values.len() != pubkeys.len() {
+        return Err(SdkError::InvalidData(format!(
+            "RPC response has {} accounts, expected {}", values.len(), pubkeys.len()
+        )));
+    }
+    
+    values
+        .into_iter()
+        .zip(pubkeys)
+        .map(|(value, pubkey)| account_from_rpc_value(value, pubkey.as_ref()))
+        .collect()
+}
+
+/// Decode the account in a `getAccountInfo` response
+/// 
+/// Returns `None` if the account doesn't exist.
+/// 
+/// ```
+/// use solana_rpcx_sdk::account_from_rpc_response;
+/// 
+/// let response = r#"{"jsonrpc":"2.0","result":{"context":{"slot":1},"value":null},"id":1}"#;
+/// assert!(account_from_rpc_response(response, "A").unwrap().is_none());
+/// 
+/// let response = r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Invalid param"},"id":1}"#;
+/// assert!(account_from_rpc_response(response, "A").is_err());
+/// ```
+pub fn account_from_rpc_response(response: &str, pubkey: &str) -> Result<Option<SolanaAccount>, SdkError> {
+    let mut accounts = accounts_from_rpc_response(response, &[pubkey])?;
+    Ok(accounts.pop().flatten())
+}
+
+/// Decode one account object from an RPC response
+/// 
+/// `value` is an entry of `result.value`, or the `account` of a
+/// `getProgramAccounts` entry; `null` gives `None`. Supports the same
+/// encodings as [`accounts_from_rpc_response`].
+pub fn account_from_rpc_value(value: &Value, pubkey: &str) -> Result<Option<SolanaAccount>, SdkError> {
+    if value.is_null() {
+        return Ok(None);
+    }
+    
+    let missing = |field: &str| SdkError::InvalidData(format!("Account {}: missing {}", pubkey, field));
+    
+    let lamports = value["lamports"].as_u64().ok_or_else(|| missing("lamports"))?;
+    let owner = value["owner"].as_str().ok_or_else(|| missing("owner"))?.to_string();
+    let executable = value["executable"].as_bool().unwrap_or(false);
+    let rent_epoch = value["rentEpoch"].as_u64().unwrap_or(0);
+    
+    let data = decode_data(&value["data"])
+        .map_err(|e| SdkError::InvalidData(format!("Account {}: {}", pubkey, e)))?;
+    
+    Ok(Some(SolanaAccount {
+        pubkey: pubkey.to_string(),
+        lamports,
+        owner,
+        executable,
+        rent_epoch,
+        data,
+    }))
+}
+
+fn decode_data(data: &Value) -> Result<Vec<u8>, String> {
+    match data {
+        // Legacy "binary" encoding: a bare base58 string
+        Value::String(encoded) => crate::base58_to_bytes(encoded),
+        Value::Array(parts) => {
+            let encoded = parts.first()
+                .and_then(Value::as_str)
+                .ok_or_else(|| "missing encoded data".to_string())?;
+            
+            match parts.get(1).and_then(Value::as_str) {
+                Some("base64") => general_purpose::STANDARD
+                    .decode(encoded)
+                    .map_err(|e| format!("Invalid base64: {}", e)),
+                Some("base58") => crate::base58_to_bytes(encoded),
+                Some(other) => Err(format!("Unsupported encoding {}; request base64", other)),
+                None => Err("missing data encoding".to_string()),
+            }
+        }
+        Value::Object(_) => Err("jsonParsed data is not supported; request base64".to_string()),
+        _ => Err("missing data".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    /// `getAccountInfo` for a token mint, base64 encoded
+    const MINT_RESPONSE: &str = r#"{
+        "jsonrpc": "2.0",
+        "result": {
+            "context": { "apiVersion": "2.2.3", "slot": 341197053 },
+            "value": {
+                "data": [
+                    "AQAAAJj+huiNm+Lqi8HMpIeLKYjCQPUrhCS/tA7Rot3LXhmbAAA0JvVrHAAGAQEAAABicKqKWcWUBbRShshncubNEm6bil06OFNtN/e0FOi2Zw==",
+                    "base64"
+                ],
+                "executable": false,
+                "lamports": 388127047454,
+                "owner": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+                "rentEpoch": 18446744073709551615,
+                "space": 82
+            }
+        },
+        "id": 1
+    }"#;
+    
+    /// `getMultipleAccounts` for a wallet, a missing account and a mint
+    const MULTIPLE_RESPONSE: &str = r#"{
+        "jsonrpc": "2.0",
+        "result": {
+            "context": { "apiVersion": "2.2.3", "slot": 341197060 },
+            "value": [
+                {
+                    "data": ["", "base64"],
+                    "executable": false,
+                    "lamports": 1000000000,
+                    "owner": "11111111111111111111111111111111",
+                    "rentEpoch": 18446744073709551615,
+                    "space": 0
+                },
+                null,
+                {
+                    "data": ["5T", "base58"],
+                    "executable": false,
+                    "lamports": 890880,
+                    "owner": "11111111111111111111111111111111",
+                    "rentEpoch": 361,
+                    "space": 2
+                }
+            ]
+        },
+        "id": 2
+    }"#;
+    
+    fn invalid_data(error: SdkError) -> String {
+        match error {
+            SdkError::InvalidData(msg) => msg,
+            other => panic!("expected InvalidData, got {:?}", other),
+        }
+    }
+    
+    #[test]
+    fn get_account_info_fixture_decodes() {
+        let mint = account_from_rpc_response(MINT_RESPONSE, "mint").unwrap().unwrap();
+        
+        assert_eq!(mint.pubkey, "mint");
+        assert_eq!(mint.owner, "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+        assert_eq!(mint.lamports, 388_127_047_454);
+        assert_eq!(mint.rent_epoch, u64::MAX);
+        assert!(!mint.executable);
+        assert_eq!(mint.data.len(), 82);
+        // Decimals, then is_initialized
+        assert_eq!(mint.data[44..46], [6, 1]);
+    }
+    
+    #[test]
+    fn get_multiple_accounts_fixture_keeps_request_order() {
+        let accounts = accounts_from_rpc_response(MULTIPLE_RESPONSE, &["wallet", "missing", "small"]).unwrap();
+        
+        let wallet = accounts[0].as_ref().unwrap();
+        assert_eq!(wallet.pubkey, "wallet");
+        assert!(wallet.data.is_empty());
+        assert!(accounts[1].is_none());
+        let small = accounts[2].as_ref().unwrap();
+        assert_eq!((small.pubkey.as_str(), small.rent_epoch), ("small", 361));
+        assert_eq!(small.data, vec![1, 2]);
+    }
+    
+    #[test]
+    fn pubkey_count_must_match_the_response() {
+        let error = accounts_from_rpc_response(MULTIPLE_RESPONSE, &["wallet", "missing"]).unwrap_err();
+        assert_eq!(invalid_data(error), "RPC response has 3 accounts, expected 2");
+        
+        let error = accounts_from_rpc_response(MINT_RESPONSE, &["mint", "extra"]).unwrap_err();
+        assert_eq!(invalid_data(error), "RPC response has 1 accounts, expected 2");
+    }
+    
+    #[test]
+    fn json_parsed_data_is_rejected() {
+        let mut response: Value = serde_json::from_str(MINT_RESPONSE).unwrap();
+        response["result"]["value"]["data"] = serde_json::json!({
+            "parsed": { "info": { "decimals": 6 }, "type": "mint" },
+            "program": "spl-token",
+            "space": 82
+        });
+        
+        let error = account_from_rpc_response(&response.to_string(), "mint").unwrap_err();
+        assert_eq!(invalid_data(error), "Account mint: jsonParsed data is not supported; request base64");
+    }
+    
+    #[test]
+    fn unsupported_encoding_is_rejected() {
+        let response = MINT_RESPONSE.replace(r#""base64""#, r#""base64+zstd""#);
+        
+        let error = account_from_rpc_response(&response, "mint").unwrap_err();
+        assert_eq!(invalid_data(error), "Account mint: Unsupported encoding base64+zstd; request base64");
+    }
+    
+    #[test]
+    fn rpc_error_object_is_returned() {
+        let response = r#"{
+            "jsonrpc": "2.0",
+            "error": { "code": -32602, "message": "Invalid param: WrongSize" },
+            "id": 1
+        }"#;
+        
+        let error = invalid_data(accounts_from_rpc_response(response, &["mint"]).unwrap_err());
+        assert!(error.starts_with("RPC error: "), "{}", error);
+        assert!(error.contains("-32602") && error.contains("Invalid param: WrongSize"), "{}", error);
+    }
+    
+    #[test]
+    fn missing_result_value_is_rejected() {
+        let error = account_from_rpc_response(r#"{"jsonrpc":"2.0","result":{},"id":1}"#, "mint").unwrap_err();
+        assert_eq!(invalid_data(error), "Missing result.value in RPC response");
+    }
+}
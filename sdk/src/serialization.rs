@@ -3,4 +3,355 @@
 //! Currently mainly uses Borsh, but this module can be extended
 //! to support other formats like Bincode, MessagePack, etc.
 
-// Future: Add trait-based serialization strategies
\ No newline at end of file
+use std::fmt;
+use std::marker::PhantomData;
+use std::str::FromStr;
+use borsh::{BorshDeserialize, BorshSerialize};
+use crate::bindings::component::solana_rpcx_bindings::types::ParseError;
+
+/// How an account body is decoded to JSON
+///
+/// Pass one to [`ParserBuilder::register_account`](crate::ParserBuilder::register_account)
+/// along with a [`DiscriminatorStrategy`](crate::DiscriminatorStrategy); the
+/// format only sees the bytes after the discriminator, so error offsets are
+/// relative to the body. Closures `Fn(&[u8]) -> Result<String, ParseError>`
+/// are formats too.
+pub trait SerializationFormat: Send + Sync {
+    /// Decode `data` to a JSON string
+    fn deserialize_to_json(&self, data: &[u8]) -> Result<String, ParseError>;
+
+    /// Decode `data` to a JSON value
+    ///
+    /// Defaults to parsing [`deserialize_to_json`](Self::deserialize_to_json);
+    /// formats that build a value anyway should override it to skip the round trip.
+    fn deserialize_to_value(&self, data: &[u8]) -> Result<serde_json::Value, ParseError> {
+        let json = self.deserialize_to_json(data)?;
+        serde_json::from_str(&json)
+            .map_err(|e| ParseError::InvalidData(format!("Format returned invalid JSON: {}", e)))
+    }
+}
+
+impl<F> SerializationFormat for F
+where
+    F: Fn(&[u8]) -> Result<String, ParseError> + Send + Sync,
+{
+    fn deserialize_to_json(&self, data: &[u8]) -> Result<String, ParseError> {
+        self(data)
+    }
+}
+
+/// Borsh, decoded into `T` and re-serialized with serde
+///
+/// Trailing bytes and oversized length prefixes are rejected, as in the
+/// Borsh `register_*` methods.
+pub struct BorshFormat<T>(PhantomData<fn() -> T>);
+
+impl<T> BorshFormat<T> {
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T> Default for BorshFormat<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> SerializationFormat for BorshFormat<T>
+where
+    T: BorshDeserialize + serde::Serialize,
+{
+    fn deserialize_to_json(&self, data: &[u8]) -> Result<String, ParseError> {
+        self.deserialize_to_value(data).map(|value| value.to_string())
+    }
+
+    fn deserialize_to_value(&self, data: &[u8]) -> Result<serde_json::Value, ParseError> {
+        let value: T = crate::parser::deserialize_borsh(data, 0, short_type_name::<T>())?;
+        serde_json::to_value(&value).map_err(|e| ParseError::InvalidData(e.to_string()))
+    }
+}
+
+/// A fixed `#[repr(C)]` layout read with `bytemuck`; the body must be
+/// exactly `size_of::<T>()` bytes
+#[cfg(feature = "pinocchio")]
+pub struct PodFormat<T>(PhantomData<fn() -> T>);
+
+#[cfg(feature = "pinocchio")]
+impl<T> PodFormat<T> {
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+#[cfg(feature = "pinocchio")]
+impl<T> Default for PodFormat<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "pinocchio")]
+impl<T> SerializationFormat for PodFormat<T>
+where
+    T: bytemuck::Pod + serde::Serialize,
+{
+    fn deserialize_to_json(&self, data: &[u8]) -> Result<String, ParseError> {
+        self.deserialize_to_value(data).map(|value| value.to_string())
+    }
+
+    fn deserialize_to_value(&self, data: &[u8]) -> Result<serde_json::Value, ParseError> {
+        let value: T = bytemuck::try_pod_read_unaligned(data)
+            .map_err(|e| ParseError::DeserializationFailed(format!("{}: {}", short_type_name::<T>(), e)))?;
+        serde_json::to_value(value).map_err(|e| ParseError::InvalidData(e.to_string()))
+    }
+}
+
+/// MessagePack (`rmp-serde`), decoded into `T` and re-serialized as JSON
+///
+/// Decoding stops at the end of the first value, so trailing bytes (e.g.
+/// zero padding up to the account's allocated size) are ignored.
+#[cfg(feature = "msgpack")]
+pub struct MsgpackFormat<T>(PhantomData<fn() -> T>);
+
+#[cfg(feature = "msgpack")]
+impl<T> MsgpackFormat<T> {
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+#[cfg(feature = "msgpack")]
+impl<T> Default for MsgpackFormat<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "msgpack")]
+impl<T> SerializationFormat for MsgpackFormat<T>
+where
+    T: serde::de::DeserializeOwned + serde::Serialize,
+{
+    fn deserialize_to_json(&self, data: &[u8]) -> Result<String, ParseError> {
+        self.deserialize_to_value(data).map(|value| value.to_string())
+    }
+
+    fn deserialize_to_value(&self, data: &[u8]) -> Result<serde_json::Value, ParseError> {
+        let value: T = rmp_serde::from_slice(data)
+            .map_err(|e| ParseError::DeserializationFailed(format!("{}: {}", short_type_name::<T>(), e)))?;
+        serde_json::to_value(&value).map_err(|e| ParseError::InvalidData(e.to_string()))
+    }
+}
+
+/// Protocol Buffers (`prost`), decoded into `T` and serialized with serde
+///
+/// The whole body must be one encoded message; zero padding is not valid
+/// protobuf, so padded accounts need a custom format that trims it first.
+#[cfg(feature = "protobuf")]
+pub struct ProtobufFormat<T>(PhantomData<fn() -> T>);
+
+#[cfg(feature = "protobuf")]
+impl<T> ProtobufFormat<T> {
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+#[cfg(feature = "protobuf")]
+impl<T> Default for ProtobufFormat<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "protobuf")]
+impl<T> SerializationFormat for ProtobufFormat<T>
+where
+    T: prost::Message + Default + serde::Serialize,
+{
+    fn deserialize_to_json(&self, data: &[u8]) -> Result<String, ParseError> {
+        self.deserialize_to_value(data).map(|value| value.to_string())
+    }
+
+    fn deserialize_to_value(&self, data: &[u8]) -> Result<serde_json::Value, ParseError> {
+        let value = T::decode(data)
+            .map_err(|e| ParseError::DeserializationFailed(format!("{}: {}", short_type_name::<T>(), e)))?;
+        serde_json::to_value(&value).map_err(|e| ParseError::InvalidData(e.to_string()))
+    }
+}
+
+/// `my_crate::state::Vault` -> `Vault`, for error messages
+fn short_type_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    let name = name.split('<').next().unwrap_or(name);
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/// A 32-byte public key that renders as base58 in JSON
+///
+/// Borsh-encodes as the raw 32 bytes, so it can be used in place of
+/// `[u8; 32]` in account structs to get `"7xKX..."` instead of an array
+/// of numbers in the parsed output. With the `pinocchio` feature it is also
+/// `Pod`, for fixed-layout structs.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "pinocchio", derive(bytemuck::Pod, bytemuck::Zeroable))]
+#[repr(transparent)]
+pub struct Pubkey(pub [u8; 32]);
+
+impl Pubkey {
+    /// Create a pubkey from raw bytes
+    pub const fn new_from_array(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    /// Raw key bytes
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0
+    }
+}
+
+impl From<[u8; 32]> for Pubkey {
+    fn from(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl AsRef<[u8]> for Pubkey {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl FromStr for Pubkey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        crate::base58_to_pubkey(s).map(Self)
+    }
+}
+
+impl fmt::Display for Pubkey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&crate::bytes_to_base58(&self.0))
+    }
+}
+
+impl fmt::Debug for Pubkey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl serde::Serialize for Pubkey {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Pubkey {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <String as serde::Deserialize>::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// An integer with an implied decimal exponent that renders as a decimal string
+/// 
+/// Borsh-encodes as the inner integer (`i64` unless given), so it can
+/// replace the raw field in an account struct; JSON shows
+/// [`format_fixed_point`](crate::format_fixed_point) of it.
+/// 
+/// ```
+/// use solana_rpcx_sdk::prelude::*;
+/// use solana_rpcx_sdk::FixedPoint;
+/// 
+/// #[derive(BorshDeserialize, Serialize)]
+/// struct PriceFeed {
+///     price: FixedPoint<-8>,
+///     // USDC amount with 6 decimals
+///     liquidity: FixedPoint<-6, u64>,
+/// }
+/// 
+/// let mut data = 15_012_345_678i64.to_le_bytes().to_vec();
+/// data.extend_from_slice(&2_500_000u64.to_le_bytes());
+/// let feed = PriceFeed::try_from_slice(&data).unwrap();
+/// assert_eq!(
+///     serde_json::to_string(&feed).unwrap(),
+///     r#"{"price":"150.12345678","liquidity":"2.500000"}"#
+/// );
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default, Debug, BorshSerialize, BorshDeserialize)]
+#[repr(transparent)]
+pub struct FixedPoint<const EXP: i32, T = i64>(pub T);
+
+impl<const EXP: i32, T: Copy + Into<i128>> FixedPoint<EXP, T> {
+    /// The value as a decimal string
+    pub fn to_decimal_string(&self) -> String {
+        crate::format_fixed_point(self.0.into(), EXP)
+    }
+}
+
+impl<const EXP: i32, T: Copy + Into<i128>> fmt::Display for FixedPoint<EXP, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_decimal_string())
+    }
+}
+
+impl<const EXP: i32, T: Copy + Into<i128>> serde::Serialize for FixedPoint<EXP, T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_decimal_string())
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for Pubkey {
+    fn schema_name() -> String {
+        "Pubkey".to_string()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        <String as schemars::JsonSchema>::json_schema(gen)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_point_renders_negatives() {
+        let price = FixedPoint::<-2>(-150);
+
+        assert_eq!(price.to_string(), "-1.50");
+        assert_eq!(serde_json::to_string(&price).unwrap(), r#""-1.50""#);
+    }
+
+    #[test]
+    fn fixed_point_with_positive_exponent() {
+        assert_eq!(FixedPoint::<3, u8>(7).to_string(), "7000");
+    }
+
+    #[test]
+    fn fixed_point_at_the_exponent_limit() {
+        // 64 is the largest exponent written out in full
+        assert_eq!(FixedPoint::<-64>(1).to_string(), format!("0.{}1", "0".repeat(63)));
+        assert_eq!(FixedPoint::<-65>(1).to_string(), "1e-65");
+    }
+
+    #[test]
+    fn fixed_point_i128_min() {
+        let data = borsh::to_vec(&i128::MIN).unwrap();
+        let value = FixedPoint::<-2, i128>::try_from_slice(&data).unwrap();
+
+        assert_eq!(value.0, i128::MIN);
+        assert_eq!(value.to_string(), "-1701411834604692317316873037158841057.28");
+    }
+
+    #[test]
+    fn fixed_point_borsh_layout_is_the_inner_integer() {
+        let value = FixedPoint::<-6, u64>(2_500_000);
+
+        assert_eq!(borsh::to_vec(&value).unwrap(), 2_500_000u64.to_le_bytes());
+    }
+}
@@ -0,0 +1,234 @@
+//! Test helpers for parser authors
+//!
+//! Enabled with the `testing` feature; add it to your `[dev-dependencies]`
+//! entry so it stays out of the component build.
+
+use std::path::Path;
+use serde_json::Value;
+use crate::bindings::component::solana_rpcx_bindings::types::*;
+use crate::parser::Parser;
+
+/// Placeholder pubkey used by [`MockAccount`] until one is set
+pub const MOCK_PUBKEY: &str = "11111111111111111111111111111111";
+
+/// Builder for `SolanaAccount`s in tests
+/// 
+/// Defaults to the system program id for both pubkey and owner, empty
+/// data and zero lamports. The discriminator and body are kept separate, so
+/// setters can be called in any order.
+/// 
+/// ```
+/// use solana_rpcx_sdk::prelude::*;
+/// use solana_rpcx_sdk::testing::MockAccount;
+/// 
+/// #[derive(BorshSerialize, BorshDeserialize, Serialize)]
+/// struct Counter { count: u64 }
+/// 
+/// let program_id = "11111111111111111111111111111111";
+/// let parser = ParserBuilder::new(program_id)
+///     .register_account_with_discriminator::<Counter>(
+///         "Counter",
+///         compute_anchor_discriminator("account", "Counter").to_vec(),
+///     )
+///     .build();
+/// 
+/// let account = MockAccount::new()
+///     .owner(program_id)
+///     .borsh(&Counter { count: 9 })
+///     .with_anchor_discriminator("account", "Counter")
+///     .build();
+/// 
+/// assert_eq!(parser.parse_account(&account).unwrap().data, r#"{"count":9}"#);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MockAccount {
+    pubkey: String,
+    owner: String,
+    discriminator: Vec<u8>,
+    data: Vec<u8>,
+    lamports: u64,
+    executable: bool,
+    rent_epoch: u64,
+}
+
+impl Default for MockAccount {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockAccount {
+    /// Create a mock account with default fields
+    pub fn new() -> Self {
+        Self {
+            pubkey: MOCK_PUBKEY.to_string(),
+            owner: MOCK_PUBKEY.to_string(),
+            discriminator: Vec::new(),
+            data: Vec::new(),
+            lamports: 0,
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+    
+    /// Set the account address
+    pub fn pubkey(mut self, pubkey: impl Into<String>) -> Self {
+        self.pubkey = pubkey.into();
+        self
+    }
+    
+    /// Set the owning program
+    pub fn owner(mut self, owner: impl Into<String>) -> Self {
+        self.owner = owner.into();
+        self
+    }
+    
+    /// Set the data that follows the discriminator
+    pub fn data(mut self, data: impl Into<Vec<u8>>) -> Self {
+        self.data = data.into();
+        self
+    }
+    
+    /// Set the data that follows the discriminator to `value`, Borsh-encoded
+    pub fn borsh<T: borsh::BorshSerialize>(mut self, value: &T) -> Self {
+        self.data = borsh::to_vec(value).expect("Borsh serialization into a Vec cannot fail");
+        self
+    }
+    
+    /// Set the bytes written before the data
+    pub fn discriminator(mut self, discriminator: impl Into<Vec<u8>>) -> Self {
+        self.discriminator = discriminator.into();
+        self
+    }
+    
+    /// Prefix the data with an Anchor discriminator, e.g. `("account", "MyAccount")`
+    pub fn with_anchor_discriminator(self, namespace: &str, name: &str) -> Self {
+        self.discriminator(crate::compute_anchor_discriminator(namespace, name))
+    }
+    
+    /// Set the lamport balance
+    pub fn lamports(mut self, lamports: u64) -> Self {
+        self.lamports = lamports;
+        self
+    }
+    
+    /// Mark the account as executable
+    pub fn executable(mut self, executable: bool) -> Self {
+        self.executable = executable;
+        self
+    }
+    
+    /// Set the rent epoch
+    pub fn rent_epoch(mut self, rent_epoch: u64) -> Self {
+        self.rent_epoch = rent_epoch;
+        self
+    }
+    
+    /// Build the account, with data = discriminator followed by the body
+    pub fn build(self) -> SolanaAccount {
+        let mut data = self.discriminator;
+        data.extend_from_slice(&self.data);
+        
+        SolanaAccount {
+            pubkey: self.pubkey,
+            data,
+            owner: self.owner,
+            lamports: self.lamports,
+            executable: self.executable,
+            rent_epoch: self.rent_epoch,
+        }
+    }
+}
+
+/// An account owned by `owner` holding exactly `data`
+/// 
+/// Shorthand for `MockAccount::new().owner(owner).data(data).build()`.
+pub fn mock_account(owner: &str, data: impl Into<Vec<u8>>) -> SolanaAccount {
+    MockAccount::new().owner(owner).data(data).build()
+}
+
+impl From<MockAccount> for SolanaAccount {
+    fn from(mock: MockAccount) -> Self {
+        mock.build()
+    }
+}
+
+/// Set this environment variable to rewrite golden files instead of comparing
+pub const UPDATE_GOLDENS_ENV: &str = "RPCX_UPDATE_GOLDENS";
+
+/// Parse `account` and compare the result with a committed JSON golden file
+/// 
+/// The golden holds `account_type`, `discriminator` (hex) and `data`. Both
+/// sides are compared as JSON values, so formatting and key order in the
+/// file don't matter. Run with `RPCX_UPDATE_GOLDENS=1` to (re)write the file
+/// from the current output.
+/// 
+/// ```
+/// use solana_rpcx_sdk::prelude::*;
+/// use solana_rpcx_sdk::testing::{assert_parsed_matches, MockAccount};
+/// 
+/// #[derive(BorshDeserialize, Serialize)]
+/// struct Counter { count: u64 }
+/// 
+/// let program_id = "11111111111111111111111111111111";
+/// let parser = ParserBuilder::new(program_id)
+///     .register_borsh_account::<Counter>("Counter")
+///     .build();
+/// let account = MockAccount::new().owner(program_id).data(42u64.to_le_bytes()).build();
+/// 
+/// let golden = std::env::temp_dir().join("rpcx_counter_golden.json");
+/// std::fs::write(&golden, r#"{ "account_type": "Counter", "discriminator": null, "data": { "count": 42 } }"#).unwrap();
+/// assert_parsed_matches(&parser, &account, &golden);
+/// ```
+/// 
+/// # Panics
+/// If parsing fails, the golden file is missing or unreadable, or the
+/// output differs from it.
+#[track_caller]
+pub fn assert_parsed_matches(parser: &Parser, account: &SolanaAccount, golden_path: impl AsRef<Path>) {
+    let golden_path = golden_path.as_ref();
+    
+    let parsed = parser
+        .parse_account(account)
+        .unwrap_or_else(|e| panic!("Failed to parse account {}: {:?}", account.pubkey, e));
+    let actual = snapshot(&parsed);
+    
+    if std::env::var_os(UPDATE_GOLDENS_ENV).is_some() {
+        if let Some(dir) = golden_path.parent() {
+            std::fs::create_dir_all(dir)
+                .unwrap_or_else(|e| panic!("Failed to create {}: {}", dir.display(), e));
+        }
+        let pretty = serde_json::to_string_pretty(&actual).expect("JSON values always serialize");
+        std::fs::write(golden_path, pretty + "\n")
+            .unwrap_or_else(|e| panic!("Failed to write {}: {}", golden_path.display(), e));
+        return;
+    }
+    
+    let contents = std::fs::read_to_string(golden_path).unwrap_or_else(|e| panic!(
+        "Failed to read golden {}: {} (run with {}=1 to create it)",
+        golden_path.display(), e, UPDATE_GOLDENS_ENV
+    ));
+    let expected: Value = serde_json::from_str(&contents)
+        .unwrap_or_else(|e| panic!("Golden {} is not valid JSON: {}", golden_path.display(), e));
+    
+    if actual != expected {
+        panic!(
+            "Parsed output does not match {} (run with {}=1 to update)\n--- expected\n{}\n+++ actual\n{}",
+            golden_path.display(),
+            UPDATE_GOLDENS_ENV,
+            serde_json::to_string_pretty(&expected).expect("JSON values always serialize"),
+            serde_json::to_string_pretty(&actual).expect("JSON values always serialize"),
+        );
+    }
+}
+
+/// Normalized form of a parsed account, as stored in golden files
+fn snapshot(parsed: &ParsedAccount) -> Value {
+    let data = serde_json::from_str(&parsed.data).unwrap_or_else(|_| Value::String(parsed.data.clone()));
+    
+    serde_json::json!({
+        "account_type": parsed.account_type,
+        "discriminator": parsed.discriminator.as_deref().map(crate::bytes_to_hex),
+        "data": data,
+    })
+}
@@ -0,0 +1,296 @@
+//! Token-2022 extension parsing
+//!
+//! Token-2022 mints and accounts are the classic SPL layout, padded to 165
+//! bytes, followed by a one-byte account type and a TLV list of extensions:
+//! `u16` type, `u16` length, then `length` bytes of value.
+
+use serde_json::{json, Value};
+use crate::bindings::component::solana_rpcx_bindings::types::*;
+use crate::{bytes_to_base58, bytes_to_hex};
+
+/// Length of the base SPL token account, which mints are padded to
+pub const TOKEN_ACCOUNT_LEN: usize = 165;
+
+/// Length of an SPL multisig account, which never carries extensions
+pub const TOKEN_MULTISIG_LEN: usize = 355;
+
+/// Decode the extensions that follow a Token-2022 mint or account
+/// 
+/// Returns `{"account_type": "mint" | "account", "extensions": [...]}`.
+/// Each extension has a `type` field; known types are decoded into fields,
+/// others keep their value as `raw` hex. Data with no extension area gives
+/// an empty list and a `null` account type.
+pub fn parse_token2022_extensions(data: &[u8]) -> Result<Value, ParseError> {
+    if data.len() <= TOKEN_ACCOUNT_LEN || data.len() == TOKEN_MULTISIG_LEN {
+        return Ok(json!({ "account_type": null, "extensions": [] }));
+    }
+    
+    let account_type = match data[TOKEN_ACCOUNT_LEN] {
+        1 => "mint",
+        2 => "account",
+        other => return Err(ParseError::InvalidData(format!(
+            "Unknown Token-2022 account type {} at offset {}", other, TOKEN_ACCOUNT_LEN
+        ))),
+    };
+    
+    let mut extensions = Vec::new();
+    let mut offset = TOKEN_ACCOUNT_LEN + 1;
+    
+    while offset < data.len() {
+        if data.len() - offset < 4 {
+            return Err(ParseError::InsufficientData(format!(
+                "Truncated extension header at offset {}: need 4 bytes, have {}", offset, data.len() - offset
+            )));
+        }
+        
+        let extension_type = u16::from_le_bytes([data[offset], data[offset + 1]]);
+        let length = u16::from_le_bytes([data[offset + 2], data[offset + 3]]) as usize;
+        
+        // Uninitialized marks the start of trailing padding
+        if extension_type == 0 {
+            break;
+        }
+        
+        let start = offset + 4;
+        let value = data.get(start..start + length).ok_or_else(|| ParseError::InsufficientData(format!(
+            "Extension {} at offset {} needs {} bytes, have {}", extension_type, start, length, data.len() - start
+        )))?;
+        
+        extensions.push(decode_extension(extension_type, value, start)?);
+        offset = start + length;
+    }
+    
+    Ok(json!({ "account_type": account_type, "extensions": extensions }))
+}
+
+fn decode_extension(extension_type: u16, value: &[u8], offset: usize) -> Result<Value, ParseError> {
+    let name = extension_name(extension_type);
+    
+    match extension_type {
+        // TransferFeeConfig
+        1 => {
+            expect_len(&name, value, 108, offset)?;
+            Ok(json!({
+                "type": name,
+                "transfer_fee_config_authority": optional_pubkey(&value[0..32]),
+                "withdraw_withheld_authority": optional_pubkey(&value[32..64]),
+                "withheld_amount": read_u64(value, 64),
+                "older_transfer_fee": transfer_fee(&value[72..90]),
+                "newer_transfer_fee": transfer_fee(&value[90..108]),
+            }))
+        }
+        // MetadataPointer
+        18 => {
+            expect_len(&name, value, 64, offset)?;
+            Ok(json!({
+                "type": name,
+                "authority": optional_pubkey(&value[0..32]),
+                "metadata_address": optional_pubkey(&value[32..64]),
+            }))
+        }
+        _ => Ok(json!({
+            "type": name,
+            "raw": bytes_to_hex(value),
+        })),
+    }
+}
+
+fn extension_name(extension_type: u16) -> Value {
+    let name = match extension_type {
+        1 => "transferFeeConfig",
+        2 => "transferFeeAmount",
+        3 => "mintCloseAuthority",
+        4 => "confidentialTransferMint",
+        5 => "confidentialTransferAccount",
+        6 => "defaultAccountState",
+        7 => "immutableOwner",
+        8 => "memoTransfer",
+        9 => "nonTransferable",
+        10 => "interestBearingConfig",
+        11 => "cpiGuard",
+        12 => "permanentDelegate",
+        13 => "nonTransferableAccount",
+        14 => "transferHook",
+        15 => "transferHookAccount",
+        16 => "confidentialTransferFeeConfig",
+        17 => "confidentialTransferFeeAmount",
+        18 => "metadataPointer",
+        19 => "tokenMetadata",
+        20 => "groupPointer",
+        21 => "tokenGroup",
+        22 => "groupMemberPointer",
+        23 => "tokenGroupMember",
+        // Unknown types are reported by number
+        other => return json!(other),
+    };
+    json!(name)
+}
+
+fn expect_len(name: &Value, value: &[u8], len: usize, offset: usize) -> Result<(), ParseError> {
+    if value.len() != len {
+        return Err(ParseError::InvalidData(format!(
+            "Extension {} at offset {}: expected {} bytes, got {}", name, offset, len, value.len()
+        )));
+    }
+    Ok(())
+}
+
+/// `OptionalNonZeroPubkey`: all zeros means none
+fn optional_pubkey(bytes: &[u8]) -> Value {
+    if bytes.iter().all(|b| *b == 0) {
+        Value::Null
+    } else {
+        json!(bytes_to_base58(bytes))
+    }
+}
+
+fn transfer_fee(bytes: &[u8]) -> Value {
+    json!({
+        "epoch": read_u64(bytes, 0),
+        "maximum_fee": read_u64(bytes, 8),
+        "transfer_fee_basis_points": u16::from_le_bytes([bytes[16], bytes[17]]),
+    })
+}
+
+fn read_u64(bytes: &[u8], offset: usize) -> u64 {
+    let mut buf = [0u8; 8];
+    buf.copy_from_slice(&bytes[offset..offset + 8]);
+    u64::from_le_bytes(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    /// A base account of `account_type`, then each `(type, value)` as a TLV entry
+    fn with_extensions(account_type: u8, extensions: &[(u16, Vec<u8>)]) -> Vec<u8> {
+        let mut data = vec![0; TOKEN_ACCOUNT_LEN];
+        data.push(account_type);
+        for (extension_type, value) in extensions {
+            data.extend_from_slice(&extension_type.to_le_bytes());
+            data.extend_from_slice(&(value.len() as u16).to_le_bytes());
+            data.extend_from_slice(value);
+        }
+        data
+    }
+    
+    fn fee(epoch: u64, maximum_fee: u64, basis_points: u16) -> Vec<u8> {
+        let mut bytes = epoch.to_le_bytes().to_vec();
+        bytes.extend_from_slice(&maximum_fee.to_le_bytes());
+        bytes.extend_from_slice(&basis_points.to_le_bytes());
+        bytes
+    }
+    
+    #[test]
+    fn transfer_fee_config_is_decoded() {
+        let mut value = vec![1; 32];
+        value.extend_from_slice(&[0; 32]);
+        value.extend_from_slice(&500u64.to_le_bytes());
+        value.extend(fee(10, 1_000, 25));
+        value.extend(fee(11, 2_000, 50));
+        assert_eq!(value.len(), 108);
+        
+        let parsed = parse_token2022_extensions(&with_extensions(1, &[(1, value)])).unwrap();
+        assert_eq!(parsed, json!({
+            "account_type": "mint",
+            "extensions": [{
+                "type": "transferFeeConfig",
+                "transfer_fee_config_authority": bytes_to_base58(&[1; 32]),
+                "withdraw_withheld_authority": null,
+                "withheld_amount": 500,
+                "older_transfer_fee": { "epoch": 10, "maximum_fee": 1_000, "transfer_fee_basis_points": 25 },
+                "newer_transfer_fee": { "epoch": 11, "maximum_fee": 2_000, "transfer_fee_basis_points": 50 },
+            }],
+        }));
+    }
+    
+    #[test]
+    fn metadata_pointer_without_authority() {
+        let mut value = vec![0; 32];
+        value.extend_from_slice(&[2; 32]);
+        
+        let parsed = parse_token2022_extensions(&with_extensions(1, &[(18, value)])).unwrap();
+        assert_eq!(parsed["extensions"][0], json!({
+            "type": "metadataPointer",
+            "authority": null,
+            "metadata_address": bytes_to_base58(&[2; 32]),
+        }));
+    }
+    
+    #[test]
+    fn undecoded_extensions_keep_their_value_as_hex() {
+        let data = with_extensions(2, &[(7, vec![]), (999, vec![0xde, 0xad])]);
+        
+        let parsed = parse_token2022_extensions(&data).unwrap();
+        assert_eq!(parsed, json!({
+            "account_type": "account",
+            "extensions": [
+                { "type": "immutableOwner", "raw": "" },
+                { "type": 999, "raw": "dead" },
+            ],
+        }));
+    }
+    
+    #[test]
+    fn zero_padding_ends_the_extension_list() {
+        let mut data = with_extensions(2, &[(7, vec![])]);
+        data.extend_from_slice(&[0; 12]);
+        
+        let parsed = parse_token2022_extensions(&data).unwrap();
+        assert_eq!(parsed["extensions"].as_array().unwrap().len(), 1);
+    }
+    
+    #[test]
+    fn accounts_without_an_extension_area() {
+        let none = json!({ "account_type": null, "extensions": [] });
+        
+        // Classic mint, classic token account, multisig
+        for len in [82, TOKEN_ACCOUNT_LEN, TOKEN_MULTISIG_LEN] {
+            assert_eq!(parse_token2022_extensions(&vec![7; len]).unwrap(), none, "{} bytes", len);
+        }
+    }
+    
+    #[test]
+    fn unknown_account_type_is_rejected() {
+        let error = parse_token2022_extensions(&with_extensions(3, &[])).unwrap_err();
+        assert!(
+            matches!(&error, ParseError::InvalidData(msg) if msg == "Unknown Token-2022 account type 3 at offset 165"),
+            "{:?}", error
+        );
+    }
+    
+    #[test]
+    fn truncated_header_is_insufficient_data() {
+        let mut data = with_extensions(1, &[]);
+        data.extend_from_slice(&[18, 0]);
+        
+        let error = parse_token2022_extensions(&data).unwrap_err();
+        assert!(
+            matches!(&error, ParseError::InsufficientData(msg) if msg == "Truncated extension header at offset 166: need 4 bytes, have 2"),
+            "{:?}", error
+        );
+    }
+    
+    #[test]
+    fn length_past_the_end_is_insufficient_data() {
+        let mut data = with_extensions(1, &[]);
+        data.extend_from_slice(&18u16.to_le_bytes());
+        data.extend_from_slice(&64u16.to_le_bytes());
+        data.extend_from_slice(&[2; 10]);
+        
+        let error = parse_token2022_extensions(&data).unwrap_err();
+        assert!(
+            matches!(&error, ParseError::InsufficientData(msg) if msg == "Extension 18 at offset 170 needs 64 bytes, have 10"),
+            "{:?}", error
+        );
+    }
+    
+    #[test]
+    fn known_extension_with_the_wrong_length_is_rejected() {
+        let error = parse_token2022_extensions(&with_extensions(1, &[(1, vec![0; 100])])).unwrap_err();
+        assert!(
+            matches!(&error, ParseError::InvalidData(msg) if msg == r#"Extension "transferFeeConfig" at offset 170: expected 108 bytes, got 100"#),
+            "{:?}", error
+        );
+    }
+}
@@ -0,0 +1,650 @@
+//! Transaction decoding
+//!
+//! Decodes a serialized transaction (legacy or v0, as returned by
+//! `getTransaction` with `base64` encoding) into its message, and resolves
+//! each compiled instruction's program id and accounts to the
+//! `InstructionData` the parsers take.
+//!
+//! A v0 message can reference accounts through address lookup tables. Those
+//! accounts aren't in the transaction itself: fetch the tables named by
+//! [`DecodedTransaction::lookup_table_keys`], decode them with
+//! [`decode_lookup_table`] and pass them to
+//! [`DecodedTransaction::load_addresses`].
+//!
+//! Instructions invoked through CPI aren't in the message either; they come
+//! from the transaction's status meta (`meta.innerInstructions` of a
+//! `getTransaction` response), which also lists the addresses the lookup
+//! tables resolved to. See [`inner_instructions_from_meta`] and
+//! [`loaded_addresses_from_meta`].
+
+use std::collections::HashMap;
+use base64::{engine::general_purpose, Engine as _};
+use serde_json::{json, Value};
+use crate::bindings::component::solana_rpcx_bindings::types::*;
+use crate::{base58_to_bytes, bytes_to_base58, bytes_to_hex, known_program_name};
+
+/// Owner of address lookup table accounts
+pub const ADDRESS_LOOKUP_TABLE_PROGRAM_ID: &str = "AddressLookupTab1e1111111111111111111111111";
+
+/// Bytes of lookup table metadata before the address list
+pub const LOOKUP_TABLE_META_SIZE: usize = 56;
+
+/// The three counts at the start of a message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MessageHeader {
+    pub num_required_signatures: u8,
+    pub num_readonly_signed_accounts: u8,
+    pub num_readonly_unsigned_accounts: u8,
+}
+
+/// An instruction as stored in a message, referring to accounts by index
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompiledInstruction {
+    pub program_id_index: u8,
+    pub accounts: Vec<u8>,
+    pub data: Vec<u8>,
+}
+
+/// Accounts a v0 message loads from one lookup table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddressTableLookup {
+    /// The lookup table account
+    pub account_key: String,
+    pub writable_indexes: Vec<u8>,
+    pub readonly_indexes: Vec<u8>,
+}
+
+/// Addresses loaded from lookup tables, in message order
+///
+/// Account indexes past the static keys refer to `writable` and then
+/// `readonly`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LoadedAddresses {
+    pub writable: Vec<String>,
+    pub readonly: Vec<String>,
+}
+
+impl LoadedAddresses {
+    /// Whether no addresses were loaded
+    pub fn is_empty(&self) -> bool {
+        self.writable.is_empty() && self.readonly.is_empty()
+    }
+}
+
+/// Instructions a top-level instruction invoked through CPI
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InnerInstructions {
+    /// Position of the invoking instruction in the message
+    pub index: u8,
+    /// Invoked instructions, in execution order, indexing the same accounts
+    /// as the message
+    pub instructions: Vec<CompiledInstruction>,
+}
+
+/// A transaction's signatures and message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedTransaction {
+    pub signatures: Vec<String>,
+    /// `None` for legacy messages, otherwise the version (only 0 exists)
+    pub version: Option<u8>,
+    pub header: MessageHeader,
+    /// Accounts stored in the message itself
+    pub account_keys: Vec<String>,
+    pub recent_blockhash: String,
+    pub instructions: Vec<CompiledInstruction>,
+    /// Always empty for legacy messages
+    pub address_table_lookups: Vec<AddressTableLookup>,
+}
+
+/// Decode a serialized transaction
+///
+/// ```
+/// use solana_rpcx_sdk::decode_transaction;
+///
+/// let mut tx = vec![1];                  // one signature
+/// tx.extend([7u8; 64]);
+/// tx.extend([1, 0, 1]);                  // header
+/// tx.push(2);                            // two account keys
+/// tx.extend([1u8; 32]);
+/// tx.extend([0u8; 32]);                  // the system program
+/// tx.extend([9u8; 32]);                  // recent blockhash
+/// tx.extend([1, 1, 1, 0, 2, 0xaa, 0xbb]); // one instruction
+///
+/// let decoded = decode_transaction(&tx).unwrap();
+/// assert_eq!(decoded.version, None);
+/// assert_eq!(decoded.instructions[0].data, [0xaa, 0xbb]);
+///
+/// let instructions = decoded.resolve_instructions(&Default::default()).unwrap();
+/// assert_eq!(instructions[0].program_id, "11111111111111111111111111111111");
+/// assert_eq!(instructions[0].accounts, [decoded.account_keys[0].clone()]);
+/// ```
+pub fn decode_transaction(bytes: &[u8]) -> Result<DecodedTransaction, ParseError> {
+    let mut reader = Reader { bytes, offset: 0 };
+
+    let signature_count = reader.short_vec_len()?;
+    let signatures = (0..signature_count)
+        .map(|_| reader.take(64).map(bytes_to_base58))
+        .collect::<Result<_, _>>()?;
+
+    let version = match reader.peek()? {
+        prefix if prefix & 0x80 != 0 => {
+            reader.offset += 1;
+            let version = prefix & 0x7f;
+            if version != 0 {
+                return Err(ParseError::InvalidData(format!("Unsupported message version {}", version)));
+            }
+            Some(version)
+        }
+        _ => None,
+    };
+
+    let header = MessageHeader {
+        num_required_signatures: reader.u8()?,
+        num_readonly_signed_accounts: reader.u8()?,
+        num_readonly_unsigned_accounts: reader.u8()?,
+    };
+
+    let key_count = reader.short_vec_len()?;
+    let account_keys = (0..key_count)
+        .map(|_| reader.pubkey())
+        .collect::<Result<_, _>>()?;
+    let recent_blockhash = reader.pubkey()?;
+
+    let instruction_count = reader.short_vec_len()?;
+    let instructions = (0..instruction_count)
+        .map(|_| {
+            Ok(CompiledInstruction {
+                program_id_index: reader.u8()?,
+                accounts: reader.short_vec_bytes()?,
+                data: reader.short_vec_bytes()?,
+            })
+        })
+        .collect::<Result<_, ParseError>>()?;
+
+    let address_table_lookups = if version.is_some() {
+        let lookup_count = reader.short_vec_len()?;
+        (0..lookup_count)
+            .map(|_| {
+                Ok(AddressTableLookup {
+                    account_key: reader.pubkey()?,
+                    writable_indexes: reader.short_vec_bytes()?,
+                    readonly_indexes: reader.short_vec_bytes()?,
+                })
+            })
+            .collect::<Result<_, ParseError>>()?
+    } else {
+        Vec::new()
+    };
+
+    if reader.offset != bytes.len() {
+        return Err(ParseError::InvalidData(format!(
+            "{} trailing bytes after the message", bytes.len() - reader.offset
+        )));
+    }
+
+    Ok(DecodedTransaction {
+        signatures,
+        version,
+        header,
+        account_keys,
+        recent_blockhash,
+        instructions,
+        address_table_lookups,
+    })
+}
+
+/// Decode a base64 serialized transaction, surrounding whitespace allowed
+pub fn decode_transaction_base64(encoded: &str) -> Result<DecodedTransaction, ParseError> {
+    let bytes = general_purpose::STANDARD.decode(encoded.trim())
+        .map_err(|e| ParseError::InvalidData(format!("Invalid base64 transaction: {}", e)))?;
+    decode_transaction(&bytes)
+}
+
+/// Addresses stored in an address lookup table account
+///
+/// ```
+/// use solana_rpcx_sdk::{decode_lookup_table, LOOKUP_TABLE_META_SIZE};
+///
+/// let mut data = vec![0u8; LOOKUP_TABLE_META_SIZE];
+/// data[0] = 1; // lookup table state
+/// data.extend([0u8; 32]);
+/// assert_eq!(decode_lookup_table(&data).unwrap(), ["11111111111111111111111111111111"]);
+/// ```
+pub fn decode_lookup_table(data: &[u8]) -> Result<Vec<String>, ParseError> {
+    if data.len() < LOOKUP_TABLE_META_SIZE {
+        return Err(ParseError::InsufficientData(format!(
+            "Lookup table too short: need {} bytes, have {}", LOOKUP_TABLE_META_SIZE, data.len()
+        )));
+    }
+    // `ProgramState::LookupTable`, a u32 enum tag
+    if data[..4] != [1, 0, 0, 0] {
+        return Err(ParseError::InvalidData("Not an initialized lookup table".to_string()));
+    }
+
+    let addresses = &data[LOOKUP_TABLE_META_SIZE..];
+    if !addresses.len().is_multiple_of(32) {
+        return Err(ParseError::InvalidData(format!(
+            "Lookup table address list is {} bytes, not a multiple of 32", addresses.len()
+        )));
+    }
+    Ok(addresses.chunks_exact(32).map(bytes_to_base58).collect())
+}
+
+impl DecodedTransaction {
+    /// The first signature, which identifies the transaction
+    pub fn signature(&self) -> Option<&str> {
+        self.signatures.first().map(String::as_str)
+    }
+
+    /// Lookup tables the message loads accounts from, in message order
+    pub fn lookup_table_keys(&self) -> Vec<String> {
+        self.address_table_lookups.iter().map(|lookup| lookup.account_key.clone()).collect()
+    }
+
+    /// Pick the addresses the message loads out of fetched lookup tables
+    ///
+    /// `tables` maps each table key to its address list (see
+    /// [`decode_lookup_table`]).
+    pub fn load_addresses(&self, tables: &HashMap<String, Vec<String>>) -> Result<LoadedAddresses, ParseError> {
+        let mut loaded = LoadedAddresses::default();
+
+        for lookup in &self.address_table_lookups {
+            let table = tables.get(&lookup.account_key).ok_or_else(|| ParseError::InvalidData(format!(
+                "Lookup table {} was not provided", lookup.account_key
+            )))?;
+            let pick = |index: &u8| table.get(*index as usize).cloned().ok_or_else(|| ParseError::InvalidData(format!(
+                "Lookup table {} has no address at index {}", lookup.account_key, index
+            )));
+
+            for index in &lookup.writable_indexes {
+                loaded.writable.push(pick(index)?);
+            }
+            for index in &lookup.readonly_indexes {
+                loaded.readonly.push(pick(index)?);
+            }
+        }
+
+        Ok(loaded)
+    }
+
+    /// Every account the message references: the static keys, then the
+    /// loaded writable and readonly addresses
+    pub fn all_account_keys(&self, loaded: &LoadedAddresses) -> Vec<String> {
+        self.account_keys.iter()
+            .chain(&loaded.writable)
+            .chain(&loaded.readonly)
+            .cloned()
+            .collect()
+    }
+
+    /// Resolve each compiled instruction's program id and accounts
+    ///
+    /// Pass `LoadedAddresses::default()` for legacy messages. A v0 message
+    /// with lookups fails unless `loaded` holds its addresses.
+    pub fn resolve_instructions(&self, loaded: &LoadedAddresses) -> Result<Vec<InstructionData>, ParseError> {
+        let keys = self.resolved_keys(loaded)?;
+        self.instructions.iter().map(|instruction| resolve(instruction, &keys)).collect()
+    }
+
+    /// Resolve inner instructions against this transaction's accounts
+    ///
+    /// Returns each invoking instruction's index with its resolved inner
+    /// instructions. Fails if an index doesn't name an instruction of the
+    /// message, which means `inner` belongs to another transaction.
+    pub fn resolve_inner_instructions(
+        &self,
+        inner: &[InnerInstructions],
+        loaded: &LoadedAddresses,
+    ) -> Result<Vec<(usize, Vec<InstructionData>)>, ParseError> {
+        let keys = self.resolved_keys(loaded)?;
+
+        inner.iter()
+            .map(|group| {
+                let parent = group.index as usize;
+                if parent >= self.instructions.len() {
+                    return Err(ParseError::InvalidData(format!(
+                        "Inner instructions for instruction {}, but the message has {}",
+                        parent, self.instructions.len()
+                    )));
+                }
+                let instructions = group.instructions.iter()
+                    .map(|instruction| resolve(instruction, &keys))
+                    .collect::<Result<_, _>>()?;
+                Ok((parent, instructions))
+            })
+            .collect()
+    }
+
+    fn resolved_keys(&self, loaded: &LoadedAddresses) -> Result<Vec<String>, ParseError> {
+        if !self.address_table_lookups.is_empty() && loaded.is_empty() {
+            return Err(ParseError::InvalidData(format!(
+                "Message loads accounts from {} lookup table(s); resolve them first",
+                self.address_table_lookups.len()
+            )));
+        }
+        Ok(self.all_account_keys(loaded))
+    }
+
+    /// The transaction as the `transaction-transformer` interface takes it
+    pub fn to_solana_transaction(&self, loaded: &LoadedAddresses) -> Result<SolanaTransaction, ParseError> {
+        Ok(SolanaTransaction {
+            signature: self.signature().unwrap_or_default().to_string(),
+            instructions: self.resolve_instructions(loaded)?,
+            accounts: self.all_account_keys(loaded),
+            recent_blockhash: self.recent_blockhash.clone(),
+        })
+    }
+}
+
+/// Inner instructions from a transaction's status meta
+///
+/// Reads `innerInstructions` as `getTransaction` returns it with `json` or
+/// `base64` encoding (instruction data in base58). A missing or null field,
+/// as from nodes not recording CPIs, gives no inner instructions.
+///
+/// ```
+/// use serde_json::json;
+/// use solana_rpcx_sdk::inner_instructions_from_meta;
+///
+/// let meta = json!({
+///     "innerInstructions": [{
+///         "index": 0,
+///         "instructions": [{ "programIdIndex": 2, "accounts": [0, 1], "data": "3Bxs4h24hBtQy9rw", "stackHeight": 2 }],
+///     }],
+/// });
+/// let inner = inner_instructions_from_meta(&meta).unwrap();
+/// assert_eq!(inner[0].index, 0);
+/// assert_eq!(inner[0].instructions[0].program_id_index, 2);
+/// ```
+pub fn inner_instructions_from_meta(meta: &Value) -> Result<Vec<InnerInstructions>, ParseError> {
+    let groups = match &meta["innerInstructions"] {
+        Value::Null => return Ok(Vec::new()),
+        Value::Array(groups) => groups,
+        _ => return Err(ParseError::InvalidData("innerInstructions is not an array".to_string())),
+    };
+
+    groups.iter()
+        .map(|group| {
+            let instructions = group["instructions"].as_array()
+                .ok_or_else(|| ParseError::InvalidData("Inner instruction group has no instructions array".to_string()))?
+                .iter()
+                .map(compiled_from_json)
+                .collect::<Result<_, _>>()?;
+            Ok(InnerInstructions {
+                index: json_u8(&group["index"], "index")?,
+                instructions,
+            })
+        })
+        .collect()
+}
+
+/// Addresses a v0 transaction loaded from lookup tables, from its status meta
+///
+/// `getTransaction` reports them as `loadedAddresses`, which saves fetching
+/// the tables. Legacy transactions, and nodes that don't report them, give
+/// no addresses.
+pub fn loaded_addresses_from_meta(meta: &Value) -> Result<LoadedAddresses, ParseError> {
+    let list = |name: &str| -> Result<Vec<String>, ParseError> {
+        match &meta["loadedAddresses"][name] {
+            Value::Null => Ok(Vec::new()),
+            Value::Array(keys) => keys.iter()
+                .map(|key| key.as_str().map(str::to_string).ok_or_else(|| ParseError::InvalidData(
+                    format!("loadedAddresses.{} holds a non-string", name)
+                )))
+                .collect(),
+            _ => Err(ParseError::InvalidData(format!("loadedAddresses.{} is not an array", name))),
+        }
+    };
+
+    Ok(LoadedAddresses {
+        writable: list("writable")?,
+        readonly: list("readonly")?,
+    })
+}
+
+/// One JSON record per instruction of a transaction, in order
+///
+/// `parse` returns `None` for instructions no parser handles; those are
+/// recorded with their data as hex under `raw`. Parsed data is embedded as
+/// JSON, and failures carry the error's stable `kind`. Records for programs
+/// with a [`known_program_name`] also carry it as `program_name`.
+pub fn instruction_records<F>(instructions: &[InstructionData], parse: F) -> Value
+where
+    F: FnMut(&InstructionData) -> Option<Result<ParsedInstruction, ParseError>>,
+{
+    transaction_records(instructions, &[], parse)
+}
+
+/// Like [`instruction_records`], with each instruction's inner instructions
+/// following it
+///
+/// `inner` is as returned by [`DecodedTransaction::resolve_inner_instructions`].
+/// Inner records carry `parent_index`, the invoking instruction's `index`;
+/// their own `index` is their position among that instruction's CPIs.
+pub fn transaction_records<F>(
+    instructions: &[InstructionData],
+    inner: &[(usize, Vec<InstructionData>)],
+    parse: F,
+) -> Value
+where
+    F: FnMut(&InstructionData) -> Option<Result<ParsedInstruction, ParseError>>,
+{
+    named_transaction_records(instructions, inner, known_program_name, parse)
+}
+
+/// [`transaction_records`], naming programs with `program_name`
+pub(crate) fn named_transaction_records<'n, N, F>(
+    instructions: &[InstructionData],
+    inner: &[(usize, Vec<InstructionData>)],
+    program_name: N,
+    mut parse: F,
+) -> Value
+where
+    N: Fn(&str) -> Option<&'n str>,
+    F: FnMut(&InstructionData) -> Option<Result<ParsedInstruction, ParseError>>,
+{
+    let mut records = Vec::with_capacity(instructions.len());
+
+    for (index, instruction) in instructions.iter().enumerate() {
+        let name = program_name(&instruction.program_id);
+        records.push(record(index, None, instruction, name, parse(instruction)));
+
+        let invoked = inner.iter()
+            .filter(|(parent, _)| *parent == index)
+            .flat_map(|(_, instructions)| instructions);
+        for (inner_index, instruction) in invoked.enumerate() {
+            let name = program_name(&instruction.program_id);
+            records.push(record(inner_index, Some(index), instruction, name, parse(instruction)));
+        }
+    }
+
+    Value::Array(records)
+}
+
+fn record(
+    index: usize,
+    parent_index: Option<usize>,
+    instruction: &InstructionData,
+    program_name: Option<&str>,
+    outcome: Option<Result<ParsedInstruction, ParseError>>,
+) -> Value {
+    let mut record = json!({
+        "index": index,
+        "program_id": instruction.program_id,
+    });
+    if let Some(parent_index) = parent_index {
+        record["parent_index"] = json!(parent_index);
+    }
+    if let Some(name) = program_name {
+        record["program_name"] = json!(name);
+    }
+
+    match outcome {
+        Some(Ok(parsed)) => {
+            record["instruction_name"] = json!(parsed.instruction_name);
+            record["data"] = serde_json::from_str::<Value>(&parsed.data).unwrap_or_else(|_| json!(parsed.data));
+        }
+        Some(Err(error)) => {
+            record["error"] = json!({ "kind": error.code(), "message": error.message() });
+        }
+        None => record["raw"] = json!(bytes_to_hex(&instruction.data)),
+    }
+    record
+}
+
+fn resolve(instruction: &CompiledInstruction, keys: &[String]) -> Result<InstructionData, ParseError> {
+    let key = |index: u8| keys.get(index as usize).cloned().ok_or_else(|| ParseError::InvalidData(format!(
+        "Account index {} out of range ({} accounts)", index, keys.len()
+    )));
+
+    Ok(InstructionData {
+        program_id: key(instruction.program_id_index)?,
+        data: instruction.data.clone(),
+        accounts: instruction.accounts.iter().map(|&index| key(index)).collect::<Result<_, _>>()?,
+    })
+}
+
+/// An RPC `UiCompiledInstruction`
+fn compiled_from_json(value: &Value) -> Result<CompiledInstruction, ParseError> {
+    let accounts = value["accounts"].as_array()
+        .ok_or_else(|| ParseError::InvalidData("Inner instruction has no accounts array".to_string()))?
+        .iter()
+        .map(|index| json_u8(index, "accounts"))
+        .collect::<Result<_, _>>()?;
+    let data = value["data"].as_str()
+        .ok_or_else(|| ParseError::InvalidData("Inner instruction has no data".to_string()))?;
+
+    Ok(CompiledInstruction {
+        program_id_index: json_u8(&value["programIdIndex"], "programIdIndex")?,
+        accounts,
+        data: base58_to_bytes(data).map_err(|e| ParseError::InvalidData(format!("Inner instruction data: {}", e)))?,
+    })
+}
+
+fn json_u8(value: &Value, field: &str) -> Result<u8, ParseError> {
+    value.as_u64()
+        .and_then(|n| u8::try_from(n).ok())
+        .ok_or_else(|| ParseError::InvalidData(format!("{} is not a u8: {}", field, value)))
+}
+
+/// Cursor over wire-format bytes
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ParseError> {
+        let end = self.offset.checked_add(len)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or_else(|| ParseError::InsufficientData(format!(
+                "Transaction truncated: need {} bytes at offset {}, have {}",
+                len, self.offset, self.bytes.len() - self.offset
+            )))?;
+        let slice = &self.bytes[self.offset..end];
+        self.offset = end;
+        Ok(slice)
+    }
+
+    fn peek(&self) -> Result<u8, ParseError> {
+        self.bytes.get(self.offset).copied().ok_or_else(|| ParseError::InsufficientData(
+            format!("Transaction truncated at offset {}", self.offset)
+        ))
+    }
+
+    fn u8(&mut self) -> Result<u8, ParseError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn pubkey(&mut self) -> Result<String, ParseError> {
+        self.take(32).map(bytes_to_base58)
+    }
+
+    /// A compact-u16 length: 7 bits per byte, at most 3 bytes
+    fn short_vec_len(&mut self) -> Result<usize, ParseError> {
+        let mut len = 0usize;
+        for shift in [0, 7, 14] {
+            let byte = self.u8()?;
+            len |= ((byte & 0x7f) as usize) << shift;
+            if byte & 0x80 == 0 {
+                return if len > u16::MAX as usize {
+                    Err(ParseError::InvalidData(format!("Compact length {} overflows u16", len)))
+                } else {
+                    Ok(len)
+                };
+            }
+        }
+        Err(ParseError::InvalidData(format!("Compact length at offset {} is too long", self.offset - 3)))
+    }
+
+    fn short_vec_bytes(&mut self) -> Result<Vec<u8>, ParseError> {
+        let len = self.short_vec_len()?;
+        self.take(len).map(<[u8]>::to_vec)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MEMO_PROGRAM: &str = "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr";
+    const CUSTOM_PROGRAM: &str = "Prog1111111111111111111111111111111111111111";
+
+    fn instruction(program_id: &str) -> InstructionData {
+        InstructionData { program_id: program_id.to_string(), data: vec![0xab], accounts: Vec::new() }
+    }
+
+    #[test]
+    fn records_name_well_known_programs() {
+        let records = instruction_records(&[instruction(MEMO_PROGRAM), instruction(CUSTOM_PROGRAM)], |_| None);
+
+        assert_eq!(records, json!([
+            { "index": 0, "program_id": MEMO_PROGRAM, "program_name": "Memo Program", "raw": "ab" },
+            { "index": 1, "program_id": CUSTOM_PROGRAM, "raw": "ab" },
+        ]));
+    }
+
+    #[test]
+    fn registry_names_take_precedence_in_records() {
+        let mut registry = crate::ParserRegistry::new();
+        registry.set_program_name(CUSTOM_PROGRAM, "Custom");
+        registry.set_program_name(MEMO_PROGRAM, "Memo");
+
+        let records = named_transaction_records(
+            &[instruction(MEMO_PROGRAM)],
+            &[(0, vec![instruction(CUSTOM_PROGRAM)])],
+            |program_id| registry.program_name(program_id),
+            |_| None,
+        );
+        assert_eq!(records[0]["program_name"], "Memo");
+        assert_eq!(records[1]["program_name"], "Custom");
+    }
+
+    #[test]
+    fn failures_carry_the_error_code() {
+        let records = instruction_records(&[instruction(CUSTOM_PROGRAM)], |_| {
+            Some(Err(ParseError::InsufficientData("need 8 bytes".to_string())))
+        });
+
+        assert_eq!(records[0]["error"], json!({ "kind": "insufficient_data", "message": "need 8 bytes" }));
+    }
+
+    #[test]
+    fn inner_records_follow_their_parent_and_are_named() {
+        let records = transaction_records(
+            &[instruction(CUSTOM_PROGRAM), instruction(CUSTOM_PROGRAM)],
+            &[(0, vec![instruction(MEMO_PROGRAM), instruction(CUSTOM_PROGRAM)])],
+            |_| None,
+        );
+
+        let shape: Vec<_> = records.as_array().unwrap().iter()
+            .map(|record| (record["index"].clone(), record["parent_index"].clone(), record["program_name"].clone()))
+            .collect();
+        assert_eq!(shape, [
+            (json!(0), Value::Null, Value::Null),
+            (json!(0), json!(0), json!("Memo Program")),
+            (json!(1), json!(0), Value::Null),
+            (json!(1), Value::Null, Value::Null),
+        ]);
+    }
+}
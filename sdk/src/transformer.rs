@@ -2,4 +2,180 @@
 //! 
 //! For implementing the accounts-transformer interface
 
-// TODO: Add helper functions for building transformers
\ No newline at end of file
+use crate::bindings::component::solana_rpcx_bindings::types::*;
+use crate::bindings::exports::component::solana_rpcx_bindings::accounts_transformer_setup::{
+    SeedComponent, TransformerRequest,
+};
+#[cfg(feature = "pda")]
+use crate::{base58_to_pubkey, bytes_to_base58, find_program_address};
+
+/// Type alias for account transform functions
+pub type TransformFn = Box<dyn Fn(Vec<SolanaAccount>, &str) -> Result<String, String> + Send + Sync>;
+
+/// Builder for creating transformers
+#[derive(Default)]
+pub struct TransformerBuilder {
+    owner_filter: Option<String>,
+    transform: Option<TransformFn>,
+}
+
+impl TransformerBuilder {
+    /// Create a new transformer builder
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Only pass accounts owned by this program to the transform
+    pub fn with_owner_filter(mut self, program_id: impl Into<String>) -> Self {
+        self.owner_filter = Some(program_id.into());
+        self
+    }
+    
+    /// Register the transform function
+    /// 
+    /// Receives the input accounts and the raw `params` string and returns
+    /// the result as a string (usually JSON).
+    pub fn register_transform<F>(mut self, transform: F) -> Self
+    where
+        F: Fn(Vec<SolanaAccount>, &str) -> Result<String, String> + Send + Sync + 'static,
+    {
+        self.transform = Some(Box::new(transform));
+        self
+    }
+    
+    /// Build the final transformer
+    pub fn build(self) -> Transformer {
+        Transformer {
+            owner_filter: self.owner_filter,
+            transform: self.transform,
+        }
+    }
+}
+
+/// Runtime transformer that runs the registered transform function
+pub struct Transformer {
+    owner_filter: Option<String>,
+    transform: Option<TransformFn>,
+}
+
+impl Transformer {
+    /// Run the transform over the given accounts
+    pub fn transform(&self, accounts: Vec<SolanaAccount>, params: &str) -> Result<String, String> {
+        let transform = self.transform
+            .as_ref()
+            .ok_or_else(|| "No transform registered".to_string())?;
+        
+        let accounts = match &self.owner_filter {
+            Some(owner) => accounts.into_iter().filter(|a| &a.owner == owner).collect(),
+            None => accounts,
+        };
+        
+        transform(accounts, params)
+    }
+}
+
+/// Adds `TransformerRequest::builder()`
+pub trait TransformerRequestExt {
+    /// Start building a transformer request
+    fn builder() -> TransformerRequestBuilder;
+}
+
+impl TransformerRequestExt for TransformerRequest {
+    fn builder() -> TransformerRequestBuilder {
+        TransformerRequestBuilder::default()
+    }
+}
+
+/// Builder for the accounts a transformer asks the host for
+/// 
+/// Seeds are added to the current PDA pattern; call [`next_pattern`](Self::next_pattern)
+/// to start another one.
+/// 
+/// ```ignore
+/// TransformerRequest::builder()
+///     .seed_literal(b"pool")
+///     .seed_pubkey(authority)
+///     .owner(PROGRAM_ID)
+///     .build()
+/// ```
+#[derive(Default)]
+pub struct TransformerRequestBuilder {
+    seeds: Vec<Vec<SeedComponent>>,
+    current: Vec<SeedComponent>,
+    addresses: Vec<String>,
+    owner_filter: Option<String>,
+}
+
+impl TransformerRequestBuilder {
+    /// Add static bytes to the current seed pattern
+    pub fn seed_literal(mut self, bytes: impl AsRef<[u8]>) -> Self {
+        self.current.push(SeedComponent {
+            constant: bytes.as_ref().to_vec(),
+            account_key: None,
+        });
+        self
+    }
+    
+    /// Add an account key to the current seed pattern
+    pub fn seed_pubkey(mut self, pubkey: impl ToString) -> Self {
+        self.current.push(SeedComponent {
+            constant: vec![],
+            account_key: Some(pubkey.to_string()),
+        });
+        self
+    }
+    
+    /// Finish the current seed pattern and start a new one
+    pub fn next_pattern(mut self) -> Self {
+        if !self.current.is_empty() {
+            self.seeds.push(std::mem::take(&mut self.current));
+        }
+        self
+    }
+    
+    /// Request a specific account address
+    pub fn address(mut self, address: impl ToString) -> Self {
+        self.addresses.push(address.to_string());
+        self
+    }
+    
+    /// Only return accounts owned by this program
+    pub fn owner(mut self, program_id: impl ToString) -> Self {
+        self.owner_filter = Some(program_id.to_string());
+        self
+    }
+    
+    /// Build the final request
+    pub fn build(self) -> TransformerRequest {
+        let builder = self.next_pattern();
+        TransformerRequest {
+            seeds: builder.seeds,
+            addresses: if builder.addresses.is_empty() { None } else { Some(builder.addresses) },
+            owner_filter: builder.owner_filter,
+        }
+    }
+}
+
+/// Derive the PDA for one seed pattern from a `TransformerRequest`
+/// 
+/// Each component contributes its constant bytes followed by its account key, if any.
+/// Returns the base58 address and bump seed.
+#[cfg(feature = "pda")]
+pub fn derive_seed_address(pattern: &[SeedComponent], program_id: &str) -> Result<(String, u8), String> {
+    let program_id = base58_to_pubkey(program_id)?;
+    
+    let mut owned: Vec<Vec<u8>> = Vec::new();
+    for component in pattern {
+        if !component.constant.is_empty() {
+            owned.push(component.constant.clone());
+        }
+        if let Some(key) = &component.account_key {
+            owned.push(base58_to_pubkey(key)?.to_vec());
+        }
+    }
+    
+    let seeds: Vec<&[u8]> = owned.iter().map(|seed| seed.as_slice()).collect();
+    find_program_address(&seeds, &program_id)
+        .map(|(address, bump)| (bytes_to_base58(&address), bump))
+        .ok_or_else(|| "Unable to find a valid program address for seeds".to_string())
+}
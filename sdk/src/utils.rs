@@ -2,6 +2,9 @@
 
 use sha2::{Digest, Sha256};
 
+/// Number of lamports in one SOL
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
 /// Compute Anchor-style discriminator
 /// 
 /// For accounts: `anchor_discriminator("account", "MyAccount")`
@@ -14,6 +17,63 @@ pub fn compute_anchor_discriminator(namespace: &str, name: &str) -> [u8; 8] {
     discriminator
 }
 
+/// Compute the discriminator Anchor uses for an instruction
+/// 
+/// Anchor hashes `global:<name>` with the name in snake_case, so the name is
+/// converted first and `initializePool`, `InitializePool` and
+/// `initialize_pool` all give the same result.
+/// 
+/// ```
+/// use solana_rpcx_sdk::{anchor_instruction_discriminator, compute_anchor_discriminator};
+/// 
+/// let expected = compute_anchor_discriminator("global", "initialize_pool");
+/// assert_eq!(anchor_instruction_discriminator("initializePool"), expected);
+/// assert_eq!(anchor_instruction_discriminator("InitializePool"), expected);
+/// assert_eq!(anchor_instruction_discriminator("initialize_pool"), expected);
+/// ```
+pub fn anchor_instruction_discriminator(name: &str) -> [u8; 8] {
+    compute_anchor_discriminator("global", &to_snake_case(name))
+}
+
+/// Convert a camelCase or PascalCase identifier to snake_case
+/// 
+/// Runs of capitals are kept together, matching Anchor's conversion.
+/// 
+/// ```
+/// use solana_rpcx_sdk::to_snake_case;
+/// 
+/// assert_eq!(to_snake_case("initializePool"), "initialize_pool");
+/// assert_eq!(to_snake_case("SetAuthority"), "set_authority");
+/// assert_eq!(to_snake_case("updateURI"), "update_uri");
+/// assert_eq!(to_snake_case("HTTPServer"), "http_server");
+/// assert_eq!(to_snake_case("swapV2"), "swap_v2");
+/// assert_eq!(to_snake_case("already_snake"), "already_snake");
+/// ```
+pub fn to_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake = String::with_capacity(name.len() + 4);
+    
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_ascii_uppercase() {
+            let prev = i.checked_sub(1).map(|p| chars[p]);
+            let next = chars.get(i + 1);
+            
+            let starts_word = match prev {
+                Some(p) if p.is_ascii_lowercase() || p.is_ascii_digit() => true,
+                Some(p) if p.is_ascii_uppercase() => next.is_some_and(|n| n.is_ascii_lowercase()),
+                _ => false,
+            };
+            if starts_word && !snake.ends_with('_') {
+                snake.push('_');
+            }
+            snake.push(c.to_ascii_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
+
 /// Convert bytes to base58 string
 pub fn bytes_to_base58(bytes: &[u8]) -> String {
     bs58::encode(bytes).into_string()
@@ -24,4 +84,414 @@ pub fn base58_to_bytes(s: &str) -> Result<Vec<u8>, String> {
     bs58::decode(s)
         .into_vec()
         .map_err(|e| format!("Invalid base58: {}", e))
-}
\ No newline at end of file
+}
+
+/// Convert base58 string to a 32-byte public key
+/// 
+/// Fails unless the string decodes to exactly 32 bytes.
+/// 
+/// ```
+/// use solana_rpcx_sdk::base58_to_pubkey;
+/// 
+/// assert!(base58_to_pubkey("11111111111111111111111111111111").is_ok());
+/// // Too short
+/// assert!(base58_to_pubkey("1111").is_err());
+/// // Too long
+/// assert!(base58_to_pubkey(&"1".repeat(33)).is_err());
+/// ```
+pub fn base58_to_pubkey(s: &str) -> Result<[u8; 32], String> {
+    let bytes = base58_to_bytes(s)?;
+    let len = bytes.len();
+    bytes
+        .try_into()
+        .map_err(|_| format!("Invalid pubkey {}: expected 32 bytes, got {}", s, len))
+}
+
+/// Convert bytes to a lowercase hex string
+pub fn bytes_to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Convert a hex string to bytes
+/// 
+/// Accepts upper or lower case and an optional `0x` prefix.
+/// 
+/// ```
+/// use solana_rpcx_sdk::{bytes_to_hex, hex_to_bytes};
+/// 
+/// let bytes = vec![0x00, 0xab, 0xff, 0x10];
+/// assert_eq!(bytes_to_hex(&bytes), "00abff10");
+/// assert_eq!(hex_to_bytes(&bytes_to_hex(&bytes)).unwrap(), bytes);
+/// assert_eq!(hex_to_bytes("0x00ABff10").unwrap(), bytes);
+/// assert_eq!(hex_to_bytes("").unwrap(), Vec::<u8>::new());
+/// 
+/// assert!(hex_to_bytes("abc").is_err());
+/// assert!(hex_to_bytes("zz").is_err());
+/// ```
+pub fn hex_to_bytes(s: &str) -> Result<Vec<u8>, String> {
+    let digits = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")).unwrap_or(s);
+    
+    if !digits.len().is_multiple_of(2) {
+        return Err(format!("Invalid hex: odd number of digits ({})", digits.len()));
+    }
+    
+    digits
+        .as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            let pair = std::str::from_utf8(pair).map_err(|_| "Invalid hex: non-ASCII input".to_string())?;
+            // from_str_radix alone would accept a sign, as in "+f"
+            if !pair.bytes().all(|b| b.is_ascii_hexdigit()) {
+                return Err(format!("Invalid hex: bad digit pair {:?}", pair));
+            }
+            u8::from_str_radix(pair, 16).map_err(|_| format!("Invalid hex: bad digit pair {:?}", pair))
+        })
+        .collect()
+}
+
+/// Convert lamports to SOL
+/// 
+/// Lossy for very large balances; use [`format_sol`] for display.
+pub fn lamports_to_sol(lamports: u64) -> f64 {
+    lamports as f64 / LAMPORTS_PER_SOL as f64
+}
+
+/// Format lamports as a SOL amount with 9 fixed decimals, e.g. `"1.500000000"`
+/// 
+/// Formatted from the integer directly, so there is no floating point error.
+pub fn format_sol(lamports: u64) -> String {
+    format_token_amount(lamports, 9)
+}
+
+/// Format a raw token amount using the mint's decimals
+/// 
+/// Uses integer/string math only and keeps all `decimals` fractional digits.
+/// 
+/// ```
+/// use solana_rpcx_sdk::format_token_amount;
+/// 
+/// assert_eq!(format_token_amount(1_500_000, 6), "1.500000");
+/// assert_eq!(format_token_amount(1_000, 9), "0.000001000");
+/// assert_eq!(format_token_amount(42, 0), "42");
+/// ```
+pub fn format_token_amount(raw: u64, decimals: u8) -> String {
+    let decimals = decimals as usize;
+    if decimals == 0 {
+        return raw.to_string();
+    }
+    
+    // Left-pad so there is always at least one integer digit
+    let digits = format!("{:0>width$}", raw, width = decimals + 1);
+    let (whole, fraction) = digits.split_at(digits.len() - decimals);
+    format!("{}.{}", whole, fraction)
+}
+
+/// Largest exponent magnitude written out in full by [`format_fixed_point`]
+const MAX_FIXED_POINT_EXPONENT: u32 = 64;
+
+/// Format a fixed-point integer `value * 10^exponent` as a decimal string
+/// 
+/// For oracle and AMM prices stored as an integer with an implied exponent
+/// (Pyth's `price` and `expo`). Like [`format_token_amount`], all digits
+/// are kept and no floating point is involved. Exponents beyond ±64 (more
+/// digits than an `i128` holds) come from corrupt data and are written as
+/// `<value>e<exponent>` instead of being expanded.
+/// 
+/// ```
+/// use solana_rpcx_sdk::format_fixed_point;
+/// 
+/// // Pyth SOL/USD: price 15012345678, expo -8
+/// assert_eq!(format_fixed_point(15_012_345_678, -8), "150.12345678");
+/// // Pyth-style confidence and small prices
+/// assert_eq!(format_fixed_point(1_234, -8), "0.00001234");
+/// assert_eq!(format_fixed_point(-250_000, -5), "-2.50000");
+/// assert_eq!(format_fixed_point(42, 3), "42000");
+/// assert_eq!(format_fixed_point(7, 0), "7");
+/// assert_eq!(format_fixed_point(7, i32::MIN), "7e-2147483648");
+/// ```
+pub fn format_fixed_point(value: i128, exponent: i32) -> String {
+    if exponent.unsigned_abs() > MAX_FIXED_POINT_EXPONENT {
+        return format!("{}e{}", value, exponent);
+    }
+    
+    let sign = if value < 0 { "-" } else { "" };
+    let magnitude = value.unsigned_abs();
+    
+    if exponent >= 0 {
+        let zeros = if magnitude == 0 { 0 } else { exponent as usize };
+        return format!("{}{}{}", sign, magnitude, "0".repeat(zeros));
+    }
+    
+    let decimals = exponent.unsigned_abs() as usize;
+    // Left-pad so there is always at least one integer digit
+    let digits = format!("{:0>width$}", magnitude, width = decimals + 1);
+    let (whole, fraction) = digits.split_at(digits.len() - decimals);
+    format!("{}{}.{}", sign, whole, fraction)
+}
+
+/// Maximum number of seeds in a program derived address
+pub const MAX_SEEDS: usize = 16;
+
+/// Maximum length of a single PDA seed
+pub const MAX_SEED_LEN: usize = 32;
+
+#[cfg(feature = "pda")]
+const PDA_MARKER: &[u8] = b"ProgramDerivedAddress";
+
+/// Derive a program address from seeds, including the bump seed
+/// 
+/// Returns `None` if the seeds are invalid or the hash lands on the curve.
+#[cfg(feature = "pda")]
+pub fn create_program_address(seeds: &[&[u8]], program_id: &[u8; 32]) -> Option<[u8; 32]> {
+    if seeds.len() > MAX_SEEDS || seeds.iter().any(|seed| seed.len() > MAX_SEED_LEN) {
+        return None;
+    }
+    
+    let mut hasher = Sha256::new();
+    for seed in seeds {
+        hasher.update(seed);
+    }
+    hasher.update(program_id);
+    hasher.update(PDA_MARKER);
+    let address: [u8; 32] = hasher.finalize().into();
+    
+    if is_on_curve(&address) {
+        return None;
+    }
+    Some(address)
+}
+
+/// Find a valid program derived address and its bump seed
+/// 
+/// Same search as `Pubkey::find_program_address`: tries bumps from 255 down.
+#[cfg(feature = "pda")]
+pub fn find_program_address(seeds: &[&[u8]], program_id: &[u8; 32]) -> Option<([u8; 32], u8)> {
+    if seeds.len() >= MAX_SEEDS {
+        return None;
+    }
+    
+    for bump in (0..=u8::MAX).rev() {
+        let bump_seed = [bump];
+        let mut with_bump = seeds.to_vec();
+        with_bump.push(&bump_seed);
+        if let Some(address) = create_program_address(&with_bump, program_id) {
+            return Some((address, bump));
+        }
+    }
+    None
+}
+
+/// Check whether 32 bytes are a valid ed25519 point
+#[cfg(feature = "pda")]
+fn is_on_curve(bytes: &[u8; 32]) -> bool {
+    curve25519_dalek::edwards::CompressedEdwardsY(*bytes)
+        .decompress()
+        .is_some()
+}
+
+/// SPL Token program id
+pub const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+
+/// SPL Token-2022 program id
+pub const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+
+/// SPL Associated Token Account program id
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: &str = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
+
+/// Built-in names for well-known programs, by program id
+const KNOWN_PROGRAMS: &[(&str, &str)] = &[
+    ("11111111111111111111111111111111", "System Program"),
+    (TOKEN_PROGRAM_ID, "Token Program"),
+    (TOKEN_2022_PROGRAM_ID, "Token-2022 Program"),
+    (ASSOCIATED_TOKEN_PROGRAM_ID, "Associated Token Account Program"),
+    ("Stake11111111111111111111111111111111111111", "Stake Program"),
+    ("Vote111111111111111111111111111111111111111", "Vote Program"),
+    ("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr", "Memo Program"),
+    ("Memo1UhkJRfHyvLMcVucJwxXeuD728EqVDDwQDxFMNo", "Memo Program v1"),
+    ("ComputeBudget111111111111111111111111111111", "Compute Budget Program"),
+    ("AddressLookupTab1e1111111111111111111111111", "Address Lookup Table Program"),
+    ("BPFLoaderUpgradeab1e11111111111111111111111", "BPF Upgradeable Loader"),
+    ("Config1111111111111111111111111111111111111", "Config Program"),
+];
+
+/// Human-readable name of a well-known program
+/// 
+/// Covers the native and SPL programs most output refers to (System, Token,
+/// Token-2022, ATA, Stake, Memo, ...). To name other programs, set them on a
+/// [`ParserRegistry`](crate::ParserRegistry) and look them up with
+/// [`ParserRegistry::program_name`](crate::ParserRegistry::program_name).
+/// 
+/// ```
+/// use solana_rpcx_sdk::known_program_name;
+/// 
+/// assert_eq!(known_program_name("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"), Some("Token Program"));
+/// assert_eq!(known_program_name("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc"), None);
+/// ```
+pub fn known_program_name(program_id: &str) -> Option<&'static str> {
+    KNOWN_PROGRAMS
+        .iter()
+        .find(|(id, _)| *id == program_id)
+        .map(|(_, name)| *name)
+}
+
+/// Derive the associated token account for a wallet and mint
+/// 
+/// Uses the SPL Token program; see
+/// [`get_associated_token_address_with_program_id`] for Token-2022 mints.
+/// 
+/// ```
+/// use solana_rpcx_sdk::get_associated_token_address;
+/// 
+/// // USDC account for a mainnet wallet
+/// let ata = get_associated_token_address(
+///     "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM",
+///     "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+/// ).unwrap();
+/// assert_eq!(ata, "FGETo8T8wMcN2wCjav8VK6eh3dLk63evNDPxzLSJra8B");
+/// 
+/// assert!(get_associated_token_address("not-a-wallet", "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v").is_err());
+/// ```
+#[cfg(feature = "pda")]
+pub fn get_associated_token_address(wallet: &str, mint: &str) -> Result<String, String> {
+    get_associated_token_address_with_program_id(wallet, mint, TOKEN_PROGRAM_ID)
+}
+
+/// Derive the associated token account for a wallet, mint and token program
+/// 
+/// Seeds are `[wallet, token_program, mint]` under the ATA program.
+#[cfg(feature = "pda")]
+pub fn get_associated_token_address_with_program_id(
+    wallet: &str,
+    mint: &str,
+    token_program_id: &str,
+) -> Result<String, String> {
+    let wallet = base58_to_pubkey(wallet)?;
+    let mint = base58_to_pubkey(mint)?;
+    let token_program_id = base58_to_pubkey(token_program_id)?;
+    let ata_program_id = base58_to_pubkey(ASSOCIATED_TOKEN_PROGRAM_ID)?;
+    
+    find_program_address(&[&wallet, &token_program_id, &mint], &ata_program_id)
+        .map(|(address, _)| bytes_to_base58(&address))
+        .ok_or_else(|| "Unable to find a valid associated token address".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn base58_to_pubkey_requires_32_bytes() {
+        assert_eq!(base58_to_pubkey("11111111111111111111111111111111").unwrap(), [0; 32]);
+        
+        let short = bytes_to_base58(&[1; 31]);
+        assert_eq!(
+            base58_to_pubkey(&short).unwrap_err(),
+            format!("Invalid pubkey {}: expected 32 bytes, got 31", short)
+        );
+        let long = bytes_to_base58(&[1; 33]);
+        assert!(base58_to_pubkey(&long).unwrap_err().ends_with("expected 32 bytes, got 33"));
+        assert!(base58_to_pubkey("").unwrap_err().ends_with("got 0"));
+    }
+    
+    #[test]
+    fn base58_to_pubkey_rejects_characters_outside_the_alphabet() {
+        // `0`, `O`, `I` and `l` are left out of base58 as look-alikes
+        let error = base58_to_pubkey("0OIl1111111111111111111111111111").unwrap_err();
+        assert!(error.starts_with("Invalid base58"), "{}", error);
+    }
+    
+    #[test]
+    fn format_token_amount_keeps_every_decimal() {
+        assert_eq!(format_token_amount(0, 6), "0.000000");
+        assert_eq!(format_token_amount(1, 9), "0.000000001");
+        assert_eq!(format_token_amount(123_456_789, 3), "123456.789");
+        assert_eq!(format_token_amount(u64::MAX, 0), "18446744073709551615");
+        assert_eq!(format_token_amount(u64::MAX, 6), "18446744073709.551615");
+    }
+    
+    #[test]
+    fn format_token_amount_pads_when_decimals_exceed_the_digits() {
+        assert_eq!(format_token_amount(u64::MAX, 25), "0.0000018446744073709551615");
+        assert_eq!(format_token_amount(5, u8::MAX).len(), 2 + u8::MAX as usize);
+    }
+    
+    #[test]
+    fn format_sol_uses_nine_decimals() {
+        assert_eq!(format_sol(1_500_000_000), "1.500000000");
+        assert_eq!(format_sol(1), "0.000000001");
+    }
+    
+    #[test]
+    fn to_snake_case_matches_anchor() {
+        assert_eq!(to_snake_case("initializePool"), "initialize_pool");
+        assert_eq!(to_snake_case("InitializePool"), "initialize_pool");
+        assert_eq!(to_snake_case("mintV2Tokens"), "mint_v2_tokens");
+        assert_eq!(to_snake_case("getURIForNFT"), "get_uri_for_nft");
+        assert_eq!(to_snake_case("ID"), "id");
+        assert_eq!(to_snake_case("already_snake"), "already_snake");
+        assert_eq!(to_snake_case("set_Authority"), "set_authority");
+        assert_eq!(to_snake_case(""), "");
+    }
+    
+    #[test]
+    fn anchor_instruction_discriminator_ignores_the_name_case() {
+        let expected = compute_anchor_discriminator("global", "initialize_pool");
+        
+        assert_eq!(anchor_instruction_discriminator("initializePool"), expected);
+        assert_eq!(anchor_instruction_discriminator("initialize_pool"), expected);
+        assert_ne!(anchor_instruction_discriminator("initializePools"), expected);
+    }
+    
+    #[test]
+    fn hex_round_trips_every_byte() {
+        let bytes: Vec<u8> = (0..=u8::MAX).collect();
+        let hex = bytes_to_hex(&bytes);
+        
+        assert_eq!(hex.len(), 512);
+        assert!(hex.starts_with("000102") && hex.ends_with("fdfeff"));
+        assert_eq!(hex_to_bytes(&hex).unwrap(), bytes);
+        assert_eq!(hex_to_bytes(&hex.to_uppercase()).unwrap(), bytes);
+        assert_eq!(hex_to_bytes(&format!("0X{}", hex)).unwrap(), bytes);
+    }
+    
+    #[test]
+    fn hex_to_bytes_rejects_malformed_input() {
+        assert_eq!(hex_to_bytes("0xabc").unwrap_err(), "Invalid hex: odd number of digits (3)");
+        assert_eq!(hex_to_bytes("0g").unwrap_err(), "Invalid hex: bad digit pair \"0g\"");
+        assert_eq!(hex_to_bytes("+f").unwrap_err(), "Invalid hex: bad digit pair \"+f\"");
+        // The pair splits the two bytes of `é`
+        assert_eq!(hex_to_bytes("aéa").unwrap_err(), "Invalid hex: non-ASCII input");
+        assert!(hex_to_bytes("0x0x").is_err());
+    }
+    
+    #[test]
+    fn format_fixed_point_handles_negatives() {
+        assert_eq!(format_fixed_point(-5, -3), "-0.005");
+        assert_eq!(format_fixed_point(-1_500, -2), "-15.00");
+        assert_eq!(format_fixed_point(-42, 2), "-4200");
+    }
+    
+    #[test]
+    fn format_fixed_point_positive_exponents_append_zeros() {
+        assert_eq!(format_fixed_point(42, 1), "420");
+        assert_eq!(format_fixed_point(0, 5), "0");
+        assert_eq!(format_fixed_point(0, -3), "0.000");
+    }
+    
+    #[test]
+    fn format_fixed_point_expands_up_to_the_limit() {
+        let limit = MAX_FIXED_POINT_EXPONENT as i32;
+        
+        assert_eq!(format_fixed_point(1, -limit), format!("0.{}1", "0".repeat(63)));
+        assert_eq!(format_fixed_point(1, limit), format!("1{}", "0".repeat(64)));
+        assert_eq!(format_fixed_point(1, -limit - 1), "1e-65");
+        assert_eq!(format_fixed_point(-1, limit + 1), "-1e65");
+    }
+    
+    #[test]
+    fn format_fixed_point_handles_i128_min() {
+        assert_eq!(format_fixed_point(i128::MIN, 0), i128::MIN.to_string());
+        assert_eq!(format_fixed_point(i128::MIN, -2), "-1701411834604692317316873037158841057.28");
+        assert_eq!(format_fixed_point(i128::MIN, -39), "-0.170141183460469231731687303715884105728");
+    }
+}
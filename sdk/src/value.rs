@@ -0,0 +1,521 @@
+//! Internal representation of parsed output
+//!
+//! Parsers produce a `serde_json::Value`, which is only serialized to a
+//! string once, at the WIT boundary. serde_json's `arbitrary_precision`
+//! feature is enabled so `u128`/`i128` fields and wide integers in custom
+//! parsers' JSON survive the round trip exactly instead of failing or
+//! rounding to `f64`.
+
+use std::collections::HashSet;
+use serde_json::Value;
+use crate::bindings::component::solana_rpcx_bindings::types::*;
+use crate::options::{Casing, OutputOptions};
+
+/// Largest integer a JavaScript number represents exactly (2^53 - 1)
+pub const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+/// A parsed account whose data is still a JSON value
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedValue {
+    pub account_type: String,
+    pub data: serde_json::Value,
+    pub discriminator: Option<Vec<u8>>,
+    /// Set when the account decoded but could not be converted to JSON
+    pub serialization_error: Option<String>,
+    /// Write unsafe integers as strings in [`to_json`](Self::to_json), set
+    /// from `bignum_as_string` by [`apply_options`](Self::apply_options)
+    bignum_as_string: bool,
+}
+
+impl ParsedValue {
+    /// Create a parsed value from JSON data
+    pub fn new(
+        account_type: impl Into<String>,
+        data: serde_json::Value,
+        discriminator: Option<Vec<u8>>,
+    ) -> Self {
+        Self {
+            account_type: account_type.into(),
+            data,
+            discriminator,
+            serialization_error: None,
+            bignum_as_string: false,
+        }
+    }
+    
+    /// Record an account that decoded but failed to serialize
+    /// 
+    /// The data is an empty object; the error surfaces at output time unless
+    /// `resilient` is set in [`OutputOptions`].
+    pub fn serialization_failed(
+        account_type: impl Into<String>,
+        discriminator: Option<Vec<u8>>,
+        error: impl Into<String>,
+    ) -> Self {
+        Self {
+            serialization_error: Some(error.into()),
+            ..Self::new(account_type, Value::Object(Default::default()), discriminator)
+        }
+    }
+    
+    /// Create a parsed value from any serializable type
+    /// 
+    /// A serialization failure is recorded rather than returned, so the
+    /// account still counts as matched.
+    pub fn from_serialize<T: serde::Serialize>(
+        account_type: impl Into<String>,
+        account: &T,
+        discriminator: Option<Vec<u8>>,
+    ) -> Self {
+        match serde_json::to_value(account) {
+            Ok(data) => Self::new(account_type, data, discriminator),
+            Err(e) => Self::serialization_failed(account_type, discriminator, e.to_string()),
+        }
+    }
+    
+    /// Apply the post-processing passes requested in `options`
+    pub fn apply_options(&mut self, options: &OutputOptions) {
+        if options.resilient {
+            if let Some(error) = self.serialization_error.take() {
+                inject_fields(&mut self.data, vec![("_serialization_error", Value::String(error))]);
+            }
+        }
+        
+        apply_value_options(&mut self.data, options);
+        self.bignum_as_string = options.bignum_as_string;
+    }
+    
+    /// Serialize the data, compact or pretty
+    pub fn to_json(&self, pretty: bool) -> Result<String, ParseError> {
+        to_json_string(&self.data, pretty, self.bignum_as_string)
+    }
+    
+    /// Convert to the WIT `ParsedAccount`, serializing the data once
+    /// 
+    /// Fails if a serialization error was recorded and not handled by
+    /// [`apply_options`](Self::apply_options).
+    pub fn into_parsed_account(self, pretty: bool) -> Result<ParsedAccount, ParseError> {
+        if let Some(error) = self.serialization_error {
+            return Err(ParseError::InvalidData(error));
+        }
+        
+        let data = self.to_json(pretty)?;
+        
+        Ok(ParsedAccount {
+            account_type: self.account_type,
+            data,
+            discriminator: self.discriminator,
+        })
+    }
+}
+
+impl TryFrom<ParsedAccount> for ParsedValue {
+    type Error = ParseError;
+    
+    fn try_from(account: ParsedAccount) -> Result<Self, Self::Error> {
+        let data = serde_json::from_str(&account.data)
+            .map_err(|e| ParseError::InvalidData(format!("Parser returned invalid JSON: {}", e)))?;
+        
+        Ok(Self::new(account.account_type, data, account.discriminator))
+    }
+}
+
+/// Adds `ParsedAccount::decode()`
+pub trait ParsedAccountExt {
+    /// Deserialize the JSON `data` back into `T`
+    /// 
+    /// `T` is usually the struct the account was parsed from; fields the
+    /// parser injected (`_`-prefixed) are ignored unless `T` denies unknown
+    /// fields. Output options that reshape the JSON (key casing, tagged
+    /// enums, bignums as strings) must match `T`'s serde attributes.
+    /// 
+    /// ```
+    /// use solana_rpcx_sdk::prelude::*;
+    /// 
+    /// #[derive(BorshDeserialize, Serialize, Deserialize, Debug, PartialEq)]
+    /// struct Counter { count: u64 }
+    /// 
+    /// let program_id = "11111111111111111111111111111111";
+    /// let parser = ParserBuilder::new(program_id)
+    ///     .register_account_with_discriminator::<Counter>("Counter", vec![7])
+    ///     .build();
+    /// 
+    /// let mut data = vec![7];
+    /// data.extend_from_slice(&3u64.to_le_bytes());
+    /// let parsed = parser.parse_account(&SolanaAccount {
+    ///     pubkey: program_id.to_string(),
+    ///     data,
+    ///     owner: program_id.to_string(),
+    ///     lamports: 0,
+    ///     executable: false,
+    ///     rent_epoch: 0,
+    /// }).unwrap();
+    /// assert_eq!(parsed.decode::<Counter>().unwrap(), Counter { count: 3 });
+    /// ```
+    fn decode<T: serde::de::DeserializeOwned>(&self) -> Result<T, ParseError>;
+}
+
+impl ParsedAccountExt for ParsedAccount {
+    fn decode<T: serde::de::DeserializeOwned>(&self) -> Result<T, ParseError> {
+        serde_json::from_str(&self.data).map_err(|e| ParseError::DeserializationFailed(format!(
+            "Cannot decode {} data as {}: {}", self.account_type, std::any::type_name::<T>(), e
+        )))
+    }
+}
+
+/// Apply the passes in `options` that rewrite the JSON itself
+/// 
+/// `bignum_as_string` isn't one of them: it applies as the value is
+/// written out, see [`to_json_string`].
+pub(crate) fn apply_value_options(value: &mut Value, options: &OutputOptions) {
+    // Before recasing, so variant names are still recognisable
+    if options.tagged_enums {
+        tag_enums(value);
+    }
+    
+    if let Some(casing) = options.key_case {
+        recase_keys(value, casing);
+    }
+    
+    // After recasing, which can change the order
+    if options.sort_keys {
+        sort_keys(value);
+    }
+}
+
+/// Add `_`-prefixed metadata keys to the output
+/// 
+/// Objects get the keys added alongside their own; any other value is
+/// wrapped as `{"value": ..., <keys>}`.
+pub(crate) fn inject_fields(value: &mut Value, fields: Vec<(&str, Value)>) {
+    if !value.is_object() {
+        let inner = value.take();
+        *value = serde_json::json!({ "value": inner });
+    }
+    
+    if let Value::Object(map) = value {
+        for (key, field) in fields {
+            map.insert(key.to_string(), field);
+        }
+    }
+}
+
+/// Serialize output data, compact or pretty, optionally writing integers
+/// outside JavaScript's safe range as their decimal string
+pub(crate) fn to_json_string(value: &Value, pretty: bool, bignum_as_string: bool) -> Result<String, ParseError> {
+    let json = match (pretty, bignum_as_string) {
+        (true, true) => serde_json::to_string_pretty(&BignumsAsStrings(value)),
+        (true, false) => serde_json::to_string_pretty(value),
+        (false, true) => serde_json::to_string(&BignumsAsStrings(value)),
+        (false, false) => serde_json::to_string(value),
+    };
+    json.map_err(|e| ParseError::InvalidData(e.to_string()))
+}
+
+/// Serializes a value with unsafe integers written as strings
+/// 
+/// The digits come straight from the number, which `arbitrary_precision`
+/// keeps exact even beyond 64 bits.
+struct BignumsAsStrings<'a>(&'a Value);
+
+impl serde::Serialize for BignumsAsStrings<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::{SerializeMap, SerializeSeq};
+        
+        match self.0 {
+            Value::Number(n) if is_unsafe_integer(n) => serializer.serialize_str(&n.to_string()),
+            Value::Array(items) => {
+                let mut seq = serializer.serialize_seq(Some(items.len()))?;
+                for item in items {
+                    seq.serialize_element(&BignumsAsStrings(item))?;
+                }
+                seq.end()
+            }
+            Value::Object(map) => {
+                let mut object = serializer.serialize_map(Some(map.len()))?;
+                for (key, value) in map {
+                    object.serialize_entry(key, &BignumsAsStrings(value))?;
+                }
+                object.end()
+            }
+            other => other.serialize(serializer),
+        }
+    }
+}
+
+/// Whether `n` is an integer JavaScript can't represent exactly
+fn is_unsafe_integer(n: &serde_json::Number) -> bool {
+    match (n.as_u64(), n.as_i64()) {
+        (Some(v), _) => v > MAX_SAFE_INTEGER,
+        (None, Some(v)) => v.unsigned_abs() > MAX_SAFE_INTEGER,
+        // Wider than 64 bits, unless it's a float
+        _ => n.to_string().trim_start_matches('-').bytes().all(|b| b.is_ascii_digit()),
+    }
+}
+
+/// Turn `{"Variant": inner}` into `{"type": "Variant", "value": inner}`, innermost first
+/// 
+/// Type information is gone by now, so this goes by shape: any object with
+/// a single PascalCase key is taken to be a variant. Base58 pubkeys are
+/// excluded, but a struct renamed to one capitalized field, or a map with
+/// one such key, is still rewritten.
+fn tag_enums(value: &mut Value) {
+    match value {
+        Value::Array(items) => items.iter_mut().for_each(tag_enums),
+        Value::Object(map) => {
+            map.values_mut().for_each(tag_enums);
+            
+            let is_variant = map.len() == 1 && map.keys().all(|key| is_variant_name(key));
+            if is_variant {
+                let (variant, inner) = std::mem::take(map).into_iter().next().expect("map has one entry");
+                map.insert("type".to_string(), Value::String(variant));
+                map.insert("value".to_string(), inner);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Whether an object key could be an enum variant name
+fn is_variant_name(key: &str) -> bool {
+    key.starts_with(|c: char| c.is_ascii_uppercase())
+        && key.chars().all(|c| c.is_ascii_alphanumeric())
+        && crate::base58_to_pubkey(key).is_err()
+}
+
+/// Rewrite the object keys that could be field names to `casing`, recursively
+/// 
+/// By now struct fields and map entries are both JSON objects, so keys that
+/// can't be field names are left alone: anything that isn't an ASCII
+/// identifier, and base58 pubkeys. An object whose keys would collide once
+/// recased must be a map, and is left unchanged.
+fn recase_keys(value: &mut Value, casing: Casing) {
+    match value {
+        Value::Array(items) => items.iter_mut().for_each(|item| recase_keys(item, casing)),
+        Value::Object(map) => {
+            map.values_mut().for_each(|value| recase_keys(value, casing));
+            
+            let keys: Vec<String> = map.keys()
+                .map(|key| if is_field_name(key) { casing.convert(key) } else { key.clone() })
+                .collect();
+            if keys.iter().collect::<HashSet<_>>().len() < keys.len() {
+                return;
+            }
+            
+            *map = std::mem::take(map)
+                .into_iter()
+                .zip(keys)
+                .map(|((_, value), key)| (key, value))
+                .collect();
+        }
+        _ => {}
+    }
+}
+
+/// Whether an object key could be a field name rather than map data
+fn is_field_name(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars.next().is_some_and(|c| c == '_' || c.is_ascii_alphabetic())
+        && chars.all(|c| c == '_' || c.is_ascii_alphanumeric())
+        && crate::base58_to_pubkey(key).is_err()
+}
+
+/// Rebuild every object with its keys in sorted order, recursively
+/// 
+/// serde_json keeps insertion order when its `preserve_order` feature is
+/// enabled anywhere in the dependency graph, so this doesn't rely on the
+/// map type.
+fn sort_keys(value: &mut Value) {
+    match value {
+        Value::Array(items) => items.iter_mut().for_each(sort_keys),
+        Value::Object(map) => {
+            let mut entries: Vec<(String, Value)> = std::mem::take(map).into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            for (key, mut value) in entries {
+                sort_keys(&mut value);
+                map.insert(key, value);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use borsh::{BorshDeserialize, BorshSerialize};
+    use serde::Serialize;
+    use crate::parser::ParserBuilder;
+    use crate::testing::mock_account;
+    
+    const PROGRAM_ID: &str = "11111111111111111111111111111111";
+    
+    #[derive(Debug, PartialEq, BorshSerialize, BorshDeserialize, Serialize, serde::Deserialize)]
+    struct Wide {
+        big: u128,
+        signed: i128,
+    }
+    
+    fn account(data: Vec<u8>) -> SolanaAccount {
+        mock_account(PROGRAM_ID, data)
+    }
+    
+    fn parse_options(custom: &str) -> ParseOptions {
+        ParseOptions {
+            include_raw: false,
+            pretty_json: false,
+            custom: Some(custom.to_string()),
+        }
+    }
+    
+    #[test]
+    fn u128_max_round_trips_through_parse_account() {
+        let parser = ParserBuilder::new(PROGRAM_ID)
+            .register_account_with_discriminator::<Wide>("Wide", vec![1])
+            .build();
+        let wide = Wide { big: u128::MAX, signed: i128::MIN };
+        let mut data = vec![1];
+        data.extend(borsh::to_vec(&wide).unwrap());
+        
+        let parsed = parser.parse_account(&account(data)).unwrap();
+        assert_eq!(
+            parsed.data,
+            r#"{"big":340282366920938463463374607431768211455,"signed":-170141183460469231731687303715884105728}"#
+        );
+        assert_eq!(serde_json::from_str::<Wide>(&parsed.data).unwrap(), wide);
+    }
+    
+    #[test]
+    fn wide_integers_from_custom_parsers_are_not_rounded() {
+        let json = r#"{"big":340282366920938463463374607431768211455}"#;
+        let parser = ParserBuilder::new(PROGRAM_ID)
+            .register_custom_account("Custom", None, move |_| Ok(ParsedAccount {
+                account_type: "Custom".to_string(),
+                data: json.to_string(),
+                discriminator: None,
+            }))
+            .build();
+        
+        let parsed = parser.parse_account(&account(vec![0])).unwrap();
+        assert_eq!(parsed.data, json);
+    }
+    
+    #[test]
+    fn bignum_as_string_writes_wide_integers_exactly() {
+        let parser = ParserBuilder::new(PROGRAM_ID)
+            .register_account_with_discriminator::<Wide>("Wide", vec![1])
+            .build();
+        let options = parse_options(r#"{"bignum_as_string": true}"#);
+        let parse = |wide: &Wide| {
+            let mut data = vec![1];
+            data.extend(borsh::to_vec(wide).unwrap());
+            parser.parse_account_with_options(&account(data), &options).unwrap().data
+        };
+        
+        assert_eq!(
+            parse(&Wide { big: u128::MAX, signed: i128::MIN }),
+            r#"{"big":"340282366920938463463374607431768211455","signed":"-170141183460469231731687303715884105728"}"#
+        );
+        assert_eq!(
+            parse(&Wide { big: MAX_SAFE_INTEGER as u128 + 1, signed: -(MAX_SAFE_INTEGER as i128) }),
+            r#"{"big":"9007199254740992","signed":-9007199254740991}"#
+        );
+    }
+    
+    #[test]
+    fn bignum_as_string_leaves_floats_alone() {
+        let value = serde_json::json!({ "ratio": 1.5e300, "small": 3, "items": [u64::MAX] });
+        
+        assert_eq!(
+            to_json_string(&value, false, true).unwrap(),
+            r#"{"items":["18446744073709551615"],"ratio":1.5e300,"small":3}"#
+        );
+    }
+    
+    #[derive(BorshSerialize, BorshDeserialize, Serialize)]
+    struct Holdings {
+        total_shares: u64,
+        by_owner: std::collections::HashMap<String, u64>,
+    }
+    
+    #[test]
+    fn key_case_leaves_map_keys_alone() {
+        let parser = ParserBuilder::new(PROGRAM_ID)
+            .register_account_with_discriminator::<Holdings>("Holdings", vec![1])
+            .build();
+        let holdings = Holdings {
+            total_shares: 6,
+            by_owner: [
+                ("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v", 1),
+                ("some-label", 2),
+                ("2nd", 3),
+            ].into_iter().map(|(key, value)| (key.to_string(), value)).collect(),
+        };
+        let mut data = vec![1];
+        data.extend(borsh::to_vec(&holdings).unwrap());
+        
+        let parsed = parser.parse_account_with_options(&account(data), &parse_options(r#"{"key_case": "camel"}"#)).unwrap();
+        let data: Value = serde_json::from_str(&parsed.data).unwrap();
+        assert_eq!(data, serde_json::json!({
+            "totalShares": 6,
+            "byOwner": {
+                "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v": 1,
+                "some-label": 2,
+                "2nd": 3,
+            },
+        }));
+    }
+    
+    #[test]
+    fn key_case_skips_objects_whose_keys_would_collide() {
+        let mut value = serde_json::json!({ "outer_key": { "user_id": 1, "userId": 2 } });
+        recase_keys(&mut value, Casing::Camel);
+        
+        assert_eq!(value, serde_json::json!({ "outerKey": { "user_id": 1, "userId": 2 } }));
+    }
+    
+    #[derive(BorshSerialize, BorshDeserialize, Serialize)]
+    enum Action {
+        Swap { amount: u64 },
+    }
+    
+    #[derive(BorshSerialize, BorshDeserialize, Serialize)]
+    struct Order {
+        action: Action,
+        by_mint: std::collections::HashMap<String, u64>,
+    }
+    
+    #[test]
+    fn tagged_enums_skips_pubkeys_and_non_identifier_keys() {
+        let parser = ParserBuilder::new(PROGRAM_ID)
+            .register_account_with_discriminator::<Order>("Order", vec![1])
+            .build();
+        let order = Order {
+            action: Action::Swap { amount: 5 },
+            by_mint: [("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v".to_string(), 7)].into_iter().collect(),
+        };
+        let mut data = vec![1];
+        data.extend(borsh::to_vec(&order).unwrap());
+        
+        let parsed = parser.parse_account_with_options(&account(data), &parse_options(r#"{"tagged_enums": true}"#)).unwrap();
+        let data: Value = serde_json::from_str(&parsed.data).unwrap();
+        assert_eq!(data, serde_json::json!({
+            "action": { "type": "Swap", "value": { "amount": 5 } },
+            "by_mint": { "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v": 7 },
+        }));
+        
+        let mut value = serde_json::json!({ "wrapper": { "inner": 1 }, "label": { "Not-A-Variant": 2 } });
+        let expected = value.clone();
+        tag_enums(&mut value);
+        assert_eq!(value, expected);
+    }
+    
+    #[test]
+    fn tagged_enums_still_rewrites_a_map_with_one_pascal_case_key() {
+        // The documented limit: without types, `{"Usdc": 7}` looks like a variant
+        let mut value = serde_json::json!({ "balances": { "Usdc": 7 } });
+        tag_enums(&mut value);
+        
+        assert_eq!(value, serde_json::json!({ "balances": { "type": "Usdc", "value": 7 } }));
+    }
+}
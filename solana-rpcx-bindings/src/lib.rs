@@ -6,3 +6,61 @@ pub mod bindings;
 
 // Re-export everything so SDK and users can access it
 pub use bindings::*;
+
+use bindings::component::solana_rpcx_bindings::types::ParseError;
+
+/// Message prefix marking a closed account
+///
+/// The WIT `ParseError` has no closed-account variant, so the SDK reports an
+/// all-zero account as an `UnknownAccountType` whose message starts with this.
+pub const CLOSED_ACCOUNT_PREFIX: &str = "Closed account: ";
+
+/// Stable string codes for `ParseError` variants
+///
+/// Safe to switch on across the WIT boundary and in logs, unlike messages.
+pub mod error_code {
+    pub const UNKNOWN_ACCOUNT_TYPE: &str = "unknown_account_type";
+    pub const DESERIALIZATION_FAILED: &str = "deserialization_failed";
+    pub const INVALID_DATA: &str = "invalid_data";
+    pub const INSUFFICIENT_DATA: &str = "insufficient_data";
+    /// An `UnknownAccountType` starting with [`CLOSED_ACCOUNT_PREFIX`](super::CLOSED_ACCOUNT_PREFIX)
+    pub const CLOSED_ACCOUNT: &str = "closed_account";
+}
+
+impl ParseError {
+    /// Stable snake_case code for this variant; see [`error_code`]
+    ///
+    /// Closed accounts, which travel as `UnknownAccountType`, get
+    /// [`error_code::CLOSED_ACCOUNT`] instead.
+    ///
+    /// ```
+    /// use solana_rpcx_bindings::component::solana_rpcx_bindings::types::ParseError;
+    ///
+    /// let error = ParseError::InsufficientData("need 8 bytes".to_string());
+    /// assert_eq!(error.code(), "insufficient_data");
+    ///
+    /// let closed = ParseError::UnknownAccountType("Closed account: 9xQe".to_string());
+    /// assert_eq!(closed.code(), "closed_account");
+    /// ```
+    pub fn code(&self) -> &'static str {
+        match self {
+            ParseError::UnknownAccountType(msg) if msg.starts_with(CLOSED_ACCOUNT_PREFIX) => {
+                error_code::CLOSED_ACCOUNT
+            }
+            ParseError::UnknownAccountType(_) => error_code::UNKNOWN_ACCOUNT_TYPE,
+            ParseError::DeserializationFailed(_) => error_code::DESERIALIZATION_FAILED,
+            ParseError::InvalidData(_) => error_code::INVALID_DATA,
+            ParseError::InsufficientData(_) => error_code::INSUFFICIENT_DATA,
+        }
+    }
+
+    /// The message carried by any variant
+    pub fn message(&self) -> &str {
+        match self {
+            ParseError::UnknownAccountType(msg)
+            | ParseError::DeserializationFailed(msg)
+            | ParseError::InvalidData(msg)
+            | ParseError::InsufficientData(msg) => msg,
+        }
+    }
+}